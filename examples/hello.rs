@@ -11,8 +11,8 @@ use egui::{
 
 use egui_dock::tab_viewer::OnCloseResponse;
 use egui_dock::{
-    AllowedSplits, DockArea, DockState, NodeIndex, OverlayType, Style, SurfaceIndex,
-    TabInteractionStyle, TabViewer,
+    ActiveTabIndicator, AllowedSplits, DockArea, DockState, NodeIndex, OverlayType,
+    StandardActions, Style, SurfaceIndex, TabIndex, TabInteractionStyle, TabViewer,
 };
 
 /// Adds a widget with a label next to it, can be given an extra parameter in order to show a hover text
@@ -74,6 +74,9 @@ struct MyContext {
     show_add_buttons: bool,
     draggable_tabs: bool,
     show_tab_name_on_hover: bool,
+    standard_context_menu: bool,
+    hide_tab_bar_if_single: bool,
+    proportional_resize: bool,
     allowed_splits: AllowedSplits,
     show_leaf_close_all: bool,
     show_leaf_collapse: bool,
@@ -104,12 +107,13 @@ impl TabViewer for MyContext {
         }
     }
 
-    fn context_menu(
+    fn tab_context_menu(
         &mut self,
         ui: &mut Ui,
         tab: &mut Self::Tab,
         _surface: SurfaceIndex,
         _node: NodeIndex,
+        _tab_index: TabIndex,
     ) {
         match tab.as_str() {
             "Simple Demo" => self.simple_demo_menu(ui),
@@ -160,6 +164,18 @@ impl MyContext {
             ui.checkbox(&mut self.show_add_buttons, "Show add buttons");
             ui.checkbox(&mut self.draggable_tabs, "Draggable tabs");
             ui.checkbox(&mut self.show_tab_name_on_hover, "Show tab name on hover");
+            ui.checkbox(
+                &mut self.standard_context_menu,
+                "Standard context menu actions",
+            );
+            ui.checkbox(
+                &mut self.hide_tab_bar_if_single,
+                "Hide tab bar of leaves with a single tab",
+            );
+            ui.checkbox(
+                &mut self.proportional_resize,
+                "Resize all nodes next to a dragged separator",
+            );
             ui.checkbox(
                 &mut self.show_leaf_close_all,
                 "Show close all button on tab bars",
@@ -240,6 +256,20 @@ impl MyContext {
                 ui.add(Slider::new(&mut style.separator.extra, 1.0..=300.0));
                 ui.end_row();
 
+                ui.label("Min leaf width:");
+                ui.add(Slider::new(
+                    &mut style.separator.min_leaf_width,
+                    0.0..=300.0,
+                ));
+                ui.end_row();
+
+                ui.label("Min leaf height:");
+                ui.add(Slider::new(
+                    &mut style.separator.min_leaf_height,
+                    0.0..=300.0,
+                ));
+                ui.end_row();
+
                 ui.label("Idle color:");
                 color_edit_button_srgba(ui, &mut style.separator.color_idle, Alpha::OnlyBlend);
                 ui.end_row();
@@ -258,6 +288,7 @@ impl MyContext {
             ui.separator();
 
             ui.checkbox(&mut style.tab_bar.fill_tab_bar, "Expand tabs");
+            ui.checkbox(&mut style.tab_bar.equal_tab_width, "Equal tab widths");
             ui.checkbox(
                 &mut style.tab_bar.show_scroll_bar_on_overflow,
                 "Show scroll bar on tab overflow",
@@ -283,6 +314,88 @@ impl MyContext {
                     }
                 });
 
+            ComboBox::new("tab_bar_position", "Tab bar position")
+                .selected_text(format!("{:?}", style.tab_bar.position))
+                .show_ui(ui, |ui| {
+                    for position in [
+                        egui_dock::TabBarPosition::Top,
+                        egui_dock::TabBarPosition::Bottom,
+                        egui_dock::TabBarPosition::Left,
+                        egui_dock::TabBarPosition::Right,
+                    ] {
+                        ui.selectable_value(
+                            &mut style.tab_bar.position,
+                            position,
+                            format!("{position:?}"),
+                        );
+                    }
+                });
+            ui.checkbox(
+                &mut style.tab_bar.vertical_icons_only,
+                "Only show icons in vertical tab bars",
+            );
+
+            ComboBox::new("overflow_mode", "Tab overflow")
+                .selected_text(format!("{:?}", style.tab_bar.overflow_mode))
+                .show_ui(ui, |ui| {
+                    for mode in [
+                        egui_dock::OverflowMode::Scroll,
+                        egui_dock::OverflowMode::Menu,
+                        egui_dock::OverflowMode::Shrink,
+                    ] {
+                        ui.selectable_value(
+                            &mut style.tab_bar.overflow_mode,
+                            mode,
+                            format!("{mode:?}"),
+                        );
+                    }
+                });
+
+            let accent = ui.visuals().selection.bg_fill;
+            ComboBox::new("active_indicator", "Active tab indicator")
+                .selected_text(match style.tab.active_indicator {
+                    ActiveTabIndicator::Fill => "Fill",
+                    ActiveTabIndicator::Underline { .. } => "Underline",
+                    ActiveTabIndicator::Overline { .. } => "Overline",
+                    ActiveTabIndicator::Pill { .. } => "Pill",
+                })
+                .show_ui(ui, |ui| {
+                    for (indicator, name) in [
+                        (ActiveTabIndicator::Fill, "Fill"),
+                        (
+                            ActiveTabIndicator::Underline {
+                                color: accent,
+                                thickness: 2.0,
+                            },
+                            "Underline",
+                        ),
+                        (
+                            ActiveTabIndicator::Overline {
+                                color: accent,
+                                thickness: 2.0,
+                            },
+                            "Overline",
+                        ),
+                        (
+                            ActiveTabIndicator::Pill {
+                                color: accent,
+                                margin: 3.0,
+                            },
+                            "Pill",
+                        ),
+                    ] {
+                        ui.selectable_value(&mut style.tab.active_indicator, indicator, name);
+                    }
+                });
+
+            ComboBox::new("text_align", "Tab title align")
+                .selected_text(format!("{:?}", style.tab.text_align))
+                .show_ui(ui, |ui| {
+                    for align in [egui::Align::Min, egui::Align::Center, egui::Align::Max] {
+                        ui.selectable_value(&mut style.tab.text_align, align, format!("{align:?}"));
+                    }
+                });
+
             ui.separator();
 
             fn tab_style_editor_ui(ui: &mut Ui, tab_style: &mut TabInteractionStyle) {
@@ -568,6 +681,9 @@ impl Default for MyApp {
             show_add_buttons: false,
             draggable_tabs: true,
             show_tab_name_on_hover: false,
+            standard_context_menu: false,
+            hide_tab_bar_if_single: false,
+            proportional_resize: true,
             allowed_splits: AllowedSplits::default(),
         };
 
@@ -620,6 +736,13 @@ impl eframe::App for MyApp {
                     .show_add_buttons(self.context.show_add_buttons)
                     .draggable_tabs(self.context.draggable_tabs)
                     .show_tab_name_on_hover(self.context.show_tab_name_on_hover)
+                    .standard_context_menu(if self.context.standard_context_menu {
+                        StandardActions::ALL
+                    } else {
+                        StandardActions::NONE
+                    })
+                    .hide_tab_bar_if_single(self.context.hide_tab_bar_if_single)
+                    .proportional_resize(self.context.proportional_resize)
                     .allowed_splits(self.context.allowed_splits)
                     .show_leaf_close_all_buttons(self.context.show_leaf_close_all)
                     .show_leaf_collapse_buttons(self.context.show_leaf_collapse)
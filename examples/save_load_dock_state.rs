@@ -30,7 +30,7 @@ impl egui_dock::TabViewer for TabViewer {
         ui.label(format!("Content of {tab}"));
     }
 
-    fn on_rect_changed(&mut self, _tab: &mut Self::Tab) {
+    fn on_content_rect_changed(&mut self, _tab: &mut Self::Tab, _rect: egui::Rect) {
         self.modified = true
     }
 }
@@ -14,7 +14,7 @@ fn main() -> eframe::Result<()> {
 }
 
 struct TabViewer<'a> {
-    added_nodes: &'a mut Vec<(SurfaceIndex, NodeIndex)>,
+    counter: &'a mut usize,
 }
 
 impl egui_dock::TabViewer for TabViewer<'_> {
@@ -28,8 +28,9 @@ impl egui_dock::TabViewer for TabViewer<'_> {
         ui.label(format!("Content of tab {tab}"));
     }
 
-    fn on_add(&mut self, surface: SurfaceIndex, node: NodeIndex) {
-        self.added_nodes.push((surface, node));
+    fn on_add_tab(&mut self, _surface: SurfaceIndex, _node: NodeIndex) -> Option<Self::Tab> {
+        *self.counter += 1;
+        Some(*self.counter - 1)
     }
 }
 
@@ -55,7 +56,6 @@ impl Default for MyApp {
 
 impl eframe::App for MyApp {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
-        let mut added_nodes = Vec::new();
         DockArea::new(&mut self.tree)
             .show_add_buttons(true)
             .style({
@@ -66,14 +66,8 @@ impl eframe::App for MyApp {
             .show(
                 ctx,
                 &mut TabViewer {
-                    added_nodes: &mut added_nodes,
+                    counter: &mut self.counter,
                 },
             );
-
-        added_nodes.drain(..).for_each(|(surface, node)| {
-            self.tree.set_focused_node_and_surface((surface, node));
-            self.tree.push_to_focused_leaf(self.counter);
-            self.counter += 1;
-        });
     }
 }
@@ -0,0 +1,216 @@
+use crate::{DockState, LeafNode, Node, NodeIndex, Split, SurfaceIndex, TabIndex};
+
+/// Refers to a leaf edited by a [`DockEditor`].
+///
+/// Unlike a [`NodeIndex`], a handle keeps referring to the same leaf while other leaves are split,
+/// emptied or moved around by the editor. Handles are only valid within the [`DockState::edit`] call
+/// which created them.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct LeafHandle(usize);
+
+/// Performs several structural edits of a [`DockState`] in a row, see [`DockState::edit`].
+///
+/// Leaves which the edits leave without tabs, and windows left without leaves,
+/// are only removed once all edits are done, so that the layout doesn't reshuffle between them.
+pub struct DockEditor<'a, Tab> {
+    dock_state: &'a mut DockState<Tab>,
+
+    /// Where each leaf with a handle is, indexed by [`LeafHandle`].
+    leaves: Vec<(SurfaceIndex, NodeIndex)>,
+}
+
+impl<Tab> DockState<Tab> {
+    /// Performs the edits made by `f` with a [`DockEditor`], cleaning up the layout once at the end.
+    ///
+    /// Within `f`, leaves are referred to with [`LeafHandle`]s which stay valid throughout the edit.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use egui_dock::{DockState, NodeIndex, Split, SurfaceIndex};
+    /// let mut dock_state = DockState::new(vec!["a", "b"]);
+    /// dock_state.main_surface_mut().split_right(NodeIndex::root(), 0.5, vec!["c"]);
+    ///
+    /// dock_state.edit(|editor| {
+    ///     let (left, _) = editor.find_tab(|tab| *tab == "a").unwrap();
+    ///     let (right, _) = editor.find_tab(|tab| *tab == "c").unwrap();
+    ///
+    ///     // Empty the right leaf, then split it. It's only removed if it's still empty at the end.
+    ///     editor.move_tab((right, 0.into()), left);
+    ///     let below = editor.split(right, Split::Below, 0.5, vec!["d"]);
+    ///     editor.move_tab((below, 0.into()), right);
+    /// });
+    ///
+    /// let tree = dock_state.main_surface();
+    /// assert_eq!(tree[NodeIndex::root().left()].tabs(), Some(&["a", "b", "c"][..]));
+    /// assert_eq!(tree[NodeIndex::root().right()].tabs(), Some(&["d"][..]));
+    /// ```
+    pub fn edit(&mut self, f: impl FnOnce(&mut DockEditor<'_, Tab>)) {
+        let mut editor = DockEditor {
+            dock_state: self,
+            leaves: Vec::new(),
+        };
+        f(&mut editor);
+        editor.clean_up();
+    }
+}
+
+impl<Tab> DockEditor<'_, Tab> {
+    /// Returns the dock state being edited.
+    pub fn dock_state(&self) -> &DockState<Tab> {
+        self.dock_state
+    }
+
+    /// Returns a handle for the leaf at `surface` and `node`.
+    ///
+    /// # Panics
+    ///
+    /// If there's no leaf at that location.
+    pub fn leaf(&mut self, (surface, node): (SurfaceIndex, NodeIndex)) -> LeafHandle {
+        assert!(self.dock_state[surface][node].is_leaf());
+        let location = (surface, node);
+        match self.leaves.iter().position(|&leaf| leaf == location) {
+            Some(index) => LeafHandle(index),
+            None => self.insert_handle(location),
+        }
+    }
+
+    /// Finds the first tab for which `predicate` returns `true`,
+    /// returning a handle for its leaf and its position within it.
+    pub fn find_tab(&mut self, predicate: impl Fn(&Tab) -> bool) -> Option<(LeafHandle, TabIndex)> {
+        let dock_state = &*self.dock_state;
+        let (location, tab) = dock_state
+            .valid_surface_indices()
+            .iter()
+            .flat_map(|&surface| {
+                dock_state[surface]
+                    .breadth_first_index_iter()
+                    .map(move |node| (surface, node))
+            })
+            .find_map(|(surface, node)| {
+                let tab = dock_state[surface][node]
+                    .tabs()?
+                    .iter()
+                    .position(&predicate)?;
+                Some(((surface, node), TabIndex(tab)))
+            })?;
+        Some((self.leaf(location), tab))
+    }
+
+    /// Returns where the leaf is located at the moment.
+    ///
+    /// The location may change with the following edits, and once the editing is finished.
+    pub fn location(&self, leaf: LeafHandle) -> (SurfaceIndex, NodeIndex) {
+        self.leaves[leaf.0]
+    }
+
+    /// Returns the leaf behind a handle.
+    pub fn get(&self, leaf: LeafHandle) -> &LeafNode<Tab> {
+        let (surface, node) = self.location(leaf);
+        self.dock_state[surface][node]
+            .get_leaf()
+            .expect("handles always refer to leaves")
+    }
+
+    /// Returns the leaf behind a handle, mutably.
+    pub fn get_mut(&mut self, leaf: LeafHandle) -> &mut LeafNode<Tab> {
+        let (surface, node) = self.location(leaf);
+        self.dock_state[surface][node]
+            .get_leaf_mut()
+            .expect("handles always refer to leaves")
+    }
+
+    /// Adds a tab to the end of the leaf.
+    pub fn push_tab(&mut self, leaf: LeafHandle, tab: Tab) {
+        self.get_mut(leaf).append_tab(tab);
+    }
+
+    /// Inserts a tab into the leaf at the given `index`, moving the following tabs back.
+    ///
+    /// # Panics
+    ///
+    /// If `index` is greater than the number of tabs in the leaf.
+    pub fn insert_tab(&mut self, leaf: LeafHandle, index: TabIndex, tab: Tab) {
+        self.get_mut(leaf).insert_tab(index, tab);
+    }
+
+    /// Removes the tab at `index` from the leaf, moving the following tabs forward.
+    ///
+    /// The leaf is kept until the editing is finished, even if it has no more tabs.
+    pub fn remove_tab(&mut self, leaf: LeafHandle, index: TabIndex) -> Option<Tab> {
+        let leaf = self.get_mut(leaf);
+        (index.0 < leaf.tabs.len()).then(|| leaf.remove_tab(index).unwrap())
+    }
+
+    /// Moves a tab to the end of the `dst` leaf.
+    ///
+    /// # Panics
+    ///
+    /// If the leaf has no tab at that index.
+    pub fn move_tab(&mut self, (src, index): (LeafHandle, TabIndex), dst: LeafHandle) {
+        let tab = self
+            .remove_tab(src, index)
+            .expect("the moved tab must exist");
+        self.push_tab(dst, tab);
+    }
+
+    /// Creates a new leaf with the given `tabs` next to `leaf`, in the direction specified by `split`,
+    /// returning a handle for the new leaf.
+    ///
+    /// `fraction` (in range 0..=1) is the same as in [`Tree::split`](crate::Tree::split).
+    ///
+    /// # Panics
+    ///
+    /// If `fraction` isn't in range 0..=1, or if `tabs` is empty.
+    pub fn split(
+        &mut self,
+        leaf: LeafHandle,
+        split: Split,
+        fraction: f32,
+        tabs: Vec<Tab>,
+    ) -> LeafHandle {
+        assert!((0.0..=1.0).contains(&fraction));
+        let (surface, node) = self.location(leaf);
+        let [old, new] =
+            self.dock_state[surface].split(node, split, fraction, Node::leaf_with(tabs));
+        self.leaves[leaf.0] = (surface, old);
+        self.insert_handle((surface, new))
+    }
+
+    /// Creates a new window with the given `tabs`, returning a handle for its only leaf.
+    pub fn add_window(&mut self, tabs: Vec<Tab>) -> LeafHandle {
+        let surface = self.dock_state.add_window(tabs);
+        self.insert_handle((surface, NodeIndex::root()))
+    }
+
+    fn insert_handle(&mut self, location: (SurfaceIndex, NodeIndex)) -> LeafHandle {
+        self.leaves.push(location);
+        LeafHandle(self.leaves.len() - 1)
+    }
+
+    /// Removes the leaves left without tabs, and the windows left without leaves.
+    fn clean_up(self) {
+        let dock_state = self.dock_state;
+        for surface in dock_state.valid_surface_indices().iter().copied() {
+            let mut removed_any = false;
+            while let Some(node) = dock_state[surface]
+                .breadth_first_index_iter()
+                .find(|&node| {
+                    dock_state[surface][node].is_leaf()
+                        && dock_state[surface][node].tabs_count() == 0
+                })
+            {
+                dock_state[surface].remove_leaf(node);
+                removed_any = true;
+            }
+
+            // Removing leaves moves the others, so a maximized leaf could now point to a different one.
+            if removed_any && dock_state.maximized_leaf.is_some_and(|(s, _)| s == surface) {
+                dock_state.maximized_leaf = None;
+            }
+            if dock_state[surface].is_empty() && !surface.is_main() {
+                dock_state.remove_surface(surface);
+            }
+        }
+    }
+}
@@ -0,0 +1,51 @@
+use crate::{NodeIndex, SurfaceIndex, TabIndex};
+
+/// Why an edit of a [`DockState`](crate::DockState) or a [`Tree`](crate::Tree) couldn't be made.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum DockError {
+    /// There's no surface at this index, or it was removed.
+    InvalidSurface(SurfaceIndex),
+
+    /// There's no node at this index.
+    InvalidNode(NodeIndex),
+
+    /// The node at this index isn't a [`Leaf`](crate::Node::Leaf).
+    NotALeaf(NodeIndex),
+
+    /// The node at this index is the root, so it has no sibling.
+    NoSibling(NodeIndex),
+
+    /// The leaf at this index holds a tab which isn't [collapsible](crate::TabViewer::is_collapsible).
+    NotCollapsible(NodeIndex),
+
+    /// The leaf has no tab at this index.
+    TabIndexOutOfRange(TabIndex),
+
+    /// The main surface can't be removed.
+    MainSurfaceProtected,
+}
+
+impl std::fmt::Display for DockError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidSurface(surface) => {
+                write!(f, "there is no surface at index {}", surface.0)
+            }
+            Self::InvalidNode(node) => write!(f, "there is no node at index {}", node.0),
+            Self::NotALeaf(node) => write!(f, "the node at index {} is not a leaf", node.0),
+            Self::NoSibling(node) => write!(f, "the node at index {} has no sibling", node.0),
+            Self::NotCollapsible(node) => {
+                write!(
+                    f,
+                    "the leaf at index {} has a tab which can't be collapsed",
+                    node.0
+                )
+            }
+            Self::TabIndexOutOfRange(tab) => write!(f, "there is no tab at index {}", tab.0),
+            Self::MainSurfaceProtected => write!(f, "the main surface can't be removed"),
+        }
+    }
+}
+
+impl std::error::Error for DockError {}
@@ -0,0 +1,208 @@
+use crate::{DockState, Node, NodeIndex, Split, TabIndex, Tree};
+
+/// Describes a layout before it's turned into a [`Tree`].
+#[derive(Clone, Debug)]
+enum Layout<Tab> {
+    Leaf(Vec<Tab>, TabIndex),
+    Split {
+        split: Split,
+        fraction: f32,
+        old: Box<Layout<Tab>>,
+        new: Box<Layout<Tab>>,
+    },
+}
+
+/// Declaratively constructs a [`DockState`] or a [`Tree`] with nested splits,
+/// without the need to keep track of [`NodeIndex`]es.
+///
+/// Start with [`leaf`](Self::leaf), then split the layout built so far with the [`split`](Self::split) family of
+/// functions, each of which builds the new part of the layout with its own closure.
+///
+/// # Example
+///
+/// ```rust
+/// # use egui_dock::{DockLayoutBuilder, NodeIndex, SurfaceIndex};
+/// // A leaf with "a" and "b" taking 70% of the width,
+/// // and a column with "c" above "d" to the right of it.
+/// let dock_state = DockLayoutBuilder::new()
+///     .leaf(vec!["a", "b"])
+///     .split_right(0.7, |b| b.leaf(vec!["c"]).split_below(0.5, |b| b.leaf(vec!["d"])))
+///     .build();
+///
+/// let tree = dock_state.main_surface();
+/// assert!(tree[NodeIndex::root()].is_horizontal());
+/// assert_eq!(tree[NodeIndex::root().left()].tabs(), Some(&["a", "b"][..]));
+/// assert!(tree[NodeIndex::root().right()].is_vertical());
+/// assert_eq!(tree.find_tab(&"d"), Some((NodeIndex::root().right().right(), 0.into())));
+/// ```
+#[derive(Clone, Debug)]
+pub struct DockLayoutBuilder<Tab> {
+    layout: Option<Layout<Tab>>,
+}
+
+impl<Tab> Default for DockLayoutBuilder<Tab> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<Tab> DockLayoutBuilder<Tab> {
+    /// Creates a new, empty layout builder.
+    pub const fn new() -> Self {
+        Self { layout: None }
+    }
+
+    /// Sets the layout to a single leaf containing the given `tabs`, replacing anything built so far.
+    pub fn leaf(self, tabs: Vec<Tab>) -> Self {
+        self.leaf_with_active(tabs, TabIndex(0))
+    }
+
+    /// Same as [`leaf`](Self::leaf), but the tab at `active` is active instead of the first one.
+    ///
+    /// If `active` is out of bounds, the last tab is active.
+    ///
+    /// ```rust
+    /// # use egui_dock::{DockLayoutBuilder, NodeIndex, SurfaceIndex, TabIndex};
+    /// let dock_state = DockLayoutBuilder::new()
+    ///     .leaf_with_active(vec!["a", "b", "c"], 1)
+    ///     .split_right(0.5, |b| b.leaf_with_active(vec!["d", "e"], 5))
+    ///     .build();
+    ///
+    /// let left = (SurfaceIndex::main(), NodeIndex::root().left());
+    /// let right = (SurfaceIndex::main(), NodeIndex::root().right());
+    /// assert_eq!(dock_state.active_tab(left), Some(TabIndex(1)));
+    /// assert_eq!(dock_state.active_tab(right), Some(TabIndex(1)));
+    /// ```
+    pub fn leaf_with_active(mut self, tabs: Vec<Tab>, active: impl Into<TabIndex>) -> Self {
+        self.layout = Some(Layout::Leaf(tabs, active.into()));
+        self
+    }
+
+    /// Splits the layout built so far, placing the layout built by `new` relatively to it,
+    /// in the direction specified by `split`.
+    ///
+    /// `fraction` (in range 0..=1) specifies how much of the area the layout built so far will occupy after the
+    /// split, the same as in [`Tree::split`].
+    ///
+    /// # Panics
+    ///
+    /// If `fraction` isn't in range 0..=1.
+    ///
+    /// If nothing was built before calling this function, or if `new` doesn't build anything.
+    pub fn split(
+        mut self,
+        split: Split,
+        fraction: f32,
+        new: impl FnOnce(DockLayoutBuilder<Tab>) -> DockLayoutBuilder<Tab>,
+    ) -> Self {
+        assert!((0.0..=1.0).contains(&fraction));
+        let old = self
+            .layout
+            .take()
+            .expect("There must be a layout to split, start with `leaf`");
+        let new = new(DockLayoutBuilder::new())
+            .layout
+            .expect("The new part of the split must not be empty, start with `leaf`");
+        self.layout = Some(Layout::Split {
+            split,
+            fraction,
+            old: Box::new(old),
+            new: Box::new(new),
+        });
+        self
+    }
+
+    /// Splits the layout built so far, placing the layout built by `new` *above* it.
+    ///
+    /// This is a shorthand for using [`split`](Self::split) with [`Split::Above`].
+    #[inline(always)]
+    pub fn split_above(
+        self,
+        fraction: f32,
+        new: impl FnOnce(DockLayoutBuilder<Tab>) -> DockLayoutBuilder<Tab>,
+    ) -> Self {
+        self.split(Split::Above, fraction, new)
+    }
+
+    /// Splits the layout built so far, placing the layout built by `new` *below* it.
+    ///
+    /// This is a shorthand for using [`split`](Self::split) with [`Split::Below`].
+    #[inline(always)]
+    pub fn split_below(
+        self,
+        fraction: f32,
+        new: impl FnOnce(DockLayoutBuilder<Tab>) -> DockLayoutBuilder<Tab>,
+    ) -> Self {
+        self.split(Split::Below, fraction, new)
+    }
+
+    /// Splits the layout built so far, placing the layout built by `new` to the *left* of it.
+    ///
+    /// This is a shorthand for using [`split`](Self::split) with [`Split::Left`].
+    #[inline(always)]
+    pub fn split_left(
+        self,
+        fraction: f32,
+        new: impl FnOnce(DockLayoutBuilder<Tab>) -> DockLayoutBuilder<Tab>,
+    ) -> Self {
+        self.split(Split::Left, fraction, new)
+    }
+
+    /// Splits the layout built so far, placing the layout built by `new` to the *right* of it.
+    ///
+    /// This is a shorthand for using [`split`](Self::split) with [`Split::Right`].
+    #[inline(always)]
+    pub fn split_right(
+        self,
+        fraction: f32,
+        new: impl FnOnce(DockLayoutBuilder<Tab>) -> DockLayoutBuilder<Tab>,
+    ) -> Self {
+        self.split(Split::Right, fraction, new)
+    }
+
+    /// Builds a [`Tree`] out of the layout.
+    ///
+    /// If nothing was built, the tree will contain a single empty leaf.
+    pub fn build_tree(self) -> Tree<Tab> {
+        let mut tree = Tree::new(Vec::new());
+        if let Some(layout) = self.layout {
+            Self::place(&mut tree, NodeIndex::root(), layout);
+        }
+        tree
+    }
+
+    /// Builds a [`DockState`] whose main surface contains the layout.
+    ///
+    /// If nothing was built, the main surface will contain a single empty leaf.
+    pub fn build(self) -> DockState<Tab> {
+        let mut dock_state = DockState::new(Vec::new());
+        *dock_state.main_surface_mut() = self.build_tree();
+        dock_state
+    }
+
+    fn place(tree: &mut Tree<Tab>, index: NodeIndex, layout: Layout<Tab>) {
+        // Keep the binary tree filled up to the level of the node being placed.
+        let len = (1 << (index.level() + 1)) - 1;
+        if tree.nodes.len() < len {
+            tree.nodes.resize_with(len, || Node::Empty);
+        }
+
+        match layout {
+            Layout::Leaf(tabs, active) => tree[index] = Node::leaf_with_active(tabs, active),
+            Layout::Split {
+                split,
+                fraction,
+                old,
+                new,
+            } => {
+                tree[index].split(split, fraction);
+                let [old_index, new_index] = match split {
+                    Split::Left | Split::Above => [index.right(), index.left()],
+                    Split::Right | Split::Below => [index.left(), index.right()],
+                };
+                Self::place(tree, old_index, *old);
+                Self::place(tree, new_index, *new);
+            }
+        }
+    }
+}
@@ -0,0 +1,186 @@
+use std::collections::VecDeque;
+
+use crate::{DockState, Surface, SurfaceIndex};
+
+/// Earlier and undone layouts of a [`DockState`], see [`DockState::set_layout_history`].
+#[derive(Clone, Debug)]
+pub(crate) struct LayoutHistory<Tab> {
+    depth: usize,
+
+    /// The layout as of the last step.
+    current: Layout<Tab>,
+    undo: VecDeque<Layout<Tab>>,
+    redo: Vec<Layout<Tab>>,
+
+    /// Set when a layout was restored, so that the change isn't recorded as a new step.
+    restored: bool,
+
+    /// Copies the surfaces, which is only possible for tabs implementing [`Clone`].
+    clone: fn(&[Surface<Tab>]) -> Vec<Surface<Tab>>,
+}
+
+/// How the layout of a [`DockState`] changed since the last frame, see [`DockState::record_layout`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum LayoutChange {
+    /// Nothing the history keeps track of changed.
+    None,
+
+    /// Only separators moved or the focus changed, which updates the last step.
+    Adjusted,
+
+    /// Tabs, surfaces or splits were added, moved or removed, which is a new step.
+    Restructured,
+}
+
+/// One step of a [`LayoutHistory`], with the tabs it had.
+#[derive(Clone, Debug)]
+struct Layout<Tab> {
+    surfaces: Vec<Surface<Tab>>,
+    focused_surface: Option<SurfaceIndex>,
+}
+
+impl<Tab> DockState<Tab> {
+    /// Keeps up to `depth` earlier layouts to go back to with [`undo_layout`](Self::undo_layout), or turns the history
+    /// off and forgets it if `depth` is 0.
+    ///
+    /// Each frame in which a [`DockArea`](crate::DockArea) finds that tabs were added, moved or closed, or that
+    /// surfaces or splits were added or removed, records a step, whether the change was made by the area or through
+    /// this [`DockState`]. Moving separators, switching tabs and focusing leaves alone aren't steps of their own.
+    ///
+    /// Every step keeps a copy of the tabs it had, so undoing brings back closed tabs too.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use egui_dock::DockState;
+    /// let mut dock_state = DockState::new(vec!["a".to_owned()]);
+    /// dock_state.set_layout_history(20);
+    ///
+    /// // Later, e.g. when Ctrl+Z is pressed:
+    /// if dock_state.can_undo_layout() {
+    ///     dock_state.undo_layout();
+    /// }
+    /// ```
+    pub fn set_layout_history(&mut self, depth: usize)
+    where
+        Tab: Clone,
+    {
+        if depth == 0 {
+            self.layout_history = None;
+            return;
+        }
+        match &mut self.layout_history {
+            Some(history) => {
+                history.depth = depth;
+                history.undo.truncate(depth);
+            }
+            None => {
+                self.layout_history = Some(Box::new(LayoutHistory {
+                    depth,
+                    current: Layout {
+                        surfaces: self.surfaces.clone(),
+                        focused_surface: self.focused_surface,
+                    },
+                    undo: VecDeque::new(),
+                    redo: Vec::new(),
+                    restored: false,
+                    clone: <[Surface<Tab>]>::to_vec,
+                }))
+            }
+        }
+    }
+
+    /// Returns whether there's an earlier layout to go back to with [`undo_layout`](Self::undo_layout).
+    pub fn can_undo_layout(&self) -> bool {
+        self.layout_history
+            .as_ref()
+            .is_some_and(|history| !history.undo.is_empty())
+    }
+
+    /// Returns whether there's an undone layout to go back to with [`redo_layout`](Self::redo_layout).
+    pub fn can_redo_layout(&self) -> bool {
+        self.layout_history
+            .as_ref()
+            .is_some_and(|history| !history.redo.is_empty())
+    }
+
+    /// Goes back to the layout before the last step of the history, see [`set_layout_history`](Self::set_layout_history).
+    ///
+    /// Returns `false` if there's no such layout.
+    pub fn undo_layout(&mut self) -> bool {
+        let Some(history) = self.layout_history.as_mut() else {
+            return false;
+        };
+        let Some(layout) = history.undo.pop_front() else {
+            return false;
+        };
+        let current = std::mem::replace(&mut history.current, layout);
+        history.redo.push(current);
+        self.restore_layout();
+        true
+    }
+
+    /// Goes forward to the layout undone last with [`undo_layout`](Self::undo_layout).
+    ///
+    /// Returns `false` if there's no such layout, which is also the case once a new step was recorded.
+    pub fn redo_layout(&mut self) -> bool {
+        let Some(history) = self.layout_history.as_mut() else {
+            return false;
+        };
+        let Some(layout) = history.redo.pop() else {
+            return false;
+        };
+        let current = std::mem::replace(&mut history.current, layout);
+        history.undo.push_front(current);
+        self.restore_layout();
+        true
+    }
+
+    /// Records how the current layout changed since the last frame, once per frame the history is checked.
+    pub(crate) fn record_layout(&mut self, change: LayoutChange) {
+        let Some(history) = self.layout_history.as_mut() else {
+            return;
+        };
+        // A restored layout already is the current one of the history, even if it didn't change anything.
+        if std::mem::take(&mut history.restored) {
+            return;
+        }
+        match change {
+            LayoutChange::None => (),
+            LayoutChange::Adjusted => {
+                let current = &mut history.current;
+                for (surface, other) in current.surfaces.iter_mut().zip(&self.surfaces) {
+                    if let (Some(tree), Some(other)) = (surface.node_tree_mut(), other.node_tree())
+                    {
+                        tree.adjust_to(other);
+                    }
+                }
+                current.focused_surface = self.focused_surface;
+            }
+            LayoutChange::Restructured => {
+                let layout = Layout {
+                    surfaces: (history.clone)(&self.surfaces),
+                    focused_surface: self.focused_surface,
+                };
+                let previous = std::mem::replace(&mut history.current, layout);
+                history.undo.push_front(previous);
+                history.undo.truncate(history.depth);
+                history.redo.clear();
+            }
+        }
+    }
+
+    /// Returns whether [`set_layout_history`](Self::set_layout_history) turned the history on.
+    pub(crate) fn has_layout_history(&self) -> bool {
+        self.layout_history.is_some()
+    }
+
+    /// Replaces the layout with the current one of the history.
+    fn restore_layout(&mut self) {
+        let history = self.layout_history.as_mut().unwrap();
+        history.restored = true;
+        self.surfaces = (history.clone)(&history.current.surfaces);
+        self.focused_surface = history.current.focused_surface;
+        self.maximized_leaf = None;
+    }
+}
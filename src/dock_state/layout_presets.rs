@@ -0,0 +1,190 @@
+use egui::Id;
+
+use crate::{DockState, Node, TabViewer};
+
+/// Named snapshots of the layout of a [`DockState`], to switch between workspaces such as "Debug" and "Editing".
+///
+/// A preset only stores the structure of a layout, with the [`TabViewer::id`] of each tab in its place.
+/// Restoring it moves the open tabs with those ids back into it, so they keep their state.
+/// With the `serde` feature, presets can be (de)serialized alongside the [`DockState`].
+///
+/// # Examples
+///
+/// ```rust
+/// # use egui_dock::{DockState, LayoutPresets, NodeIndex, TabViewer};
+/// # use egui::{Ui, WidgetText};
+/// struct MyTabViewer;
+///
+/// impl TabViewer for MyTabViewer {
+///     type Tab = String;
+///
+///     fn title(&mut self, tab: &mut String) -> WidgetText {
+///         tab.as_str().into()
+///     }
+///
+///     fn ui(&mut self, ui: &mut Ui, tab: &mut String) {
+///         ui.label(tab.as_str());
+///     }
+/// }
+///
+/// let mut dock_state = DockState::new(vec!["code".to_owned(), "console".to_owned()]);
+/// let mut presets = LayoutPresets::new();
+/// presets.save("Editing", &mut dock_state, &mut MyTabViewer);
+///
+/// // Move the console below the code for debugging.
+/// let console = dock_state.main_surface_mut()[NodeIndex::root()]
+///     .remove_tab(1.into())
+///     .unwrap();
+/// dock_state
+///     .main_surface_mut()
+///     .split_below(NodeIndex::root(), 0.7, vec![console]);
+/// presets.save("Debug", &mut dock_state, &mut MyTabViewer);
+///
+/// assert!(presets.restore("Editing", &mut dock_state, &mut MyTabViewer));
+/// assert_eq!(dock_state.main_surface().num_leaves(), 1);
+/// assert!(presets.restore("Debug", &mut dock_state, &mut MyTabViewer));
+/// assert_eq!(dock_state.main_surface().num_leaves(), 2);
+/// ```
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct LayoutPresets {
+    presets: Vec<(String, DockState<Id>)>,
+}
+
+impl LayoutPresets {
+    /// Creates an empty collection of presets.
+    pub const fn new() -> Self {
+        Self {
+            presets: Vec::new(),
+        }
+    }
+
+    /// Saves the current layout of `dock_state` under `name`, replacing any preset with the same name.
+    pub fn save<Tab>(
+        &mut self,
+        name: impl Into<String>,
+        dock_state: &mut DockState<Tab>,
+        tab_viewer: &mut impl TabViewer<Tab = Tab>,
+    ) {
+        let name = name.into();
+        let structure = dock_state.structure(tab_viewer);
+        match self.presets.iter_mut().find(|(preset, _)| *preset == name) {
+            Some((_, layout)) => *layout = structure,
+            None => self.presets.push((name, structure)),
+        }
+    }
+
+    /// Rearranges the tabs of `dock_state` into the preset saved under `name`.
+    ///
+    /// Open tabs which aren't part of the preset are added to the first leaf of the main surface,
+    /// and the tabs of the preset which aren't open anymore are left out.
+    ///
+    /// Returns `false` if there's no preset with that name.
+    pub fn restore<Tab>(
+        &self,
+        name: &str,
+        dock_state: &mut DockState<Tab>,
+        tab_viewer: &mut impl TabViewer<Tab = Tab>,
+    ) -> bool {
+        let Some((_, layout)) = self.presets.iter().find(|(preset, _)| preset == name) else {
+            return false;
+        };
+        dock_state.rearrange(layout, Vec::new(), tab_viewer);
+        true
+    }
+
+    /// Removes the preset saved under `name`, returning whether there was one.
+    pub fn remove(&mut self, name: &str) -> bool {
+        let len = self.presets.len();
+        self.presets.retain(|(preset, _)| preset != name);
+        self.presets.len() != len
+    }
+
+    /// Returns whether a preset is saved under `name`.
+    pub fn contains(&self, name: &str) -> bool {
+        self.presets.iter().any(|(preset, _)| preset == name)
+    }
+
+    /// Returns the names of the presets, in the order they were first saved in.
+    pub fn names(&self) -> impl Iterator<Item = &str> + '_ {
+        self.presets.iter().map(|(name, _)| name.as_str())
+    }
+
+    /// Returns how many presets are saved.
+    pub fn len(&self) -> usize {
+        self.presets.len()
+    }
+
+    /// Returns `true` if no preset is saved.
+    pub fn is_empty(&self) -> bool {
+        self.presets.is_empty()
+    }
+}
+
+impl<Tab> DockState<Tab> {
+    /// Replaces the layout with `default`, such as the one the application starts with.
+    ///
+    /// Tabs are told apart by [`TabViewer::id`]: the open tabs with the same id as a tab of `default`
+    /// take its place, so they keep their state. Open tabs which aren't part of `default`
+    /// are added to the first leaf of the main surface.
+    pub fn reset_layout(
+        &mut self,
+        mut default: DockState<Tab>,
+        tab_viewer: &mut impl TabViewer<Tab = Tab>,
+    ) {
+        let structure = default.structure(tab_viewer);
+        let fallback = default.take_tabs(tab_viewer);
+        self.rearrange(&structure, fallback, tab_viewer);
+    }
+
+    /// Returns the layout with the [`TabViewer::id`] of each tab in its place.
+    fn structure(&mut self, tab_viewer: &mut impl TabViewer<Tab = Tab>) -> DockState<Id> {
+        let ids: Vec<Id> = self
+            .iter_all_tabs_mut()
+            .map(|(_, tab)| tab_viewer.id(tab))
+            .collect();
+        // Both go through the tabs in the same order.
+        let mut ids = ids.into_iter();
+        self.map_tabs(|_| ids.next().expect("every tab has an id"))
+    }
+
+    /// Moves all tabs out of their leaves, along with their [`TabViewer::id`].
+    fn take_tabs(&mut self, tab_viewer: &mut impl TabViewer<Tab = Tab>) -> Vec<(Id, Tab)> {
+        let mut tabs = Vec::new();
+        for (_, node) in self.iter_all_nodes_mut() {
+            if let Node::Leaf(leaf) = node {
+                for mut tab in leaf.tabs.drain(..) {
+                    tabs.push((tab_viewer.id(&mut tab), tab));
+                }
+            }
+        }
+        tabs
+    }
+
+    /// Rebuilds `layout` out of the open tabs, or out of the `fallback` tabs for those which aren't open.
+    fn rearrange(
+        &mut self,
+        layout: &DockState<Id>,
+        mut fallback: Vec<(Id, Tab)>,
+        tab_viewer: &mut impl TabViewer<Tab = Tab>,
+    ) {
+        let mut open = self.take_tabs(tab_viewer);
+        let take = |tabs: &mut Vec<(Id, Tab)>, id: &Id| {
+            let index = tabs.iter().position(|(tab_id, _)| tab_id == id)?;
+            Some(tabs.remove(index).1)
+        };
+        let mut rearranged =
+            layout.filter_map_tabs(|id| take(&mut open, id).or_else(|| take(&mut fallback, id)));
+
+        // Leaves may have lost the tabs which aren't open anymore.
+        for (_, leaf) in rearranged.iter_leaves_mut() {
+            leaf.active.0 = leaf.active.0.min(leaf.tabs.len().saturating_sub(1));
+        }
+        for (_, tab) in open {
+            rearranged.push_to_first_leaf(tab);
+        }
+        rearranged.translations = self.translations.clone();
+        rearranged.layout_history = self.layout_history.take();
+        *self = rearranged;
+    }
+}
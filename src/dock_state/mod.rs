@@ -1,6 +1,15 @@
 /// Wrapper around indices to the collection of surfaces inside a [`DockState`].
 pub mod surface_index;
 
+/// Declarative construction of layouts made of nested splits.
+pub mod layout_builder;
+
+/// Named snapshots of layouts to switch between.
+pub mod layout_presets;
+
+/// Undoing and redoing changes of the layout.
+mod layout_history;
+
 pub mod tree;
 
 /// Represents an area in which a dock tree is rendered.
@@ -10,14 +19,33 @@ pub mod translations;
 /// Window states which tells floating tabs how to be displayed inside their window,
 pub mod window_state;
 
+/// Callbacks for going through the whole structure of a [`DockState`].
+pub mod visitor;
+
+/// Several structural edits of a [`DockState`] with a single clean-up at the end.
+pub mod editor;
+
+/// Errors returned by the fallible edits of a [`DockState`] or a [`Tree`].
+pub mod error;
+
+pub use editor::{DockEditor, LeafHandle};
+pub use error::DockError;
+pub use layout_builder::DockLayoutBuilder;
+pub(crate) use layout_history::LayoutChange;
+use layout_history::LayoutHistory;
+pub use layout_presets::LayoutPresets;
 pub use surface::Surface;
 pub use surface_index::SurfaceIndex;
 use tree::node::LeafNode;
+pub use visitor::{DockVisitor, DockVisitorMut};
 pub use window_state::WindowState;
 
-use egui::Rect;
+use egui::{Pos2, Rect, Vec2};
 
-use crate::{Node, NodeIndex, Split, TabDestination, TabIndex, TabInsert, Translations, Tree};
+use crate::{
+    tab_viewer::OnCloseResponse, DropKind, Node, NodeIndex, Split, Style, TabDestination, TabIndex,
+    TabInsert, TabViewer, Translations, Tree,
+};
 
 /// The heart of `egui_dock`.
 ///
@@ -32,6 +60,18 @@ pub struct DockState<Tab> {
     surfaces: Vec<Surface<Tab>>,
     focused_surface: Option<SurfaceIndex>, // Part of the tree which is in focus.
 
+    /// Leaf temporarily filling the whole surface it's on.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) maximized_leaf: Option<(SurfaceIndex, NodeIndex)>,
+
+    /// What dropping the tabs dragged in the last frame would do.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) drop_kind: Option<DropKind>,
+
+    /// Earlier layouts to go back to, see [`DockState::set_layout_history`].
+    #[cfg_attr(feature = "serde", serde(skip, default = "Option::default"))]
+    pub(crate) layout_history: Option<Box<LayoutHistory<Tab>>>,
+
     /// Contains translations of text shown in [`DockArea`](crate::DockArea).
     pub translations: Translations,
 }
@@ -68,6 +108,9 @@ impl<Tab> DockState<Tab> {
         Self {
             surfaces: vec![Surface::Main(Tree::new(tabs))],
             focused_surface: None,
+            maximized_leaf: None,
+            drop_kind: None,
+            layout_history: None,
             translations: Translations::english(),
         }
     }
@@ -123,6 +166,55 @@ impl<Tab> DockState<Tab> {
         }
     }
 
+    /// Returns whether the window at `surface` is collapsed down to its title bar, as with the buttons shown by
+    /// [`DockArea::show_window_collapse_buttons`](crate::DockArea::show_window_collapse_buttons).
+    ///
+    /// Returns `None` if the surface isn't a [`Window`](Surface::Window).
+    pub fn is_window_collapsed(&self, surface: SurfaceIndex) -> Option<bool> {
+        match self.surfaces.get(surface.0)? {
+            Surface::Window(_, state) => Some(state.is_minimized()),
+            _ => None,
+        }
+    }
+
+    /// Collapses the window at `surface` down to its title bar, or expands it again.
+    ///
+    /// Does nothing if the surface isn't a [`Window`](Surface::Window).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use egui_dock::DockState;
+    /// let mut dock_state = DockState::new(vec!["Main Tab".to_string()]);
+    /// let window = dock_state.add_window(vec!["Window Tab".to_string()]);
+    ///
+    /// dock_state.set_window_collapsed(window, true);
+    /// assert_eq!(dock_state.is_window_collapsed(window), Some(true));
+    /// assert_eq!(dock_state.is_window_collapsed(egui_dock::SurfaceIndex::main()), None);
+    /// ```
+    pub fn set_window_collapsed(&mut self, surface: SurfaceIndex, collapsed: bool) {
+        let Some(Surface::Window(tree, state)) = self.surfaces.get_mut(surface.0) else {
+            return;
+        };
+        if state.is_minimized() == collapsed {
+            return;
+        }
+
+        if tree.root_node().is_some_and(|node| node.is_collapsed()) {
+            // The window is already fully collapsed,
+            // so `expanded_height` has already been set.
+            // We don't need to set `new` either.
+        } else if !collapsed {
+            state.set_new(true);
+        } else if let Some(rect) = tree.root_node().and_then(Node::rect) {
+            // A window which wasn't shown yet has no height to go back to.
+            if rect.is_positive() {
+                state.set_expanded_height(rect.height());
+            }
+        }
+        state.toggle_minimized();
+    }
+
     /// Returns the viewport [`Rect`] and the `Tab` inside the focused leaf node or `None` if no node is in focus.
     #[inline]
     pub fn find_active_focused(&mut self) -> Option<(Rect, &mut Tab)> {
@@ -171,7 +263,12 @@ impl<Tab> DockState<Tab> {
     pub fn remove_surface(&mut self, surface_index: SurfaceIndex) -> Option<Surface<Tab>> {
         assert!(!surface_index.is_main());
         (surface_index.0 < self.surfaces.len()).then(|| {
-            self.focused_surface = Some(SurfaceIndex::main());
+            if self.focused_surface == Some(surface_index) {
+                self.focused_surface = Some(SurfaceIndex::main());
+            }
+            if self.maximized_leaf.is_some_and(|(s, _)| s == surface_index) {
+                self.maximized_leaf = None;
+            }
             if surface_index.0 == self.surfaces.len() - 1 {
                 self.surfaces.pop().unwrap()
             } else {
@@ -181,6 +278,295 @@ impl<Tab> DockState<Tab> {
         })
     }
 
+    /// Same as [`remove_surface`](Self::remove_surface), but tells why nothing was removed instead of panicking
+    /// or returning [`None`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use egui_dock::{DockError, DockState, SurfaceIndex};
+    /// let mut dock_state = DockState::new(vec!["a"]);
+    /// let window = dock_state.add_window(vec!["b"]);
+    ///
+    /// assert_eq!(
+    ///     dock_state.try_remove_surface(SurfaceIndex::main()).unwrap_err(),
+    ///     DockError::MainSurfaceProtected
+    /// );
+    /// assert!(dock_state.try_remove_surface(window).is_ok());
+    /// assert_eq!(
+    ///     dock_state.try_remove_surface(window).unwrap_err(),
+    ///     DockError::InvalidSurface(window)
+    /// );
+    /// ```
+    pub fn try_remove_surface(
+        &mut self,
+        surface_index: SurfaceIndex,
+    ) -> Result<Surface<Tab>, DockError> {
+        if surface_index.is_main() {
+            return Err(DockError::MainSurfaceProtected);
+        }
+        if !self.is_surface_valid(surface_index) {
+            return Err(DockError::InvalidSurface(surface_index));
+        }
+        Ok(self
+            .remove_surface(surface_index)
+            .expect("the surface was checked to exist"))
+    }
+
+    /// Removes a window, handing its tabs back instead of dropping them.
+    ///
+    /// Returns an empty `Vec` without removing anything if `surface_index` is the main surface
+    /// or doesn't refer to a window.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use egui_dock::DockState;
+    /// let mut dock_state = DockState::new(vec!["a"]);
+    /// let window = dock_state.add_window(vec!["b", "c"]);
+    ///
+    /// assert_eq!(dock_state.remove_surface_tabs(window), vec!["b", "c"]);
+    /// assert!(!dock_state.is_surface_valid(window));
+    /// ```
+    pub fn remove_surface_tabs(&mut self, surface_index: SurfaceIndex) -> Vec<Tab> {
+        if surface_index.is_main() || !self.is_surface_valid(surface_index) {
+            return Vec::new();
+        }
+        let Some(Surface::Window(tree, _)) = self.remove_surface(surface_index) else {
+            return Vec::new();
+        };
+        tree.nodes
+            .into_iter()
+            .filter_map(|node| match node {
+                Node::Leaf(leaf) => Some(leaf.tabs),
+                _ => None,
+            })
+            .flatten()
+            .collect()
+    }
+
+    /// Closes a window the same way its close button in the [`DockArea`](crate::DockArea) does.
+    ///
+    /// Each tab of the window is passed to [`TabViewer::on_close`] if it's [closeable](TabViewer::is_closeable),
+    /// and the window is only removed if all of its tabs are closeable and agree to be closed.
+    /// The main surface is never removed.
+    ///
+    /// Returns `true` if the window was removed.
+    pub fn remove_surface_with(
+        &mut self,
+        surface_index: SurfaceIndex,
+        tab_viewer: &mut impl TabViewer<Tab = Tab>,
+    ) -> bool {
+        if surface_index.is_main() || !self.is_surface_valid(surface_index) {
+            return false;
+        }
+        let mut all_tabs_are_closable = true;
+        for node in self[surface_index].iter_mut() {
+            for tab in node.iter_tabs_mut() {
+                if !(tab_viewer.is_closeable(tab)
+                    && matches!(tab_viewer.on_close(tab), OnCloseResponse::Close))
+                {
+                    all_tabs_are_closable = false;
+                }
+            }
+        }
+        if all_tabs_are_closable {
+            self.remove_surface(surface_index);
+        }
+        all_tabs_are_closable
+    }
+
+    /// Returns whether the tabs of the leaf at `surface_index` and `node_index` didn't fit in its tab bar
+    /// when it was last shown, see [`LeafNode::tab_overflow`].
+    ///
+    /// Returns `None` if there's no such leaf, or if its tab bar wasn't laid out yet.
+    pub fn leaf_tab_overflow(
+        &self,
+        (surface_index, node_index): (SurfaceIndex, NodeIndex),
+    ) -> Option<bool> {
+        self.leaf(surface_index, node_index)?.tab_overflow()
+    }
+
+    /// Returns the active tab of the leaf at `surface_index` and `node_index`.
+    ///
+    /// Returns `None` if there's no such leaf, or if it has no tabs.
+    pub fn active_tab(
+        &self,
+        (surface_index, node_index): (SurfaceIndex, NodeIndex),
+    ) -> Option<TabIndex> {
+        let leaf = self.leaf(surface_index, node_index)?;
+        (leaf.active.0 < leaf.tabs.len()).then_some(leaf.active)
+    }
+
+    /// Returns the tab after the active one in the leaf at `surface_index` and `node_index`.
+    ///
+    /// Past the last tab, this goes back to the first one if `wrap` is `true`, and stays on the last one otherwise.
+    /// Returns `None` if there's no such leaf, or if it has no tabs.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use egui_dock::{DockState, NodeIndex, SurfaceIndex, TabIndex};
+    /// let mut dock_state = DockState::new(vec!["a", "b"]);
+    /// let leaf = (SurfaceIndex::main(), NodeIndex::root());
+    /// dock_state.set_active_tab((leaf.0, leaf.1, TabIndex(1)));
+    ///
+    /// assert_eq!(dock_state.next_tab(leaf, true), Some(TabIndex(0)));
+    /// assert_eq!(dock_state.next_tab(leaf, false), Some(TabIndex(1)));
+    /// assert_eq!(dock_state.previous_tab(leaf, false), Some(TabIndex(0)));
+    /// ```
+    pub fn next_tab(
+        &self,
+        (surface_index, node_index): (SurfaceIndex, NodeIndex),
+        wrap: bool,
+    ) -> Option<TabIndex> {
+        let len = self.leaf(surface_index, node_index)?.tabs.len();
+        let active = self.active_tab((surface_index, node_index))?.0;
+        Some(TabIndex(if active + 1 < len {
+            active + 1
+        } else if wrap {
+            0
+        } else {
+            active
+        }))
+    }
+
+    /// Returns the tab before the active one in the leaf at `surface_index` and `node_index`.
+    ///
+    /// Before the first tab, this goes to the last one if `wrap` is `true`, and stays on the first one otherwise.
+    /// Returns `None` if there's no such leaf, or if it has no tabs.
+    pub fn previous_tab(
+        &self,
+        (surface_index, node_index): (SurfaceIndex, NodeIndex),
+        wrap: bool,
+    ) -> Option<TabIndex> {
+        let len = self.leaf(surface_index, node_index)?.tabs.len();
+        let active = self.active_tab((surface_index, node_index))?.0;
+        Some(TabIndex(match active.checked_sub(1) {
+            Some(previous) => previous,
+            None if wrap => len - 1,
+            None => active,
+        }))
+    }
+
+    /// Returns the leaf at `surface_index` and `node_index`, if there's one.
+    pub(crate) fn leaf(
+        &self,
+        surface_index: SurfaceIndex,
+        node_index: NodeIndex,
+    ) -> Option<&LeafNode<Tab>> {
+        self.get_surface(surface_index)?
+            .node_tree()?
+            .nodes
+            .get(node_index.0)?
+            .get_leaf()
+    }
+
+    /// Returns the leaf at `surface_index` and `node_index`, or why there's none.
+    fn leaf_or_error(
+        &self,
+        surface_index: SurfaceIndex,
+        node_index: NodeIndex,
+    ) -> Result<&LeafNode<Tab>, DockError> {
+        if !self.is_surface_valid(surface_index) {
+            return Err(DockError::InvalidSurface(surface_index));
+        }
+        match self[surface_index].nodes.get(node_index.0) {
+            None | Some(Node::Empty) => Err(DockError::InvalidNode(node_index)),
+            Some(Node::Leaf(leaf)) => Ok(leaf),
+            Some(_) => Err(DockError::NotALeaf(node_index)),
+        }
+    }
+
+    /// Collapses the leaf at `surface_index` and `node_index` down to its tab bar, or expands it again.
+    ///
+    /// Fails if there's no leaf at that location, or if the leaf should be collapsed but one of its tabs isn't
+    /// [collapsible](TabViewer::is_collapsible).
+    pub fn set_leaf_collapsed(
+        &mut self,
+        (surface_index, node_index): (SurfaceIndex, NodeIndex),
+        collapsed: bool,
+        tab_viewer: &impl TabViewer<Tab = Tab>,
+    ) -> Result<(), DockError> {
+        let leaf = self.leaf_or_error(surface_index, node_index)?;
+        if collapsed && !leaf.tabs.iter().all(|tab| tab_viewer.is_collapsible(tab)) {
+            return Err(DockError::NotCollapsible(node_index));
+        }
+        self[surface_index][node_index].set_collapsed(collapsed);
+        self[surface_index].node_update_collapsed(node_index);
+        self.window_update_collapsed(surface_index, node_index);
+        Ok(())
+    }
+
+    /// Pins or unpins a tab, and returns where it is then.
+    ///
+    /// Pinned tabs are kept at the start of their leaf, can't be dragged out of it and are left open by the button
+    /// closing all tabs of the leaf. See also [`LeafNode::set_pinned`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use egui_dock::{DockState, NodeIndex, SurfaceIndex, TabIndex};
+    /// let mut dock_state = DockState::new(vec!["a", "b", "c"]);
+    /// let surface = SurfaceIndex::main();
+    /// let root = NodeIndex::root();
+    ///
+    /// let pinned = dock_state.set_pinned((surface, root, TabIndex(2)), true);
+    /// assert_eq!(pinned, Ok(TabIndex(0)));
+    /// assert!(dock_state.is_pinned((surface, root, TabIndex(0))));
+    /// assert_eq!(dock_state[surface][root].tabs(), Some(&["c", "a", "b"][..]));
+    /// ```
+    pub fn set_pinned(
+        &mut self,
+        (surface_index, node_index, tab_index): (SurfaceIndex, NodeIndex, TabIndex),
+        pinned: bool,
+    ) -> Result<TabIndex, DockError> {
+        let leaf = self.leaf_or_error(surface_index, node_index)?;
+        if tab_index.0 >= leaf.tabs.len() {
+            return Err(DockError::TabIndexOutOfRange(tab_index));
+        }
+        let leaf = self[surface_index][node_index]
+            .get_leaf_mut()
+            .expect("the leaf was checked to exist");
+        Ok(leaf.set_pinned(tab_index, pinned))
+    }
+
+    /// Returns whether a tab is pinned, see [`set_pinned`](Self::set_pinned).
+    ///
+    /// Returns `false` if there's no such tab.
+    pub fn is_pinned(
+        &self,
+        (surface_index, node_index, tab_index): (SurfaceIndex, NodeIndex, TabIndex),
+    ) -> bool {
+        self.leaf_or_error(surface_index, node_index)
+            .is_ok_and(|leaf| leaf.is_pinned(tab_index))
+    }
+
+    /// Updates the state of the window at `surface_index` after the leaf at `node_index` was collapsed or expanded.
+    pub(crate) fn window_update_collapsed(
+        &mut self,
+        surface_index: SurfaceIndex,
+        node_index: NodeIndex,
+    ) {
+        let surface = &mut self[surface_index];
+        let collapsed = surface[node_index].is_collapsed();
+        if !collapsed {
+            if let Some(window_state) = self.get_window_state_mut(surface_index) {
+                window_state.set_new(true);
+            }
+        } else if surface.root_node().is_some_and(|root| root.is_collapsed()) {
+            let root_index = NodeIndex::root();
+            let surface_height = if surface.root_node().is_some() {
+                surface[root_index].rect().unwrap().height()
+            } else {
+                0.0
+            };
+            if let Some(window_state) = self.get_window_state_mut(surface_index) {
+                window_state.set_expanded_height(surface_height);
+            }
+        }
+    }
+
     /// Sets which is the active tab within a specific node on a given surface.
     #[inline]
     pub fn set_active_tab(
@@ -209,12 +595,160 @@ impl<Tab> DockState<Tab> {
         self.focused_surface = None;
     }
 
+    /// Re-applies the stored focus, for instance after deserializing a [`DockState`].
+    ///
+    /// If the focused leaf no longer exists, the first leaf of the main surface is focused instead.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use egui_dock::{DockState, NodeIndex, SurfaceIndex};
+    /// let mut dock_state = DockState::new(vec!["a"]);
+    /// let window = dock_state.add_window(vec!["b"]);
+    /// dock_state.set_focused_node_and_surface((window, NodeIndex::root()));
+    ///
+    /// dock_state.remove_surface(window);
+    /// dock_state.restore_focus();
+    /// assert_eq!(
+    ///     dock_state.focused_leaf(),
+    ///     Some((SurfaceIndex::main(), NodeIndex::root()))
+    /// );
+    /// ```
+    pub fn restore_focus(&mut self) {
+        let stored = self.focused_surface.and_then(|surface| {
+            let node = self.get_surface(surface)?.node_tree()?.focused_leaf()?;
+            self.leaf(surface, node).map(|_| (surface, node))
+        });
+        let target = stored.or_else(|| {
+            let tree = self.main_surface();
+            tree.breadth_first_index_iter()
+                .find(|&node| tree[node].is_leaf())
+                .map(|node| (SurfaceIndex::main(), node))
+        });
+        match target {
+            Some(target) => self.set_focused_node_and_surface(target),
+            None => self.focused_surface = None,
+        }
+    }
+
+    /// Maximizes a leaf, making it temporarily fill the whole surface it's on, or restores the layout with [`None`].
+    ///
+    /// The tree itself is left intact, so restoring brings back the exact layout from before.
+    /// While a leaf of the main surface is maximized, windows are hidden and dragged tabs can't create new ones,
+    /// and while any leaf is maximized, dragged tabs can't create new splits.
+    ///
+    /// Does nothing if `leaf` is `Some` but doesn't point to a leaf.
+    pub fn set_maximized_leaf(&mut self, leaf: Option<(SurfaceIndex, NodeIndex)>) {
+        match leaf {
+            Some((surface, node)) => {
+                if self.is_surface_valid(surface)
+                    && node.0 < self[surface].len()
+                    && self[surface][node].is_leaf()
+                {
+                    self.maximized_leaf = leaf;
+                }
+            }
+            None => self.maximized_leaf = None,
+        }
+    }
+
+    /// Returns the currently maximized leaf, if any.
+    ///
+    /// See [`set_maximized_leaf`](Self::set_maximized_leaf).
+    #[inline]
+    pub fn maximized_leaf(&self) -> Option<(SurfaceIndex, NodeIndex)> {
+        self.maximized_leaf
+    }
+
+    /// Returns what releasing the tabs being dragged in a [`DockArea`](crate::DockArea) would do,
+    /// or [`None`] if nothing is dragged, or if the tabs would stay where they are.
+    ///
+    /// This reflects the last time the [`DockArea`](crate::DockArea) was shown,
+    /// so it can be used to set a custom cursor or show a hint during a drag.
+    #[inline]
+    pub fn current_drop_kind(&self) -> Option<DropKind> {
+        self.drop_kind
+    }
+
+    /// Maximizes the leaf holding a tab and makes the tab active, so that it fills the whole surface it's on.
+    ///
+    /// This is a shorthand for [`set_active_tab`](Self::set_active_tab) and
+    /// [`set_maximized_leaf`](Self::set_maximized_leaf), the layout is brought back with
+    /// [`restore_maximized`](Self::restore_maximized).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use egui_dock::{DockState, NodeIndex, SurfaceIndex, TabIndex};
+    /// let mut dock_state = DockState::new(vec!["a", "b"]);
+    /// let [_, right] = dock_state
+    ///     .main_surface_mut()
+    ///     .split_right(NodeIndex::root(), 0.5, vec!["c"]);
+    ///
+    /// dock_state.maximize(SurfaceIndex::main(), right, TabIndex(0)).unwrap();
+    /// assert_eq!(dock_state.maximized_leaf(), Some((SurfaceIndex::main(), right)));
+    ///
+    /// assert_eq!(dock_state.restore_maximized(), Some((SurfaceIndex::main(), right)));
+    /// assert_eq!(dock_state.maximized_leaf(), None);
+    /// assert_eq!(dock_state.main_surface().num_tabs(), 3);
+    /// ```
+    pub fn maximize(
+        &mut self,
+        surface_index: SurfaceIndex,
+        node_index: NodeIndex,
+        tab_index: TabIndex,
+    ) -> Result<(), DockError> {
+        let leaf = self.leaf_or_error(surface_index, node_index)?;
+        if tab_index.0 >= leaf.tabs.len() {
+            return Err(DockError::TabIndexOutOfRange(tab_index));
+        }
+        self.set_active_tab((surface_index, node_index, tab_index));
+        self.maximized_leaf = Some((surface_index, node_index));
+        Ok(())
+    }
+
+    /// Restores the layout from before a leaf was [maximized](Self::maximize),
+    /// and returns the leaf which was maximized, if any.
+    pub fn restore_maximized(&mut self) -> Option<(SurfaceIndex, NodeIndex)> {
+        self.maximized_leaf.take()
+    }
+
+    /// Maximizes the leaf at the given address, or restores it if it's already maximized.
+    pub(crate) fn toggle_maximized_leaf(&mut self, leaf: (SurfaceIndex, NodeIndex)) {
+        if self.maximized_leaf == Some(leaf) {
+            self.set_maximized_leaf(None);
+        } else {
+            self.set_maximized_leaf(Some(leaf));
+        }
+    }
+
     /// Moves a tab from a node to another node.
     /// You need to specify with [`TabDestination`] how the tab should be moved.
+    ///
+    /// If the tab is moved into a new split, it takes half of the destination node's area.
+    #[inline]
     pub fn move_tab(
+        &mut self,
+        src_tab: (SurfaceIndex, NodeIndex, TabIndex),
+        dst_tab: impl Into<TabDestination>,
+    ) {
+        self.move_tab_with_split_fraction(src_tab, dst_tab, 0.5);
+    }
+
+    /// Moves a tab from a node to another node.
+    /// You need to specify with [`TabDestination`] how the tab should be moved.
+    ///
+    /// If the tab is moved into a new split, `fraction` (in range 0..=1) specifies how much of the destination
+    /// node's area the moved tab will occupy.
+    ///
+    /// # Panics
+    ///
+    /// If the tab is moved into a new split and `fraction` isn't in range 0..=1.
+    pub fn move_tab_with_split_fraction(
         &mut self,
         (src_surface, src_node, src_tab): (SurfaceIndex, NodeIndex, TabIndex),
         dst_tab: impl Into<TabDestination>,
+        fraction: f32,
     ) {
         match dst_tab.into() {
             TabDestination::Window(position) => {
@@ -234,7 +768,12 @@ impl<Tab> DockState<Tab> {
                 let tab = self[src_surface][src_node].remove_tab(src_tab).unwrap();
                 match dst_tab {
                     TabInsert::Split(split) => {
-                        self[dst_surface].split(dst_node, split, 0.5, Node::leaf(tab));
+                        // The fraction of a split node belongs to its top or left child.
+                        let fraction = match split {
+                            Split::Left | Split::Above => fraction,
+                            Split::Right | Split::Below => 1.0 - fraction,
+                        };
+                        self[dst_surface].split(dst_node, split, fraction, Node::leaf(tab));
                     }
 
                     TabInsert::Insert(index) => self[dst_surface][dst_node].insert_tab(index, tab),
@@ -249,12 +788,261 @@ impl<Tab> DockState<Tab> {
         }
         if self[src_surface][src_node].is_leaf() && self[src_surface][src_node].tabs_count() == 0 {
             self[src_surface].remove_leaf(src_node);
+            self.forget_maximized_leaf(src_surface);
+        }
+        if self[src_surface].is_empty() && !src_surface.is_main() {
+            self.remove_surface(src_surface);
+        }
+    }
+
+    /// Adds a tab coming from outside the dock state, e.g. dropped from another widget, at the given `destination`.
+    ///
+    /// If the tab is added to a new split, it takes half of the destination node's area.
+    ///
+    /// See also [`hit_test`](Self::hit_test) and [`DockArea::external_drag_active`](crate::DockArea::external_drag_active).
+    ///
+    /// # Panics
+    ///
+    /// If the destination is an [`EmptySurface`](TabDestination::EmptySurface) which isn't empty.
+    pub fn accept_external_drop(&mut self, destination: impl Into<TabDestination>, tab: Tab) {
+        match destination.into() {
+            TabDestination::Window(window_rect) => {
+                let surface_index = self.add_window(vec![tab]);
+                self.get_window_state_mut(surface_index)
+                    .unwrap()
+                    .set_position(window_rect.min)
+                    .set_size(window_rect.size());
+            }
+            TabDestination::Node(surface_index, node_index, insert) => match insert {
+                TabInsert::Split(split) => {
+                    self[surface_index].split(node_index, split, 0.5, Node::leaf(tab));
+                }
+                TabInsert::Insert(index) => self[surface_index][node_index].insert_tab(index, tab),
+                TabInsert::Append => self[surface_index][node_index].append_tab(tab),
+            },
+            TabDestination::EmptySurface(surface_index) => {
+                assert!(self[surface_index].is_empty());
+                self[surface_index] = Tree::new(vec![tab]);
+            }
+        }
+    }
+
+    /// Returns the leaf which was shown at `pos` the last time the dock state was shown, if any.
+    ///
+    /// Windows are checked before the main surface, since they're shown on top of it, in the order they were stacked
+    /// in the last time they were shown. Minimized windows and windows shown in their own viewport are skipped.
+    pub fn hit_test(&self, pos: Pos2) -> Option<(SurfaceIndex, NodeIndex)> {
+        let leaf_at = |surface_index: SurfaceIndex| {
+            let contains = |node_index: NodeIndex| {
+                self[surface_index][node_index].is_leaf()
+                    && self[surface_index][node_index]
+                        .rect()
+                        .is_some_and(|rect| rect.contains(pos))
+            };
+            match self.maximized_leaf {
+                Some((surface, node_index)) if surface == surface_index => {
+                    contains(node_index).then_some((surface_index, node_index))
+                }
+                _ => self[surface_index]
+                    .breadth_first_index_iter()
+                    .find(|&node_index| contains(node_index))
+                    .map(|node_index| (surface_index, node_index)),
+            }
+        };
+
+        // Windows are hidden while a leaf of the main surface is maximized.
+        let windows_shown = !self
+            .maximized_leaf
+            .is_some_and(|(surface, _)| surface.is_main());
+        if windows_shown {
+            let mut windows: Vec<_> = self
+                .iter_windows()
+                .filter(|(_, _, state)| !state.is_minimized() && !state.is_viewport())
+                .map(|(surface_index, _, state)| (surface_index, state.stack_order()))
+                .collect();
+            windows.sort_by_key(|&(surface_index, stack_order)| {
+                std::cmp::Reverse((stack_order, surface_index.0))
+            });
+            if let Some(leaf) = windows
+                .into_iter()
+                .find_map(|(surface_index, _)| leaf_at(surface_index))
+            {
+                return Some(leaf);
+            }
+        }
+        leaf_at(SurfaceIndex::main())
+    }
+
+    /// Moves all tabs of a leaf to another node, or to a new window, removing the leaf.
+    /// You need to specify with [`TabDestination`] how the tabs should be moved.
+    ///
+    /// If the tabs are moved into a new split, they take half of the destination node's area.
+    #[inline]
+    pub fn move_leaf(
+        &mut self,
+        src_leaf: (SurfaceIndex, NodeIndex),
+        dst_leaf: impl Into<TabDestination>,
+    ) {
+        self.move_leaf_with_split_fraction(src_leaf, dst_leaf, 0.5);
+    }
+
+    /// Moves all tabs of a leaf to another node, or to a new window, removing the leaf.
+    /// You need to specify with [`TabDestination`] how the tabs should be moved.
+    ///
+    /// If the tabs are moved into a new split, `fraction` (in range 0..=1) specifies how much of the destination
+    /// node's area they will occupy. Moving a leaf into itself does nothing.
+    ///
+    /// # Panics
+    ///
+    /// If the tabs are moved into a new split and `fraction` isn't in range 0..=1.
+    pub fn move_leaf_with_split_fraction(
+        &mut self,
+        (src_surface, src_node): (SurfaceIndex, NodeIndex),
+        dst_leaf: impl Into<TabDestination>,
+        fraction: f32,
+    ) {
+        let dst_leaf = dst_leaf.into();
+        if let TabDestination::Node(dst_surface, dst_node, _) = dst_leaf {
+            if (src_surface, src_node) == (dst_surface, dst_node) {
+                return;
+            }
+        }
+        let Some(leaf) = self[src_surface][src_node].get_leaf_mut() else {
+            return;
+        };
+        let tabs = std::mem::take(&mut leaf.tabs);
+        if tabs.is_empty() {
+            return;
         }
+
+        match dst_leaf {
+            TabDestination::Window(window_rect) => {
+                let surface_index = self.add_window(tabs);
+                let state = self.get_window_state_mut(surface_index).unwrap();
+                state.set_position(window_rect.min);
+                state.set_size(window_rect.size());
+            }
+            TabDestination::Node(dst_surface, dst_node, dst_tab) => match dst_tab {
+                TabInsert::Split(split) => {
+                    // The fraction of a split node belongs to its top or left child.
+                    let fraction = match split {
+                        Split::Left | Split::Above => fraction,
+                        Split::Right | Split::Below => 1.0 - fraction,
+                    };
+                    self[dst_surface].split(dst_node, split, fraction, Node::leaf_with(tabs));
+                }
+                TabInsert::Insert(index) => {
+                    for (offset, tab) in tabs.into_iter().enumerate() {
+                        self[dst_surface][dst_node].insert_tab(TabIndex(index.0 + offset), tab);
+                    }
+                }
+                TabInsert::Append => {
+                    for tab in tabs {
+                        self[dst_surface][dst_node].append_tab(tab);
+                    }
+                }
+            },
+            TabDestination::EmptySurface(dst_surface) => {
+                assert!(self[dst_surface].is_empty());
+                self[dst_surface] = Tree::new(tabs);
+            }
+        }
+
+        self[src_surface].remove_leaf(src_node);
+        self.forget_maximized_leaf(src_surface);
         if self[src_surface].is_empty() && !src_surface.is_main() {
             self.remove_surface(src_surface);
         }
     }
 
+    /// Moves several tabs of a leaf to another node, or to a new window, keeping their relative order.
+    /// You need to specify with [`TabDestination`] how the tabs should be moved.
+    ///
+    /// If the tabs are moved into a new split, they take half of the destination node's area.
+    #[inline]
+    pub fn move_tabs(
+        &mut self,
+        src_leaf: (SurfaceIndex, NodeIndex),
+        src_tabs: &[TabIndex],
+        dst_tab: impl Into<TabDestination>,
+    ) {
+        self.move_tabs_with_split_fraction(src_leaf, src_tabs, dst_tab, 0.5);
+    }
+
+    /// Moves several tabs of a leaf to another node, or to a new window, keeping their relative order.
+    /// You need to specify with [`TabDestination`] how the tabs should be moved.
+    ///
+    /// When the tabs are inserted into their own leaf, [`TabInsert::Insert`] refers to the position among the tabs
+    /// before any of them is taken out. If all tabs of the leaf are moved, this is the same as
+    /// [`move_leaf_with_split_fraction`](Self::move_leaf_with_split_fraction).
+    ///
+    /// # Panics
+    ///
+    /// If any of `src_tabs` doesn't exist in the leaf.
+    ///
+    /// If the tabs are moved into a new split and `fraction` isn't in range 0..=1.
+    pub fn move_tabs_with_split_fraction(
+        &mut self,
+        (src_surface, src_node): (SurfaceIndex, NodeIndex),
+        src_tabs: &[TabIndex],
+        dst_tab: impl Into<TabDestination>,
+        fraction: f32,
+    ) {
+        let mut src_tabs = src_tabs.to_vec();
+        src_tabs.sort_unstable();
+        src_tabs.dedup();
+        if src_tabs.is_empty() {
+            return;
+        }
+        if src_tabs.len() == self[src_surface][src_node].tabs_count() {
+            self.move_leaf_with_split_fraction((src_surface, src_node), dst_tab, fraction);
+            return;
+        }
+
+        // Take the tabs out back to front so that the indices stay valid.
+        let mut tabs = Vec::with_capacity(src_tabs.len());
+        for &tab_index in src_tabs.iter().rev() {
+            tabs.push(self[src_surface][src_node].remove_tab(tab_index).unwrap());
+        }
+        tabs.reverse();
+
+        match dst_tab.into() {
+            TabDestination::Window(window_rect) => {
+                let surface_index = self.add_window(tabs);
+                let state = self.get_window_state_mut(surface_index).unwrap();
+                state.set_position(window_rect.min);
+                state.set_size(window_rect.size());
+            }
+            TabDestination::Node(dst_surface, dst_node, dst_tab) => match dst_tab {
+                TabInsert::Split(split) => {
+                    // The fraction of a split node belongs to its top or left child.
+                    let fraction = match split {
+                        Split::Left | Split::Above => fraction,
+                        Split::Right | Split::Below => 1.0 - fraction,
+                    };
+                    self[dst_surface].split(dst_node, split, fraction, Node::leaf_with(tabs));
+                }
+                TabInsert::Insert(mut index) => {
+                    if (dst_surface, dst_node) == (src_surface, src_node) {
+                        index.0 -= src_tabs.iter().filter(|tab| tab.0 < index.0).count();
+                    }
+                    for (offset, tab) in tabs.into_iter().enumerate() {
+                        self[dst_surface][dst_node].insert_tab(TabIndex(index.0 + offset), tab);
+                    }
+                }
+                TabInsert::Append => {
+                    for tab in tabs {
+                        self[dst_surface][dst_node].append_tab(tab);
+                    }
+                }
+            },
+            TabDestination::EmptySurface(dst_surface) => {
+                assert!(self[dst_surface].is_empty());
+                self[dst_surface] = Tree::new(tabs);
+            }
+        }
+    }
+
     /// Takes a tab out of its current surface and puts it in a new window.
     /// Returns the surface index of the new window.
     pub fn detach_tab(
@@ -278,6 +1066,7 @@ impl<Tab> DockState<Tab> {
         // Clean up any empty leaves and surfaces which may be left behind from the detachment.
         if self[src_surface][src_node].is_leaf() && self[src_surface][src_node].tabs_count() == 0 {
             self[src_surface].remove_leaf(src_node);
+            self.forget_maximized_leaf(src_surface);
         }
         if self[src_surface].is_empty() && !src_surface.is_main() {
             self.remove_surface(src_surface);
@@ -299,20 +1088,104 @@ impl<Tab> DockState<Tab> {
         (surface_index, node_index, tab_index): (SurfaceIndex, NodeIndex, TabIndex),
     ) -> Option<Tab> {
         let removed_tab = self[surface_index].remove_tab((node_index, tab_index));
+        if self.leaf_or_error(surface_index, node_index).is_err() {
+            self.forget_maximized_leaf(surface_index);
+        }
         if !surface_index.is_main() && self[surface_index].is_empty() {
             self.remove_surface(surface_index);
         }
         removed_tab
     }
 
+    /// Same as [`remove_tab`](Self::remove_tab), but tells why nothing was removed instead of panicking
+    /// or returning [`None`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use egui_dock::{DockError, DockState, NodeIndex, SurfaceIndex, TabIndex};
+    /// let mut dock_state = DockState::new(vec!["a"]);
+    /// let window = dock_state.add_window(vec!["b"]);
+    ///
+    /// let root = NodeIndex::root();
+    /// assert_eq!(
+    ///     dock_state.try_remove_tab((SurfaceIndex::main(), root, TabIndex(1))),
+    ///     Err(DockError::TabIndexOutOfRange(TabIndex(1)))
+    /// );
+    /// assert_eq!(dock_state.try_remove_tab((window, root, TabIndex(0))), Ok("b"));
+    /// assert_eq!(
+    ///     dock_state.try_remove_tab((window, root, TabIndex(0))),
+    ///     Err(DockError::InvalidSurface(window))
+    /// );
+    /// ```
+    pub fn try_remove_tab(
+        &mut self,
+        (surface_index, node_index, tab_index): (SurfaceIndex, NodeIndex, TabIndex),
+    ) -> Result<Tab, DockError> {
+        let leaf = self.leaf_or_error(surface_index, node_index)?;
+        if tab_index.0 >= leaf.tabs.len() {
+            return Err(DockError::TabIndexOutOfRange(tab_index));
+        }
+        Ok(self
+            .remove_tab((surface_index, node_index, tab_index))
+            .expect("the tab was checked to exist"))
+    }
+
+    /// Closes a tab the same way the close button of the [`DockArea`](crate::DockArea) does.
+    ///
+    /// Tabs which aren't [closeable](TabViewer::is_closeable) are left alone. Otherwise, the outcome depends on
+    /// what [`TabViewer::on_close`] returns: the tab is either removed, focused or kept as is.
+    /// Any leaf or window left empty by the removal is removed as well.
+    ///
+    /// Returns `true` if the tab was closed.
+    pub fn close_tab(
+        &mut self,
+        (surface_index, node_index, tab_index): (SurfaceIndex, NodeIndex, TabIndex),
+        tab_viewer: &mut impl TabViewer<Tab = Tab>,
+    ) -> bool {
+        if !self.is_surface_valid(surface_index) || node_index.0 >= self[surface_index].len() {
+            return false;
+        }
+        let Some(tab) = self[surface_index][node_index]
+            .get_leaf_mut()
+            .and_then(|leaf| leaf.tabs.get_mut(tab_index.0))
+        else {
+            return false;
+        };
+        if !tab_viewer.is_closeable(tab) {
+            return false;
+        }
+        match tab_viewer.on_close(tab) {
+            OnCloseResponse::Close => {
+                self.remove_tab((surface_index, node_index, tab_index));
+                true
+            }
+            OnCloseResponse::Focus => {
+                self.set_active_tab((surface_index, node_index, tab_index));
+                self.set_focused_node_and_surface((surface_index, node_index));
+                false
+            }
+            OnCloseResponse::Ignore => false,
+        }
+    }
+
     /// Remove a leaf at the specified surface, and node index.
     pub fn remove_leaf(&mut self, (surface_index, node_index): (SurfaceIndex, NodeIndex)) {
         self[surface_index].remove_leaf(node_index);
+        self.forget_maximized_leaf(surface_index);
         if !surface_index.is_main() && self[surface_index].is_empty() {
             self.remove_surface(surface_index);
         }
     }
 
+    /// Restores the layout if the maximized leaf is on a surface whose leaves were just removed,
+    /// which moves the others so that it could now point to a different one, or to none at all.
+    fn forget_maximized_leaf(&mut self, surface_index: SurfaceIndex) {
+        if self.maximized_leaf.is_some_and(|(s, _)| s == surface_index) {
+            self.maximized_leaf = None;
+        }
+    }
+
     /// Creates two new nodes by splitting a given `parent` node and assigns them as its children. The first (old) node
     /// inherits content of the `parent` from before the split, and the second (new) has `tabs`.
     ///
@@ -348,6 +1221,44 @@ impl<Tab> DockState<Tab> {
         index
     }
 
+    /// Merges the window at `src` into the window at `dst`, grouping their tabs in the same window.
+    ///
+    /// If `src` only has one leaf, its tabs are pushed to the focused leaf of `dst` (or its first leaf if none is
+    /// focused), in the order they appear in, so that a window group is simply a window whose leaf holds the tabs
+    /// of the merged windows. Otherwise the whole tree of `src` is put to the right of the tree of `dst`, keeping
+    /// its splits. The `src` window is removed afterward. Any tab can be taken out of the group again by dragging
+    /// it away.
+    ///
+    /// Windows are also merged when one is dragged onto the title bar of another, if
+    /// [`DockArea::allow_window_merging`](crate::DockArea::allow_window_merging) is enabled. The merged window is an
+    /// ordinary window, so there's no record of which windows it was merged from.
+    ///
+    /// Does nothing if either surface is not a window or if `src` and `dst` are the same.
+    pub fn merge_windows(&mut self, src: SurfaceIndex, dst: SurfaceIndex) {
+        if src == dst || src.is_main() || dst.is_main() {
+            return;
+        }
+        if !self.is_surface_valid(src) || !self.is_surface_valid(dst) {
+            return;
+        }
+        let Some(Surface::Window(tree, _)) = self.remove_surface(src) else {
+            return;
+        };
+        let dst_tree = &mut self[dst];
+        match tree.root_node() {
+            Some(Node::Leaf(_)) => {
+                let Some(Node::Leaf(leaf)) = tree.nodes.into_iter().next() else {
+                    unreachable!()
+                };
+                for tab in leaf.tabs {
+                    dst_tree.push_to_focused_leaf(tab);
+                }
+            }
+            _ => dst_tree.graft(Split::Right, 0.5, tree),
+        }
+        self.focused_surface = Some(dst);
+    }
+
     /// Finds the first empty surface index which may be used.
     ///
     /// **WARNING**: in cases where one isn't found, `SurfaceIndex(self.surfaces.len())` is used.
@@ -397,11 +1308,97 @@ impl<Tab> DockState<Tab> {
         self[SurfaceIndex::main()].push_to_first_leaf(tab);
     }
 
+    /// Pushes `tab` to the end of the leaf at `surface_index` and `node_index`, and makes it the active tab there.
+    ///
+    /// Unlike [`push_to_focused_leaf`](Self::push_to_focused_leaf), this doesn't change which leaf is focused.
+    ///
+    /// Returns the [`TabIndex`] of the pushed tab, or gives the tab back if there's no leaf at that location.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use egui_dock::{DockState, NodeIndex, SurfaceIndex, TabIndex};
+    /// let mut dock_state = DockState::new(vec!["a"]);
+    /// let [_, right] = dock_state.main_surface_mut().split_right(NodeIndex::root(), 0.5, vec!["b"]);
+    ///
+    /// assert_eq!(dock_state.push_to_leaf((SurfaceIndex::main(), right), "c"), Ok(TabIndex(1)));
+    /// assert_eq!(dock_state.push_to_leaf((SurfaceIndex::main(), NodeIndex::root()), "d"), Err("d"));
+    /// ```
+    pub fn push_to_leaf(
+        &mut self,
+        (surface_index, node_index): (SurfaceIndex, NodeIndex),
+        tab: Tab,
+    ) -> Result<TabIndex, Tab> {
+        match self
+            .get_surface_mut(surface_index)
+            .and_then(Surface::node_tree_mut)
+            .and_then(|tree| tree.nodes.get_mut(node_index.0))
+        {
+            Some(Node::Leaf(leaf)) => {
+                leaf.append_tab(tab);
+                Ok(leaf.active)
+            }
+            _ => Err(tab),
+        }
+    }
+
     /// Returns the current number of surfaces.
     pub fn surfaces_count(&self) -> usize {
         self.surfaces.len()
     }
 
+    /// Counts and returns the number of tabs on all surfaces.
+    pub fn num_tabs(&self) -> usize {
+        self.iter_surfaces()
+            .filter_map(Surface::node_tree)
+            .map(Tree::num_tabs)
+            .sum()
+    }
+
+    /// Counts and returns the number of leaves on all surfaces.
+    pub fn num_leaves(&self) -> usize {
+        self.iter_surfaces()
+            .filter_map(Surface::node_tree)
+            .map(Tree::num_leaves)
+            .sum()
+    }
+
+    /// Returns the largest [`Tree::max_depth`] of all surfaces.
+    pub fn max_depth(&self) -> usize {
+        self.iter_surfaces()
+            .filter_map(Surface::node_tree)
+            .map(Tree::max_depth)
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Estimates how much space the main surface needs when shown in a [`DockArea`](crate::DockArea)
+    /// with the given `style`, see [`Tree::preferred_size`].
+    ///
+    /// Windows aren't counted, as they float above the main surface. This is a heuristic which can be used to size
+    /// a parent container before the dock area is shown, not an exact measurement.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use egui_dock::{DockState, NodeIndex, Style};
+    /// let style = Style::from_egui(&egui::Style::default());
+    /// let mut dock_state = DockState::new(vec!["tab 1"]);
+    /// let single = dock_state.preferred_size(&style);
+    ///
+    /// dock_state.main_surface_mut().split_right(NodeIndex::root(), 0.5, vec!["tab 2"]);
+    /// let split = dock_state.preferred_size(&style);
+    /// assert!(split.x > single.x);
+    /// assert_eq!(split.y, single.y);
+    /// ```
+    pub fn preferred_size(&self, style: &Style) -> Vec2 {
+        let mut size = self.main_surface().preferred_size(style);
+        if let Some(margin) = style.dock_area_padding {
+            size += margin.sum();
+        }
+        size + Vec2::splat(style.main_surface_border_stroke.width)
+    }
+
     /// Returns an [`Iterator`] over all surfaces.
     pub fn iter_surfaces(&self) -> impl Iterator<Item = &Surface<Tab>> {
         self.surfaces.iter()
@@ -412,6 +1409,39 @@ impl<Tab> DockState<Tab> {
         self.surfaces.iter_mut()
     }
 
+    /// Returns an [`Iterator`] over all window surfaces, skipping the main surface and empty surfaces.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use egui_dock::{DockState, SurfaceIndex};
+    /// let mut dock_state = DockState::new(vec!["tab 1"]);
+    /// let window = dock_state.add_window(vec!["tab 2"]);
+    ///
+    /// let windows: Vec<SurfaceIndex> = dock_state.iter_windows().map(|(index, _, _)| index).collect();
+    /// assert_eq!(windows, [window]);
+    /// ```
+    pub fn iter_windows(&self) -> impl Iterator<Item = (SurfaceIndex, &Tree<Tab>, &WindowState)> {
+        self.iter_surfaces()
+            .enumerate()
+            .filter_map(|(index, surface)| match surface {
+                Surface::Window(tree, state) => Some((SurfaceIndex(index), tree, state)),
+                _ => None,
+            })
+    }
+
+    /// Returns a mutable [`Iterator`] over all window surfaces, skipping the main surface and empty surfaces.
+    pub fn iter_windows_mut(
+        &mut self,
+    ) -> impl Iterator<Item = (SurfaceIndex, &mut Tree<Tab>, &mut WindowState)> {
+        self.iter_surfaces_mut()
+            .enumerate()
+            .filter_map(|(index, surface)| match surface {
+                Surface::Window(tree, state) => Some((SurfaceIndex(index), tree, state)),
+                _ => None,
+            })
+    }
+
     /// Returns an [`Iterator`] of **all** underlying nodes in the dock state,
     /// and the indices of containing surfaces.
     pub fn iter_all_nodes(&self) -> impl Iterator<Item = (SurfaceIndex, &Node<Tab>)> {
@@ -462,6 +1492,63 @@ impl<Tab> DockState<Tab> {
             })
     }
 
+    /// Returns an [`Iterator`] of the tabs the user can currently see, that is the active tab of each leaf which
+    /// isn't collapsed, along with their full addresses.
+    ///
+    /// Leaves of minimized windows are skipped unless `include_minimized_windows` is `true`, see
+    /// [`set_window_collapsed`](Self::set_window_collapsed), and so is anything hidden by a
+    /// [maximized leaf](Self::set_maximized_leaf). Windows are otherwise included even if they are off-screen.
+    ///
+    /// This reflects the layout as of the last time it was shown or modified, so collapsing done by the user is only
+    /// accounted for once the [`DockArea`](crate::DockArea) has been shown.
+    ///
+    /// ```
+    /// # use egui_dock::{DockState, NodeIndex, SurfaceIndex, TabIndex};
+    /// let mut dock_state = DockState::new(vec!["a", "b"]);
+    /// dock_state.main_surface_mut().split_right(NodeIndex::root(), 0.5, vec!["c"]);
+    ///
+    /// let window = dock_state.add_window(vec!["d"]);
+    /// dock_state.set_window_collapsed(window, true);
+    ///
+    /// let visible: Vec<_> = dock_state.iter_visible_tabs(false).map(|(_, tab)| *tab).collect();
+    /// assert_eq!(visible, vec!["a", "c"]);
+    /// let visible: Vec<_> = dock_state.iter_visible_tabs(true).map(|(_, tab)| *tab).collect();
+    /// assert_eq!(visible, vec!["a", "c", "d"]);
+    /// ```
+    pub fn iter_visible_tabs(
+        &self,
+        include_minimized_windows: bool,
+    ) -> impl Iterator<Item = ((SurfaceIndex, NodeIndex, TabIndex), &Tab)> {
+        let maximized = self.maximized_leaf;
+        let main_maximized = maximized.is_some_and(|(surface, _)| surface.is_main());
+        self.surfaces
+            .iter()
+            .enumerate()
+            .filter_map(move |(surface_index, surface)| match surface {
+                Surface::Main(tree) => Some((SurfaceIndex(surface_index), tree)),
+                Surface::Window(tree, state)
+                    if (include_minimized_windows || !state.is_minimized()) && !main_maximized =>
+                {
+                    Some((SurfaceIndex(surface_index), tree))
+                }
+                _ => None,
+            })
+            .flat_map(move |(surface_index, tree)| {
+                tree.iter()
+                    .enumerate()
+                    .filter_map(move |(node_index, node)| {
+                        let node_index = NodeIndex(node_index);
+                        let hidden = maximized.is_some_and(|(surface, node)| {
+                            surface == surface_index && node != node_index
+                        });
+                        let leaf = node.get_leaf().filter(|leaf| !hidden && !leaf.collapsed)?;
+                        leaf.tabs
+                            .get(leaf.active.0)
+                            .map(|tab| ((surface_index, node_index, leaf.active), tab))
+                    })
+            })
+    }
+
     /// Returns an [`Iterator`] of the underlying collection of nodes on the main surface.
     #[deprecated = "Use `dock_state.main_surface().iter()` instead"]
     pub fn iter_main_surface_nodes(&self) -> impl Iterator<Item = &Node<Tab>> {
@@ -513,6 +1600,9 @@ impl<Tab> DockState<Tab> {
         let DockState {
             surfaces,
             focused_surface,
+            maximized_leaf: _,
+            drop_kind: _,
+            layout_history: _,
             translations,
         } = self;
         let surfaces = surfaces
@@ -525,6 +1615,9 @@ impl<Tab> DockState<Tab> {
         DockState {
             surfaces,
             focused_surface: *focused_surface,
+            maximized_leaf: None,
+            drop_kind: None,
+            layout_history: None,
             translations: translations.clone(),
         }
     }
@@ -546,6 +1639,66 @@ impl<Tab> DockState<Tab> {
         self.filter_map_tabs(move |tab| Some(function(tab)))
     }
 
+    /// Serializes the layout of the [`DockState`] without the tabs themselves, storing the key returned by `key` for
+    /// each tab in their place.
+    ///
+    /// The layout can be restored with [`deserialize_structure_with`](Self::deserialize_structure_with).
+    ///
+    /// ```
+    /// # use egui_dock::{egui, DockState};
+    /// struct Tab {
+    ///     name: String,
+    ///     heavy_data: Vec<u8>,
+    /// }
+    ///
+    /// let mut dock_state = DockState::new(vec![Tab { name: "tab".to_string(), heavy_data: vec![0; 1024] }]);
+    /// # // JSON can't represent the infinite rects of a layout that was never shown.
+    /// # for (_, leaf) in dock_state.iter_leaves_mut() {
+    /// #     (leaf.rect, leaf.viewport) = (egui::Rect::ZERO, egui::Rect::ZERO);
+    /// # }
+    ///
+    /// let mut json = Vec::new();
+    /// dock_state
+    ///     .serialize_structure(&mut serde_json::Serializer::new(&mut json), |tab| tab.name.clone())
+    ///     .unwrap();
+    ///
+    /// let restored = DockState::deserialize_structure_with(
+    ///     &mut serde_json::Deserializer::from_slice(&json),
+    ///     |name: &String| Some(Tab { name: name.clone(), heavy_data: Vec::new() }),
+    /// )
+    /// .unwrap();
+    /// assert_eq!(restored.iter_all_tabs().next().unwrap().1.name, "tab");
+    /// ```
+    #[cfg(feature = "serde")]
+    pub fn serialize_structure<S, K>(
+        &self,
+        serializer: S,
+        key: impl FnMut(&Tab) -> K,
+    ) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+        K: serde::Serialize,
+    {
+        serde::Serialize::serialize(&self.map_tabs(key), serializer)
+    }
+
+    /// Deserializes a layout serialized with [`serialize_structure`](Self::serialize_structure), looking up each tab
+    /// from its key with `lookup`.
+    ///
+    /// Tabs for which `lookup` returns [`None`] are left out, along with any nodes and surfaces left empty afterward.
+    #[cfg(feature = "serde")]
+    pub fn deserialize_structure_with<'de, D, K>(
+        deserializer: D,
+        lookup: impl FnMut(&K) -> Option<Tab>,
+    ) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+        K: serde::Deserialize<'de>,
+    {
+        let structure: DockState<K> = serde::Deserialize::deserialize(deserializer)?;
+        Ok(structure.filter_map_tabs(lookup))
+    }
+
     /// Returns a new [`DockState`] while filtering the tab type.
     /// Any remaining empty [`Node`]s and [`Surface`]s are removed.
     ///
@@ -608,6 +1761,54 @@ impl<Tab> DockState<Tab> {
         }
         None
     }
+
+    /// Focuses the first tab for which `predicate` returns `true`, or pushes the tab created by `make` to the
+    /// focused leaf if there's no such tab, see [`push_to_focused_leaf`](Self::push_to_focused_leaf).
+    ///
+    /// Focusing a tab makes it the active tab of its leaf, focuses that leaf and [raises](WindowState::raise)
+    /// the window it's in, if any.
+    ///
+    /// Returns where the tab is, whether it was found or created.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use egui_dock::{DockState, NodeIndex, SurfaceIndex, TabIndex};
+    /// let mut dock_state = DockState::new(vec!["a".to_owned(), "b".to_owned()]);
+    ///
+    /// let location = dock_state.focus_or_open(|tab| tab == "a", || "a".to_owned());
+    /// assert_eq!(location, (SurfaceIndex::main(), NodeIndex::root(), TabIndex(0)));
+    ///
+    /// let location = dock_state.focus_or_open(|tab| tab == "c", || "c".to_owned());
+    /// assert_eq!(location, (SurfaceIndex::main(), NodeIndex::root(), TabIndex(2)));
+    /// assert_eq!(dock_state.num_tabs(), 3);
+    /// ```
+    pub fn focus_or_open(
+        &mut self,
+        predicate: impl Fn(&Tab) -> bool,
+        make: impl FnOnce() -> Tab,
+    ) -> (SurfaceIndex, NodeIndex, TabIndex) {
+        if let Some(location @ (surface_index, node_index, _)) = self.find_tab_from(predicate) {
+            self.set_active_tab(location);
+            self.set_focused_node_and_surface((surface_index, node_index));
+            if let Some(window_state) = self.get_window_state_mut(surface_index) {
+                window_state.raise();
+            }
+            return location;
+        }
+
+        self.push_to_focused_leaf(make());
+        let surface_index = self.focused_surface.unwrap_or(SurfaceIndex::main());
+        let node_index = self[surface_index]
+            .focused_leaf()
+            .expect("pushing a tab focuses the leaf it was pushed to");
+        self.focused_surface = Some(surface_index);
+        let tab_index = self[surface_index][node_index]
+            .get_leaf()
+            .expect("the focused node is a leaf")
+            .active;
+        (surface_index, node_index, tab_index)
+    }
 }
 
 impl<Tab> DockState<Tab>
@@ -637,6 +1838,21 @@ where
     pub fn find_main_surface_tab(&self, needle_tab: &Tab) -> Option<(NodeIndex, TabIndex)> {
         self[SurfaceIndex::main()].find_tab(needle_tab)
     }
+
+    /// Returns where the given tab is in the leaf at `surface_index` and `node_index`.
+    ///
+    /// Returns `None` if there's no such leaf, or if the tab isn't in it.
+    pub fn tab_index_of(
+        &self,
+        (surface_index, node_index): (SurfaceIndex, NodeIndex),
+        needle_tab: &Tab,
+    ) -> Option<TabIndex> {
+        self.leaf(surface_index, node_index)?
+            .tabs
+            .iter()
+            .position(|tab| tab == needle_tab)
+            .map(TabIndex)
+    }
 }
 
 #[cfg(test)]
@@ -652,4 +1868,179 @@ mod test {
         t.retain_tabs(|_| false);
         t.push_to_focused_leaf(0);
     }
+
+    #[test]
+    fn merge_windows() {
+        let mut t = DockState::new(vec![0]);
+        let dst = t.add_window(vec![1]);
+        let src = t.add_window(vec![2]);
+        let single = t.add_window(vec![4]);
+        t[src].split_below(NodeIndex::root(), 0.5, vec![3]);
+        t[dst].split_right(NodeIndex::root(), 0.5, vec![5]);
+        t.merge_windows(single, dst);
+        assert!(!t.is_surface_valid(single));
+        assert_eq!(t[dst].num_leaves(), 2);
+
+        // The splits of the merged window are kept.
+        t.merge_windows(src, dst);
+        assert!(!t.is_surface_valid(src));
+        assert_eq!(t[dst].num_tabs(), 5);
+        let leaves: Vec<_> = t[dst]
+            .iter()
+            .filter_map(|node| node.tabs().map(<[_]>::to_vec))
+            .collect();
+        assert_eq!(leaves, [vec![1], vec![5, 4], vec![2], vec![3]]);
+        let right = NodeIndex::root().right();
+        assert!(t[dst][right].is_vertical());
+        assert!(t[dst][right.left()].is_leaf() && t[dst][right.right()].is_leaf());
+    }
+
+    #[test]
+    fn maximize_only_leaves() {
+        let mut t = DockState::new(vec![0]);
+        let [_, new] = t
+            .main_surface_mut()
+            .split_right(NodeIndex::root(), 0.5, vec![1]);
+        t.set_maximized_leaf(Some((SurfaceIndex::main(), NodeIndex::root())));
+        assert_eq!(t.maximized_leaf(), None);
+
+        t.set_maximized_leaf(Some((SurfaceIndex::main(), new)));
+        assert_eq!(t.maximized_leaf(), Some((SurfaceIndex::main(), new)));
+        assert_eq!(t.main_surface().num_tabs(), 2);
+
+        // Removing the leaf moves the other one into the root.
+        t.remove_tab((SurfaceIndex::main(), new, TabIndex(0)));
+        assert_eq!(t.maximized_leaf(), None);
+    }
+
+    #[test]
+    fn move_leaf_into_another() {
+        let mut t = DockState::new(vec![0, 1]);
+        let [old, new] = t
+            .main_surface_mut()
+            .split_below(NodeIndex::root(), 0.5, vec![2]);
+        t.move_leaf(
+            (SurfaceIndex::main(), old),
+            (SurfaceIndex::main(), new, TabInsert::Append),
+        );
+
+        let root = t.main_surface().root_node().unwrap();
+        assert_eq!(root.tabs(), Some(&[2, 0, 1][..]));
+    }
+
+    #[test]
+    fn hit_test_prefers_windows() {
+        let mut t = DockState::new(vec![0]);
+        let [left, right] = t
+            .main_surface_mut()
+            .split_right(NodeIndex::root(), 0.5, vec![1]);
+        let window = t.add_window(vec![2]);
+        let rect = |x0, x1| Rect::from_x_y_ranges(x0..=x1, 0.0..=100.0);
+        t[SurfaceIndex::main()][left].set_rect(rect(0.0, 50.0));
+        t[SurfaceIndex::main()][right].set_rect(rect(50.0, 100.0));
+        t[window][NodeIndex::root()].set_rect(rect(80.0, 120.0));
+
+        let at = |x| egui::pos2(x, 10.0);
+        assert_eq!(t.hit_test(at(10.0)), Some((SurfaceIndex::main(), left)));
+        assert_eq!(t.hit_test(at(60.0)), Some((SurfaceIndex::main(), right)));
+        assert_eq!(t.hit_test(at(90.0)), Some((window, NodeIndex::root())));
+        assert_eq!(t.hit_test(at(200.0)), None);
+    }
+
+    #[test]
+    fn close_tab_asks_the_viewer() {
+        struct Viewer;
+        impl TabViewer for Viewer {
+            type Tab = i32;
+            fn title(&mut self, tab: &mut i32) -> egui::WidgetText {
+                tab.to_string().into()
+            }
+            fn ui(&mut self, _ui: &mut egui::Ui, _tab: &mut i32) {}
+            fn is_closeable(&self, tab: &i32) -> bool {
+                *tab != 0
+            }
+            fn on_close(&mut self, tab: &mut i32) -> OnCloseResponse {
+                match tab {
+                    1 => OnCloseResponse::Close,
+                    _ => OnCloseResponse::Focus,
+                }
+            }
+        }
+
+        let mut t = DockState::new(vec![0, 1, 2]);
+        let root = NodeIndex::root();
+        assert!(!t.close_tab((SurfaceIndex::main(), root, TabIndex(0)), &mut Viewer));
+        assert!(t.close_tab((SurfaceIndex::main(), root, TabIndex(1)), &mut Viewer));
+        assert!(!t.close_tab((SurfaceIndex::main(), root, TabIndex(1)), &mut Viewer));
+        assert_eq!(t.main_surface()[root].tabs(), Some(&[0, 2][..]));
+        assert_eq!(t.find_active_focused().map(|(_, tab)| *tab), Some(2));
+    }
+
+    #[test]
+    fn move_tabs_keeping_their_order() {
+        let mut t = DockState::new(vec![0, 1, 2, 3]);
+        let root = NodeIndex::root();
+        t.move_tabs(
+            (SurfaceIndex::main(), root),
+            &[TabIndex(2), TabIndex(0)],
+            (SurfaceIndex::main(), root, TabInsert::Insert(TabIndex(4))),
+        );
+        assert_eq!(t.main_surface()[root].tabs(), Some(&[1, 3, 0, 2][..]));
+
+        t.move_tabs(
+            (SurfaceIndex::main(), root),
+            &[TabIndex(1), TabIndex(3)],
+            (SurfaceIndex::main(), root, TabInsert::Split(Split::Below)),
+        );
+        assert_eq!(t.main_surface()[root.left()].tabs(), Some(&[1, 0][..]));
+        assert_eq!(t.main_surface()[root.right()].tabs(), Some(&[3, 2][..]));
+    }
+
+    #[test]
+    fn move_tab_into_split_with_fraction() {
+        let mut t = DockState::new(vec![0, 1]);
+        let root = NodeIndex::root();
+        let destination = (SurfaceIndex::main(), root, TabInsert::Split(Split::Right));
+        t.move_tab_with_split_fraction(
+            (SurfaceIndex::main(), root, TabIndex(1)),
+            destination,
+            0.25,
+        );
+
+        let Node::Horizontal(split) = &t.main_surface()[root] else {
+            panic!("the root must have been split horizontally");
+        };
+        assert_eq!(split.fraction, 0.75);
+        assert_eq!(
+            t.find_tab(&1),
+            Some((SurfaceIndex::main(), root.right(), TabIndex(0)))
+        );
+    }
+
+    #[test]
+    fn reset_layout_keeps_open_tabs() {
+        struct Viewer;
+        impl TabViewer for Viewer {
+            type Tab = (i32, &'static str);
+            fn title(&mut self, tab: &mut Self::Tab) -> egui::WidgetText {
+                tab.0.to_string().into()
+            }
+            fn ui(&mut self, _ui: &mut egui::Ui, _tab: &mut Self::Tab) {}
+        }
+
+        let mut t = DockState::new(vec![(1, "edited"), (3, "extra")]);
+        let mut default = DockState::new(vec![(1, "default")]);
+        default
+            .main_surface_mut()
+            .split_right(NodeIndex::root(), 0.5, vec![(2, "default")]);
+        t.reset_layout(default, &mut Viewer);
+
+        let tree = t.main_surface();
+        let root = NodeIndex::root();
+        assert_eq!(
+            tree[root.left()].tabs(),
+            Some(&[(1, "edited"), (3, "extra")][..])
+        );
+        assert_eq!(tree[root.right()].tabs(), Some(&[(2, "default")][..]));
+    }
 }
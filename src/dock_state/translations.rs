@@ -16,6 +16,25 @@ pub struct TabContextMenuTranslations {
     pub close_button: String,
     /// Button that undocks the tab into a new window.
     pub eject_button: String,
+    /// Button that pins the tab.
+    pub pin_button: String,
+    /// Button that unpins the tab.
+    pub unpin_button: String,
+    /// Button that closes the other tabs of the leaf, see [`StandardActions::CLOSE_OTHERS`](crate::StandardActions::CLOSE_OTHERS).
+    pub close_others_button: String,
+    /// Button that closes all tabs of the leaf, see [`StandardActions::CLOSE_ALL`](crate::StandardActions::CLOSE_ALL).
+    pub close_all_button: String,
+    /// Button that closes the tabs after the tab, see
+    /// [`StandardActions::CLOSE_TO_THE_RIGHT`](crate::StandardActions::CLOSE_TO_THE_RIGHT).
+    pub close_to_the_right_button: String,
+    /// Button that moves the tab into a new window, see [`StandardActions::FLOAT`](crate::StandardActions::FLOAT).
+    pub float_button: String,
+    /// Button that moves the tab into a new leaf to the right, see
+    /// [`StandardActions::SPLIT_RIGHT`](crate::StandardActions::SPLIT_RIGHT).
+    pub split_right_button: String,
+    /// Button that moves the tab into a new leaf below, see
+    /// [`StandardActions::SPLIT_BELOW`](crate::StandardActions::SPLIT_BELOW).
+    pub split_below_button: String,
 }
 
 /// Specifies text displayed in the primary buttons on a tab bar.
@@ -68,6 +87,14 @@ impl TabContextMenuTranslations {
         Self {
             close_button: String::from("Close"),
             eject_button: String::from("Eject"),
+            pin_button: String::from("Pin"),
+            unpin_button: String::from("Unpin"),
+            close_others_button: String::from("Close others"),
+            close_all_button: String::from("Close all"),
+            close_to_the_right_button: String::from("Close to the right"),
+            float_button: String::from("Float"),
+            split_right_button: String::from("Split right"),
+            split_below_button: String::from("Split down"),
         }
     }
 }
@@ -28,7 +28,7 @@ pub use tab_index::TabIndex;
 pub use tab_iter::TabIter;
 
 use egui::ahash::HashSet;
-use egui::Rect;
+use egui::{vec2, Rect, Vec2};
 use std::{
     cmp::max,
     fmt,
@@ -36,7 +36,7 @@ use std::{
     slice::{Iter, IterMut},
 };
 
-use crate::SurfaceIndex;
+use crate::{DockError, Style, SurfaceIndex};
 
 // ----------------------------------------------------------------------------
 
@@ -63,6 +63,7 @@ impl Split {
 }
 
 /// Specify how a tab should be added to a Node.
+#[derive(Clone, Copy, Debug)]
 pub enum TabInsert {
     /// Split the node in the given direction.
     Split(Split),
@@ -75,6 +76,7 @@ pub enum TabInsert {
 }
 
 /// The destination for a tab which is being moved.
+#[derive(Clone, Copy, Debug)]
 pub enum TabDestination {
     /// Move to a new window with this rect.
     Window(Rect),
@@ -105,6 +107,22 @@ impl TabDestination {
     }
 }
 
+/// What dropping the dragged tabs would do, see [`DockState::current_drop_kind`](crate::DockState::current_drop_kind).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DropKind {
+    /// The tabs would be added to another leaf.
+    IntoLeaf,
+
+    /// A leaf would be split to make room for the tabs.
+    Split,
+
+    /// The tabs would be moved into a new window.
+    NewWindow,
+
+    /// The tabs would be moved within the leaf they're already in.
+    Reorder,
+}
+
 /// Binary tree representing the relationships between [`Node`]s.
 ///
 /// # Implementation details
@@ -260,6 +278,103 @@ impl<Tab> Tree<Tab> {
         count
     }
 
+    /// Counts and returns the number of leaves in the whole tree.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use egui_dock::{DockState, NodeIndex};
+    /// let mut dock_state = DockState::new(vec!["tab 1", "tab 2"]);
+    /// assert_eq!(dock_state.main_surface().num_leaves(), 1);
+    ///
+    /// dock_state.main_surface_mut().split_below(NodeIndex::root(), 0.5, vec!["tab 3"]);
+    /// assert_eq!(dock_state.main_surface().num_leaves(), 2);
+    /// ```
+    #[inline]
+    pub fn num_leaves(&self) -> usize {
+        self.nodes.iter().filter(|node| node.is_leaf()).count()
+    }
+
+    /// Returns the number of nodes on the longest path from the root to a leaf,
+    /// or 0 if the tree is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use egui_dock::{DockState, NodeIndex};
+    /// let mut dock_state = DockState::new(vec!["tab 1"]);
+    /// assert_eq!(dock_state.main_surface().max_depth(), 1);
+    ///
+    /// let [_, new] = dock_state.main_surface_mut().split_below(NodeIndex::root(), 0.5, vec!["tab 2"]);
+    /// dock_state.main_surface_mut().split_right(new, 0.5, vec!["tab 3"]);
+    /// assert_eq!(dock_state.main_surface().max_depth(), 3);
+    /// ```
+    pub fn max_depth(&self) -> usize {
+        self.breadth_first_index_iter()
+            .filter(|&index| self[index].is_leaf())
+            .map(NodeIndex::level)
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Estimates the smallest size in which every leaf can show its tab bar and the margins of its content,
+    /// with the splits keeping their current fractions.
+    ///
+    /// This is a heuristic meant for sizing a container before the tree is shown, not the exact size the
+    /// [`DockArea`](crate::DockArea) will use. It doesn't know how much space the content of the tabs needs.
+    pub fn preferred_size(&self, style: &Style) -> Vec2 {
+        if self.is_empty() {
+            return Vec2::ZERO;
+        }
+        self.preferred_node_size(NodeIndex::root(), style)
+    }
+
+    fn preferred_node_size(&self, index: NodeIndex, style: &Style) -> Vec2 {
+        let child_size = |child: NodeIndex| {
+            if child.0 < self.nodes.len() {
+                self.preferred_node_size(child, style)
+            } else {
+                Vec2::ZERO
+            }
+        };
+        // The split must be large enough for both children to fit in their share of it.
+        let required = |fraction: f32, left: f32, right: f32| {
+            let left = if fraction > 0.0 { left / fraction } else { 0.0 };
+            let right = if fraction < 1.0 {
+                right / (1.0 - fraction)
+            } else {
+                0.0
+            };
+            left.max(right)
+        };
+        match &self[index] {
+            Node::Empty => Vec2::ZERO,
+            Node::Leaf(leaf) => {
+                let margin = style.tab.tab_body.inner_margin.sum();
+                let width = margin.x.max(style.tab.minimum_width.unwrap_or(0.0));
+                if leaf.collapsed {
+                    vec2(width, style.tab_bar_height())
+                } else {
+                    vec2(width, style.tab_bar_height() + margin.y)
+                }
+            }
+            Node::Horizontal(split) => {
+                let (left, right) = (child_size(index.left()), child_size(index.right()));
+                vec2(
+                    required(split.fraction, left.x, right.x) + style.separator.width,
+                    left.y.max(right.y),
+                )
+            }
+            Node::Vertical(split) => {
+                let (top, bottom) = (child_size(index.left()), child_size(index.right()));
+                vec2(
+                    top.x.max(bottom.x),
+                    required(split.fraction, top.y, bottom.y) + style.separator.width,
+                )
+            }
+        }
+    }
+
     /// Acquire a immutable borrow to the [`Node`] at the root of the tree.
     /// Returns [`None`] if the tree is empty.
     ///
@@ -533,6 +648,49 @@ impl<Tab> Tree<Tab> {
         index
     }
 
+    /// Splits the root of the tree so that the whole `other` tree takes the `split` side of it, keeping the nodes
+    /// of both trees as they are. Like [`split`](Self::split), the old root is given `fraction` of the space.
+    ///
+    /// The focused node stays the same, so does the tree if `other` is empty.
+    pub(crate) fn graft(&mut self, split: Split, fraction: f32, other: Tree<Tab>) {
+        if other.is_empty() {
+            return;
+        }
+        if self.is_empty() {
+            *self = other;
+            return;
+        }
+        let mut root = std::mem::replace(&mut self.nodes[0], Node::Empty);
+        let old_root = root.split(split, fraction);
+        root.set_collapsed(false);
+        self.nodes[0] = old_root;
+
+        let (old, new) = match split {
+            Split::Left | Split::Above => (NodeIndex::root().right(), NodeIndex::root().left()),
+            Split::Right | Split::Below => (NodeIndex::root().left(), NodeIndex::root().right()),
+        };
+        // Moves the node at `index` of a tree to where it is below `top`.
+        let below = |top: NodeIndex, index: usize| {
+            let depth = NodeIndex(index).level() - 1;
+            top.children_at(depth).start + index - ((1 << depth) - 1)
+        };
+        let mut nodes = vec![root];
+        let moved = [(old, std::mem::take(&mut self.nodes)), (new, other.nodes)];
+        for (top, tree_nodes) in moved {
+            for (index, node) in tree_nodes.into_iter().enumerate() {
+                let index = below(top, index);
+                if index >= nodes.len() {
+                    nodes.resize_with(index + 1, || Node::Empty);
+                }
+                nodes[index] = node;
+            }
+        }
+        self.nodes = nodes;
+        self.focused_node = self.focused_node.map(|node| NodeIndex(below(old, node.0)));
+        self.set_collapsed(false);
+        self.node_update_collapsed(old);
+    }
+
     fn first_leaf(&self, top: NodeIndex) -> Option<NodeIndex> {
         let left = top.left();
         let right = top.right();
@@ -581,6 +739,20 @@ impl<Tab> Tree<Tab> {
             .map(|_| node_index);
     }
 
+    /// Takes over the fractions of the splits and the focused leaf of `other`, a tree with the same nodes.
+    pub(crate) fn adjust_to(&mut self, other: &Self) {
+        for (node, other) in self.nodes.iter_mut().zip(&other.nodes) {
+            if let (
+                Node::Horizontal(split) | Node::Vertical(split),
+                Node::Horizontal(other) | Node::Vertical(other),
+            ) = (node, other)
+            {
+                split.fraction = other.fraction;
+            }
+        }
+        self.focused_node = other.focused_node;
+    }
+
     /// Removes the given node from the [`Tree`].
     ///
     /// # Panics
@@ -665,6 +837,50 @@ impl<Tab> Tree<Tab> {
         }
     }
 
+    /// Moves the tabs of the leaf at `node` to the end of its sibling, then removes the leaf,
+    /// so that the sibling takes the place of their parent.
+    ///
+    /// Leaves the tree untouched and returns an error if `node` is the root,
+    /// or if either `node` or its sibling isn't a [`Leaf`](Node::Leaf).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use egui_dock::{DockError, NodeIndex, Tree};
+    /// let mut tree = Tree::new(vec!["a", "b"]);
+    /// let [_, right] = tree.split_right(NodeIndex::root(), 0.5, vec!["c"]);
+    /// let [_, below] = tree.split_below(right, 0.5, vec!["d"]);
+    ///
+    /// // The sibling of the leaf with "a" and "b" isn't a leaf.
+    /// assert_eq!(
+    ///     tree.merge_into_sibling(NodeIndex::root().left()),
+    ///     Err(DockError::NotALeaf(right))
+    /// );
+    ///
+    /// assert_eq!(tree.merge_into_sibling(below), Ok(()));
+    /// assert_eq!(tree[right].tabs(), Some(&["c", "d"][..]));
+    /// ```
+    pub fn merge_into_sibling(&mut self, node: NodeIndex) -> Result<(), DockError> {
+        let check_leaf = |index: NodeIndex| match self.nodes.get(index.0) {
+            None | Some(Node::Empty) => Err(DockError::InvalidNode(index)),
+            Some(Node::Leaf(_)) => Ok(()),
+            Some(_) => Err(DockError::NotALeaf(index)),
+        };
+        check_leaf(node)?;
+        let parent = node.parent().ok_or(DockError::NoSibling(node))?;
+        let sibling = if node.is_left() {
+            parent.right()
+        } else {
+            parent.left()
+        };
+        check_leaf(sibling)?;
+
+        let tabs = std::mem::take(&mut self[node].get_leaf_mut().unwrap().tabs);
+        self[sibling].get_leaf_mut().unwrap().tabs.extend(tabs);
+        self.remove_leaf(node);
+        Ok(())
+    }
+
     /// Pushes a tab to the first `Leaf` it finds or create a new leaf if an `Empty` node is encountered.
     pub fn push_to_first_leaf(&mut self, tab: Tab) {
         for (index, node) in &mut self.nodes.iter_mut().enumerate() {
@@ -689,6 +905,9 @@ impl<Tab> Tree<Tab> {
     }
 
     /// Sets which is the active tab within a specific node.
+    ///
+    /// This can be done before the tree is shown for the first time, to pick the initially active tabs.
+    /// If `tab_index` is out of bounds, the active tab isn't changed.
     #[inline]
     pub fn set_active_tab(
         &mut self,
@@ -986,4 +1205,25 @@ mod test {
         tree.retain_tabs(|_| true);
         assert!(tree.find_tab(&Tab(0)).is_some());
     }
+
+    /// Tests whether pinned tabs stay at the start of their leaf as tabs are added and removed
+    #[test]
+    fn pinned_tabs_stay_in_front() {
+        let mut tree = Tree::new(vec![Tab(0), Tab(1), Tab(2)]);
+        let leaf = tree[NodeIndex::root()].get_leaf_mut().unwrap();
+        leaf.set_active_tab(TabIndex(1));
+        assert_eq!(leaf.set_pinned(TabIndex(2), true), TabIndex(0));
+        assert_eq!(leaf.tabs(), [Tab(2), Tab(0), Tab(1)]);
+        assert_eq!(leaf.active, TabIndex(2));
+
+        leaf.insert_tab(TabIndex(0), Tab(3));
+        assert_eq!(leaf.tabs(), [Tab(2), Tab(3), Tab(0), Tab(1)]);
+        assert_eq!(leaf.pinned_count(), 1);
+
+        leaf.retain_tabs(|tab| *tab != Tab(2));
+        assert_eq!(leaf.pinned_count(), 0);
+        leaf.set_pinned(TabIndex(2), true);
+        assert_eq!(leaf.set_pinned(TabIndex(0), false), TabIndex(0));
+        assert_eq!(leaf.pinned_count(), 0);
+    }
 }
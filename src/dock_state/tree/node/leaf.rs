@@ -1,6 +1,6 @@
-use egui::Rect;
+use egui::{Rangef, Rect};
 
-use crate::TabIndex;
+use crate::{TabBarPosition, TabIndex};
 
 /// The inner data of a [``Node::Leaf``](crate::Node), which contains tabs and can be collapsed.
 #[derive(Clone, Debug)]
@@ -23,6 +23,26 @@ pub struct LeafNode<Tab> {
 
     /// Whether the leaf is collapsed.
     pub collapsed: bool,
+
+    /// How many tabs at the start of [`tabs`](Self::tabs) are pinned.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) pinned: usize,
+
+    /// Where the tab bar is placed, or [`None`] to follow [`TabBarStyle::position`](crate::TabBarStyle::position).
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) tab_bar_position: Option<TabBarPosition>,
+
+    /// The range of widths this leaf can be resized to, or [`None`] if it isn't constrained.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) width_range: Option<Rangef>,
+
+    /// The range of heights this leaf can be resized to, or [`None`] if it isn't constrained.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) height_range: Option<Rangef>,
+
+    /// Whether the tabs didn't fit in the tab bar when it was last shown.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) tab_overflow: Option<bool>,
 }
 
 impl<Tab> LeafNode<Tab> {
@@ -35,6 +55,11 @@ impl<Tab> LeafNode<Tab> {
             active: TabIndex(0),
             scroll: 0.0,
             collapsed: false,
+            pinned: 0,
+            tab_bar_position: None,
+            width_range: None,
+            height_range: None,
+            tab_overflow: None,
         }
     }
 
@@ -70,6 +95,53 @@ impl<Tab> LeafNode<Tab> {
         self.rect
     }
 
+    /// Returns whether the tabs of this [`LeafNode`] didn't fit in its tab bar when it was last shown,
+    /// or `None` if its tab bar wasn't laid out yet.
+    #[inline]
+    pub fn tab_overflow(&self) -> Option<bool> {
+        self.tab_overflow
+    }
+
+    /// Returns where the tab bar of this [`LeafNode`] is placed,
+    /// or [`None`] if it follows [`TabBarStyle::position`](crate::TabBarStyle::position).
+    #[inline]
+    pub fn tab_bar_position(&self) -> Option<TabBarPosition> {
+        self.tab_bar_position
+    }
+
+    /// Places the tab bar of this [`LeafNode`] on a given side,
+    /// or makes it follow [`TabBarStyle::position`](crate::TabBarStyle::position) with [`None`].
+    #[inline]
+    pub fn set_tab_bar_position(&mut self, position: impl Into<Option<TabBarPosition>>) {
+        self.tab_bar_position = position.into();
+    }
+
+    /// Returns the range of widths this [`LeafNode`] can be resized to, or [`None`] if it isn't constrained.
+    #[inline]
+    pub fn width_range(&self) -> Option<Rangef> {
+        self.width_range
+    }
+
+    /// Returns the range of heights this [`LeafNode`] can be resized to, or [`None`] if it isn't constrained.
+    #[inline]
+    pub fn height_range(&self) -> Option<Rangef> {
+        self.height_range
+    }
+
+    /// Constrains the width and height of this [`LeafNode`], or lifts a constraint with [`None`].
+    ///
+    /// The constraints are kept both when dragging separators and when the dock area is resized,
+    /// as far as the other leaves allow. A maximum of [`f32::INFINITY`] can't be serialized, use [`f32::MAX`] instead.
+    #[inline]
+    pub fn set_size_range(
+        &mut self,
+        width: impl Into<Option<Rangef>>,
+        height: impl Into<Option<Rangef>>,
+    ) {
+        self.width_range = width.into();
+        self.height_range = height.into();
+    }
+
     /// Get immutable access to the ``Tab``s of this [`LeafNode`]
     #[inline]
     pub fn tabs(&self) -> &[Tab] {
@@ -82,6 +154,54 @@ impl<Tab> LeafNode<Tab> {
         &mut self.tabs
     }
 
+    /// Returns how many tabs at the start of this [`LeafNode`] are pinned.
+    #[inline]
+    pub fn pinned_count(&self) -> usize {
+        self.pinned.min(self.tabs.len())
+    }
+
+    /// Returns whether the tab at `tab_index` is pinned.
+    #[inline]
+    pub fn is_pinned(&self, tab_index: TabIndex) -> bool {
+        tab_index.0 < self.pinned_count()
+    }
+
+    /// Pins or unpins the tab at `tab_index`, and returns where it is then.
+    ///
+    /// Pinned tabs are kept at the start of the tab list, so a newly pinned tab moves behind the other pinned tabs,
+    /// and a newly unpinned one in front of the other unpinned tabs. The active tab stays the same.
+    ///
+    /// # Panics
+    ///
+    /// if ``tab_index`` is out of bounds for the tab list
+    pub fn set_pinned(&mut self, tab_index: TabIndex, pinned: bool) -> TabIndex {
+        assert!(tab_index.0 < self.tabs.len(), "tab index out of bounds");
+        if self.is_pinned(tab_index) == pinned {
+            return tab_index;
+        }
+        let count = self.pinned_count();
+        let (new_index, count) = if pinned {
+            (TabIndex(count), count + 1)
+        } else {
+            (TabIndex(count - 1), count - 1)
+        };
+
+        let active = self.active;
+        let tab = self.tabs.remove(tab_index.0);
+        self.tabs.insert(new_index.0, tab);
+        self.pinned = count;
+        self.active = if active == tab_index {
+            new_index
+        } else if tab_index < active && active <= new_index {
+            TabIndex(active.0 - 1)
+        } else if new_index <= active && active < tab_index {
+            TabIndex(active.0 + 1)
+        } else {
+            active
+        };
+        new_index
+    }
+
     /// Append a ``Tab`` to the end of this [`LeafNode`]s tab list.
     ///
     /// This will also focus the added tab.
@@ -94,7 +214,7 @@ impl<Tab> LeafNode<Tab> {
 
     /// Insert a ``Tab`` to this [`LeafNode`]s tab list at the specified [`TabIndex`].
     ///
-    /// This will also focus the added tab.
+    /// This will also focus the added tab. The tab is inserted after the pinned tabs, if ``tab_index`` is among them.
     ///
     /// # Panics
     ///
@@ -102,7 +222,8 @@ impl<Tab> LeafNode<Tab> {
     #[track_caller]
     #[inline]
     pub fn insert_tab(&mut self, tab_index: impl Into<TabIndex>, tab: Tab) {
-        let tab_index = tab_index.into();
+        let mut tab_index = tab_index.into();
+        tab_index.0 = tab_index.0.max(self.pinned_count());
         self.tabs.insert(tab_index.0, tab);
         self.active = tab_index;
     }
@@ -120,15 +241,29 @@ impl<Tab> LeafNode<Tab> {
         if index <= self.active {
             self.active.0 = self.active.0.saturating_sub(1);
         }
+        if self.is_pinned(index) {
+            self.pinned -= 1;
+        }
         Some(self.tabs.remove(index.0))
     }
 
     /// Removes all tabs for which `predicate` returns `false`.
-    pub fn retain_tabs<F>(&mut self, predicate: F)
+    pub fn retain_tabs<F>(&mut self, mut predicate: F)
     where
         F: FnMut(&mut Tab) -> bool,
     {
-        self.tabs.retain_mut(predicate);
+        let mut index = 0;
+        let count = self.pinned_count();
+        let mut pinned = count;
+        self.tabs.retain_mut(|tab| {
+            let retained = predicate(tab);
+            if !retained && index < count {
+                pinned -= 1;
+            }
+            index += 1;
+            retained
+        });
+        self.pinned = pinned;
     }
 
     /// Return the area and tab which is currently representing this [`LeafNode`]
@@ -1,5 +1,5 @@
-use crate::{Split, TabIndex};
-use egui::Rect;
+use crate::{Split, TabBarPosition, TabIndex};
+use egui::{Rangef, Rect};
 
 mod leaf;
 mod split;
@@ -52,6 +52,16 @@ impl<Tab> Node<Tab> {
         Self::Leaf(LeafNode::new(tabs))
     }
 
+    /// Constructs a leaf node with a given list of `tabs`, of which the one at `active` is active.
+    ///
+    /// If `active` is out of bounds, the last tab is active.
+    pub fn leaf_with_active(tabs: Vec<Tab>, active: impl Into<TabIndex>) -> Self {
+        let active = TabIndex(active.into().0.min(tabs.len().saturating_sub(1)));
+        let mut leaf = LeafNode::new(tabs);
+        leaf.active = active;
+        Self::Leaf(leaf)
+    }
+
     /// Sets the area occupied by the node.
     ///
     /// If the node is a ``Node::Empty``, this will do nothing.
@@ -257,6 +267,84 @@ impl<Tab> Node<Tab> {
         }
     }
 
+    /// Places the tab bar of a leaf on a given side of it,
+    /// or makes it follow [`TabBarStyle::position`](crate::TabBarStyle::position) with [`None`].
+    ///
+    /// Does nothing if the node isn't a [`Leaf`](Node::Leaf).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use egui_dock::{DockState, NodeIndex, TabBarPosition};
+    /// let mut dock_state = DockState::new(vec!["a", "b"]);
+    /// let [_, right] = dock_state
+    ///     .main_surface_mut()
+    ///     .split_right(NodeIndex::root(), 0.7, vec!["sidebar"]);
+    ///
+    /// dock_state.main_surface_mut()[right].set_tab_bar_position(TabBarPosition::Right);
+    /// assert_eq!(dock_state.main_surface()[right].tab_bar_position(), Some(TabBarPosition::Right));
+    /// ```
+    #[inline]
+    pub fn set_tab_bar_position(&mut self, position: impl Into<Option<TabBarPosition>>) {
+        if let Node::Leaf(leaf) = self {
+            leaf.set_tab_bar_position(position);
+        }
+    }
+
+    /// Returns where the tab bar of a leaf is placed, or [`None`] if it follows
+    /// [`TabBarStyle::position`](crate::TabBarStyle::position) or the node isn't a [`Leaf`](Node::Leaf).
+    #[inline]
+    pub fn tab_bar_position(&self) -> Option<TabBarPosition> {
+        match self {
+            Node::Leaf(leaf) => leaf.tab_bar_position,
+            _ => None,
+        }
+    }
+
+    /// Constrains the width and height of a leaf, or lifts a constraint with [`None`].
+    ///
+    /// Separators can't be dragged past the constraints, and they are kept when the dock area
+    /// or a window surface is resized, as far as the other leaves allow.
+    ///
+    /// Does nothing if the node isn't a [`Leaf`](Node::Leaf).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use egui_dock::{DockState, NodeIndex};
+    /// # use egui::Rangef;
+    /// let mut dock_state = DockState::new(vec!["a", "b"]);
+    /// let [_, right] = dock_state
+    ///     .main_surface_mut()
+    ///     .split_right(NodeIndex::root(), 0.7, vec!["sidebar"]);
+    ///
+    /// dock_state.main_surface_mut()[right].set_size_range(Rangef::new(150.0, 400.0), None);
+    /// assert_eq!(
+    ///     dock_state.main_surface()[right].size_range(),
+    ///     (Some(Rangef::new(150.0, 400.0)), None)
+    /// );
+    /// ```
+    #[inline]
+    pub fn set_size_range(
+        &mut self,
+        width: impl Into<Option<Rangef>>,
+        height: impl Into<Option<Rangef>>,
+    ) {
+        if let Node::Leaf(leaf) = self {
+            leaf.set_size_range(width, height);
+        }
+    }
+
+    /// Returns the ranges of widths and heights a leaf is constrained to,
+    /// which are both [`None`] if the node isn't a [`Leaf`](Node::Leaf).
+    #[inline]
+    pub fn size_range(&self) -> (Option<Rangef>, Option<Rangef>) {
+        match self {
+            Node::Leaf(leaf) => (leaf.width_range, leaf.height_range),
+            _ => (None, None),
+        }
+    }
+
     /// Sets the collapsing state of the node.
     ///
     /// # Panics
@@ -323,7 +411,7 @@ impl<Tab> Node<Tab> {
 
     /// Returns a new [`Node`] while mapping and filtering the tab type.
     /// If this [`Node`] remains empty, it will change to [`Node::Empty`].
-    pub fn filter_map_tabs<F, NewTab>(&self, function: F) -> Node<NewTab>
+    pub fn filter_map_tabs<F, NewTab>(&self, mut function: F) -> Node<NewTab>
     where
         F: FnMut(&Tab) -> Option<NewTab>,
     {
@@ -336,8 +424,24 @@ impl<Tab> Node<Tab> {
                     active,
                     scroll,
                     collapsed,
+                    pinned,
+                    tab_bar_position,
+                    width_range,
+                    height_range,
+                    tab_overflow,
                 } = leaf;
-                let tabs: Vec<_> = tabs.iter().filter_map(function).collect();
+                let mut retained_pinned = 0;
+                let tabs: Vec<_> = tabs
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(index, tab)| {
+                        let tab = function(tab)?;
+                        if index < *pinned {
+                            retained_pinned += 1;
+                        }
+                        Some(tab)
+                    })
+                    .collect();
                 if tabs.is_empty() {
                     Node::Empty
                 } else {
@@ -348,6 +452,11 @@ impl<Tab> Node<Tab> {
                         active: *active,
                         scroll: *scroll,
                         collapsed: *collapsed,
+                        pinned: retained_pinned,
+                        tab_bar_position: *tab_bar_position,
+                        width_range: *width_range,
+                        height_range: *height_range,
+                        tab_overflow: *tab_overflow,
                     })
                 }
             }
@@ -0,0 +1,161 @@
+use crate::{DockState, LeafNode, Node, NodeIndex, Surface, SurfaceIndex, Tree, WindowState};
+
+/// Receives callbacks while [`DockState::walk`] goes through a dock state.
+///
+/// Surfaces are visited in order. Within a surface, nodes are visited depth first, the left (or top) child of a
+/// split before the right (or bottom) one, with [`on_split_enter`](Self::on_split_enter) and
+/// [`on_split_leave`](Self::on_split_leave) surrounding the children of each split.
+pub trait DockVisitor<Tab> {
+    /// Called before the nodes of a window surface are visited.
+    fn on_window(&mut self, _surface: SurfaceIndex, _state: &WindowState) {}
+
+    /// Called for every leaf.
+    fn on_leaf(&mut self, _surface: SurfaceIndex, _node: NodeIndex, _leaf: &LeafNode<Tab>) {}
+
+    /// Called before the children of a [`Horizontal`](Node::Horizontal) or [`Vertical`](Node::Vertical) node
+    /// are visited.
+    fn on_split_enter(&mut self, _surface: SurfaceIndex, _node: NodeIndex, _split: &Node<Tab>) {}
+
+    /// Called after the children of a [`Horizontal`](Node::Horizontal) or [`Vertical`](Node::Vertical) node
+    /// were visited.
+    fn on_split_leave(&mut self, _surface: SurfaceIndex, _node: NodeIndex, _split: &Node<Tab>) {}
+}
+
+/// Same as [`DockVisitor`], but receives mutable access, see [`DockState::walk_mut`].
+pub trait DockVisitorMut<Tab> {
+    /// Called before the nodes of a window surface are visited.
+    fn on_window(&mut self, _surface: SurfaceIndex, _state: &mut WindowState) {}
+
+    /// Called for every leaf.
+    fn on_leaf(&mut self, _surface: SurfaceIndex, _node: NodeIndex, _leaf: &mut LeafNode<Tab>) {}
+
+    /// Called before the children of a [`Horizontal`](Node::Horizontal) or [`Vertical`](Node::Vertical) node
+    /// are visited.
+    fn on_split_enter(&mut self, _surface: SurfaceIndex, _node: NodeIndex, _split: &mut Node<Tab>) {
+    }
+
+    /// Called after the children of a [`Horizontal`](Node::Horizontal) or [`Vertical`](Node::Vertical) node
+    /// were visited.
+    fn on_split_leave(&mut self, _surface: SurfaceIndex, _node: NodeIndex, _split: &mut Node<Tab>) {
+    }
+}
+
+impl<Tab> DockState<Tab> {
+    /// Goes through all surfaces and nodes, calling the matching functions of the `visitor`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use egui_dock::{DockState, DockVisitor, LeafNode, Node, NodeIndex, SurfaceIndex};
+    /// // Describes the layout as nested lists, e.g. `[| a [/ b c]]`.
+    /// struct Describe(String);
+    ///
+    /// impl DockVisitor<&str> for Describe {
+    ///     fn on_leaf(&mut self, _: SurfaceIndex, _: NodeIndex, leaf: &LeafNode<&str>) {
+    ///         self.0 += " ";
+    ///         self.0 += &leaf.tabs.join(",");
+    ///     }
+    ///
+    ///     fn on_split_enter(&mut self, _: SurfaceIndex, node: NodeIndex, split: &Node<&str>) {
+    ///         if node != NodeIndex::root() {
+    ///             self.0 += " ";
+    ///         }
+    ///         self.0 += if split.is_horizontal() { "[|" } else { "[/" };
+    ///     }
+    ///
+    ///     fn on_split_leave(&mut self, _: SurfaceIndex, _: NodeIndex, _: &Node<&str>) {
+    ///         self.0 += "]";
+    ///     }
+    /// }
+    ///
+    /// let mut dock_state = DockState::new(vec!["a"]);
+    /// let [_, right] = dock_state.main_surface_mut().split_right(NodeIndex::root(), 0.5, vec!["b"]);
+    /// dock_state.main_surface_mut().split_below(right, 0.5, vec!["c"]);
+    ///
+    /// let mut describe = Describe(String::new());
+    /// dock_state.walk(&mut describe);
+    /// assert_eq!(describe.0, "[| a [/ b c]]");
+    /// ```
+    pub fn walk(&self, visitor: &mut impl DockVisitor<Tab>) {
+        for (index, surface) in self.iter_surfaces().enumerate() {
+            let surface_index = SurfaceIndex(index);
+            let tree = match surface {
+                Surface::Empty => continue,
+                Surface::Main(tree) => tree,
+                Surface::Window(tree, state) => {
+                    visitor.on_window(surface_index, state);
+                    tree
+                }
+            };
+            if !tree.is_empty() {
+                walk_node(tree, surface_index, NodeIndex::root(), visitor);
+            }
+        }
+    }
+
+    /// Same as [`walk`](Self::walk), but the `visitor` can modify the surfaces and nodes it visits.
+    ///
+    /// If [`DockVisitorMut::on_split_enter`] turns a split into a different kind of node,
+    /// its children aren't visited and [`DockVisitorMut::on_split_leave`] isn't called for it.
+    pub fn walk_mut(&mut self, visitor: &mut impl DockVisitorMut<Tab>) {
+        for (index, surface) in self.iter_surfaces_mut().enumerate() {
+            let surface_index = SurfaceIndex(index);
+            let tree = match surface {
+                Surface::Empty => continue,
+                Surface::Main(tree) => tree,
+                Surface::Window(tree, state) => {
+                    visitor.on_window(surface_index, state);
+                    tree
+                }
+            };
+            if !tree.is_empty() {
+                walk_node_mut(tree, surface_index, NodeIndex::root(), visitor);
+            }
+        }
+    }
+}
+
+fn walk_node<Tab>(
+    tree: &Tree<Tab>,
+    surface: SurfaceIndex,
+    index: NodeIndex,
+    visitor: &mut impl DockVisitor<Tab>,
+) {
+    match &tree[index] {
+        Node::Empty => (),
+        Node::Leaf(leaf) => visitor.on_leaf(surface, index, leaf),
+        split @ (Node::Horizontal(_) | Node::Vertical(_)) => {
+            visitor.on_split_enter(surface, index, split);
+            for child in [index.left(), index.right()] {
+                if child.0 < tree.len() {
+                    walk_node(tree, surface, child, visitor);
+                }
+            }
+            visitor.on_split_leave(surface, index, split);
+        }
+    }
+}
+
+fn walk_node_mut<Tab>(
+    tree: &mut Tree<Tab>,
+    surface: SurfaceIndex,
+    index: NodeIndex,
+    visitor: &mut impl DockVisitorMut<Tab>,
+) {
+    match &mut tree[index] {
+        Node::Empty => (),
+        Node::Leaf(leaf) => visitor.on_leaf(surface, index, leaf),
+        split @ (Node::Horizontal(_) | Node::Vertical(_)) => {
+            visitor.on_split_enter(surface, index, split);
+            if !tree[index].is_parent() {
+                return;
+            }
+            for child in [index.left(), index.right()] {
+                if child.0 < tree.len() {
+                    walk_node_mut(tree, surface, child, visitor);
+                }
+            }
+            visitor.on_split_leave(surface, index, &mut tree[index]);
+        }
+    }
+}
@@ -27,6 +27,18 @@ pub struct WindowState {
 
     /// True if the window is minimized
     minimized: bool,
+
+    /// True if the window is shown in its own OS-level viewport instead of inside the main one.
+    #[cfg_attr(feature = "serde", serde(default))]
+    viewport: bool,
+
+    /// True if the window should be brought to the front the next time it's shown.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    raise_requested: bool,
+
+    /// The position of this window in the stack of `egui` layers the last time it was shown, higher is more on top.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    stack_order: usize,
 }
 
 impl Default for WindowState {
@@ -39,6 +51,9 @@ impl Default for WindowState {
             expanded_height: None,
             new: true,
             minimized: false,
+            viewport: false,
+            raise_requested: false,
+            stack_order: 0,
         }
     }
 }
@@ -75,6 +90,29 @@ impl WindowState {
         self.dragged
     }
 
+    /// Returns if this window is shown in its own OS-level viewport, rather than inside the viewport of the
+    /// [`DockArea`](crate::DockArea).
+    pub fn is_viewport(&self) -> bool {
+        self.viewport
+    }
+
+    /// Set whether this window is shown in its own OS-level viewport.
+    ///
+    /// This only has an effect if the `egui` backend supports multiple viewports,
+    /// otherwise the window is shown inside the viewport of the [`DockArea`](crate::DockArea).
+    pub fn set_viewport(&mut self, viewport: bool) -> &mut Self {
+        self.viewport = viewport;
+        self
+    }
+
+    /// Brings this window in front of the other windows the next time it's shown.
+    ///
+    /// If the window is shown in its own viewport, that viewport gets focused instead.
+    pub fn raise(&mut self) -> &mut Self {
+        self.raise_requested = true;
+        self
+    }
+
     /// Set the height of this window when it is expanded.
     #[inline(always)]
     pub(crate) fn set_expanded_height(&mut self, height: f32) -> &mut Self {
@@ -103,6 +141,11 @@ impl WindowState {
         self.expanded_height.take()
     }
 
+    #[inline(always)]
+    pub(crate) fn raise_requested(&mut self) -> bool {
+        std::mem::take(&mut self.raise_requested)
+    }
+
     #[inline(always)]
     pub(crate) fn toggle_minimized(&mut self) {
         self.minimized = !self.minimized;
@@ -113,6 +156,21 @@ impl WindowState {
         self.minimized
     }
 
+    #[inline(always)]
+    pub(crate) fn set_screen_rect(&mut self, rect: Rect) {
+        self.screen_rect = Some(rect);
+    }
+
+    #[inline(always)]
+    pub(crate) fn set_stack_order(&mut self, stack_order: usize) {
+        self.stack_order = stack_order;
+    }
+
+    #[inline(always)]
+    pub(crate) fn stack_order(&self) -> usize {
+        self.stack_order
+    }
+
     //the 'static in this case means that the `open` field is always `None`
     pub(crate) fn create_window(&mut self, id: Id, bounds: Rect) -> egui::Window<'static> {
         let new = self.new;
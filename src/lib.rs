@@ -198,6 +198,14 @@
 //!     tab_context_menu: TabContextMenuTranslations {
 //!         close_button: "Zamknij zakładkę".to_string(),
 //!         eject_button: "Przenieś zakładkę do nowego okna".to_string(),
+//!         pin_button: "Przypnij zakładkę".to_string(),
+//!         unpin_button: "Odepnij zakładkę".to_string(),
+//!         close_others_button: "Zamknij pozostałe zakładki".to_string(),
+//!         close_all_button: "Zamknij wszystkie zakładki".to_string(),
+//!         close_to_the_right_button: "Zamknij zakładki po prawej".to_string(),
+//!         float_button: "Przenieś zakładkę do okna".to_string(),
+//!         split_right_button: "Podziel w prawo".to_string(),
+//!         split_below_button: "Podziel w dół".to_string(),
 //!     },
 //!     leaf: LeafTranslations {
 //!         close_button_disabled_tooltip: "Ten węzeł zawiera niezamykalne zakładki.".to_string(),
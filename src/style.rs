@@ -1,4 +1,8 @@
-use egui::{ecolor::*, CornerRadius, Margin, Stroke};
+use egui::{
+    ecolor::*, Align, CornerRadius, CursorIcon, Key, KeyboardShortcut, Margin, Modifiers, Painter,
+    Rect, Stroke,
+};
+use std::sync::Arc;
 
 /// Left or right alignment for tab add button.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
@@ -9,6 +13,80 @@ pub enum TabAddAlign {
     Right,
 }
 
+/// Which side of a leaf its tab bar is placed on, see [`TabBarStyle::position`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum TabBarPosition {
+    /// Above the content of the leaf.
+    #[default]
+    Top,
+
+    /// Below the content of the leaf.
+    Bottom,
+
+    /// To the left of the content, with the tabs stacked vertically and their titles reading upwards.
+    Left,
+
+    /// To the right of the content, with the tabs stacked vertically and their titles reading downwards.
+    Right,
+}
+
+impl TabBarPosition {
+    /// Returns `true` for [`Left`](Self::Left) and [`Right`](Self::Right), where the tabs are stacked vertically.
+    #[inline]
+    pub const fn is_vertical(self) -> bool {
+        matches!(self, Self::Left | Self::Right)
+    }
+}
+
+/// How a tab bar deals with tabs that don't fit in it, see [`TabBarStyle::overflow_mode`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum OverflowMode {
+    /// The tabs are scrolled with the mouse wheel, or with arrow buttons at the ends of the tab bar.
+    #[default]
+    Scroll,
+
+    /// The tabs are scrolled with the mouse wheel, and a "»" button lists the hidden tabs.
+    Menu,
+
+    /// Every tab shrinks to at most an equal share of the tab bar, truncating its title.
+    Shrink,
+}
+
+/// Marks the active tab of each leaf, in addition to its [`TabStyle::active`] style.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum ActiveTabIndicator {
+    /// Only the background of the tab tells it apart.
+    #[default]
+    Fill,
+
+    /// A line along the bottom edge of the tab.
+    Underline {
+        /// Color of the line.
+        color: Color32,
+        /// Height of the line.
+        thickness: f32,
+    },
+
+    /// A line along the top edge of the tab.
+    Overline {
+        /// Color of the line.
+        color: Color32,
+        /// Height of the line.
+        thickness: f32,
+    },
+
+    /// A fully rounded background behind the title, inset from the edges of the tab by `margin`.
+    Pill {
+        /// Color of the background.
+        color: Color32,
+        /// Space between the background and the edges of the tab.
+        margin: f32,
+    },
+}
+
 /// Lets you change how tabs and the [`DockArea`](crate::DockArea) should look and feel.
 /// [`Style`] is divided into several, more specialized structs that handle individual
 /// elements of the UI.
@@ -50,6 +128,8 @@ pub enum TabAddAlign {
 #[allow(missing_docs)]
 pub struct Style {
     /// Sets padding to indent from the edges of the window. By `Default` it's `None`.
+    ///
+    /// The default [`DockArea::window_bounds`](crate::DockArea::window_bounds) are inset by it as well.
     pub dock_area_padding: Option<Margin>,
 
     pub main_surface_border_stroke: Stroke,
@@ -60,6 +140,14 @@ pub struct Style {
     pub tab_bar: TabBarStyle,
     pub tab: TabStyle,
     pub overlay: OverlayStyle,
+    pub shortcuts: Shortcuts,
+    pub animation: AnimationStyle,
+
+    /// Whether anything in the [`DockArea`](crate::DockArea) moves smoothly, such as scrolling the tab bar and the
+    /// animations timed by [`Style::animation`].
+    /// When disabled, every change takes effect immediately, which helps both accessibility and tests.
+    /// By `Default` it's `true`.
+    pub animations_enabled: bool,
 }
 
 /// Specifies the look and feel of buttons.
@@ -135,8 +223,19 @@ pub struct ButtonsStyle {
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct SeparatorStyle {
     /// Width of the rectangle separator between nodes. By `Default` it's `1.0`.
+    ///
+    /// With a width of `0.0`, nodes touch each other, and the separator stays draggable within
+    /// [`extra_interact_width`](Self::extra_interact_width) without being drawn.
     pub width: f32,
 
+    /// Width of the rectangle separator while it's hovered, if it differs from [`width`](Self::width).
+    /// This only affects how the separator is drawn, not the layout of the nodes. By `Default` it's `None`.
+    pub width_hovered: Option<f32>,
+
+    /// Width of the rectangle separator while it's dragged, if it differs from [`width`](Self::width).
+    /// This only affects how the separator is drawn, not the layout of the nodes. By `Default` it's `None`.
+    pub width_dragged: Option<f32>,
+
     /// Extra width added to the "logical thickness" of the rectangle so it's
     /// easier to grab. By `Default` it's `4.0`.
     pub extra_interact_width: f32,
@@ -145,6 +244,16 @@ pub struct SeparatorStyle {
     /// `bigger value > less allowed offset` for the current window size.
     pub extra: f32,
 
+    /// Narrowest a node laid out side by side with another one can be made by dragging the separator between them,
+    /// when it's bigger than [`extra`](Self::extra). A leaf narrower than twice this width can't be split left or right by
+    /// dropping a tab onto it. By `Default` it's `0.0`.
+    pub min_leaf_width: f32,
+
+    /// Shortest a node laid out above or below another one can be made by dragging the separator between them,
+    /// when it's bigger than [`extra`](Self::extra). A leaf shorter than twice this height can't be split above or below by
+    /// dropping a tab onto it. By `Default` it's `0.0`.
+    pub min_leaf_height: f32,
+
     /// Idle color of the rectangle separator. By `Default` it's [`Color32::BLACK`].
     pub color_idle: Color32,
 
@@ -153,6 +262,22 @@ pub struct SeparatorStyle {
 
     /// Dragged color of the rectangle separator. By `Default` it's [`Color32::WHITE`].
     pub color_dragged: Color32,
+
+    /// Color of the rectangle separator while it's dragged and snapped to one of the
+    /// [`DockArea::split_snap`](crate::DockArea::split_snap) points. By `Default` it's [`Color32::LIGHT_BLUE`].
+    pub color_snapped: Color32,
+
+    /// Cursor shown while the separator between nodes laid out side by side is hovered or dragged.
+    /// By `Default` it's [`CursorIcon::ResizeHorizontal`].
+    pub cursor_horizontal: CursorIcon,
+
+    /// Cursor shown while the separator between nodes laid out one above the other is hovered or dragged.
+    /// By `Default` it's [`CursorIcon::ResizeVertical`].
+    pub cursor_vertical: CursorIcon,
+
+    /// Whether the separator is drawn. When `false`, it takes up [`width`](Self::width) and can be dragged as usual,
+    /// but nothing is drawn in any state. By `Default` it's `true`.
+    pub visible: bool,
 }
 
 /// Specifies the look and feel of tab bars.
@@ -171,6 +296,23 @@ pub struct TabBarStyle {
     /// Show a scroll bar when tab bar overflows. By `Default` it's `true`.
     pub show_scroll_bar_on_overflow: bool,
 
+    /// How tabs that don't fit in the tab bar are reached. By `Default` it's [`OverflowMode::Scroll`].
+    ///
+    /// Vertical tab bars are only scrolled with the mouse wheel, and ignore [`OverflowMode::Menu`].
+    pub overflow_mode: OverflowMode,
+
+    /// Which side of a leaf its tab bar is placed on,
+    /// unless overridden with [`Node::set_tab_bar_position`](crate::Node::set_tab_bar_position).
+    /// The tab bar of a collapsed leaf is always on top. By `Default` it's [`TabBarPosition::Top`].
+    ///
+    /// Vertical tab bars are as wide as [`height`](Self::height) and have no collapse, close all or drag buttons.
+    pub position: TabBarPosition,
+
+    /// Whether tabs in vertical tab bars only show their [`TabViewer::tab_icon`](crate::TabViewer::tab_icon),
+    /// instead of their rotated title. Tabs without an icon still show their title.
+    /// By `Default` it's `false`.
+    pub vertical_icons_only: bool,
+
     /// Tab corner_radius. By `Default` it's [`CornerRadius::default`].
     pub corner_radius: CornerRadius,
 
@@ -181,6 +323,53 @@ pub struct TabBarStyle {
     /// Whether tab titles expand to fill the width of their tab bars.
     /// By `Default` it's `false`.
     pub fill_tab_bar: bool,
+
+    /// Whether all tabs share the width of their tab bar equally, truncating titles that don't fit.
+    ///
+    /// This takes precedence over [`fill_tab_bar`](Self::fill_tab_bar),
+    /// [`TabStyle::minimum_width`] and [`TabStyle::maximum_width`],
+    /// so the tabs never overflow the tab bar. By `Default` it's `false`.
+    pub equal_tab_width: bool,
+
+    /// Paints the background of tab bars instead of [`bg_fill`](Self::bg_fill),
+    /// before any tabs or buttons are drawn. By `Default` it's `None`.
+    ///
+    /// This isn't (de)serialized.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub bg_painter: Option<BackgroundPainter>,
+}
+
+/// Paints a custom background, such as a gradient, into the given [`Rect`].
+///
+/// ```rust
+/// # use egui_dock::{BackgroundPainter, Style};
+/// # use egui::{Color32, CornerRadius};
+/// let mut style = Style::default();
+/// style.tab_bar.bg_painter = Some(BackgroundPainter::new(|painter, rect| {
+///     painter.rect_filled(rect, CornerRadius::ZERO, Color32::DARK_BLUE);
+/// }));
+/// ```
+#[derive(Clone)]
+pub struct BackgroundPainter(Arc<PaintFn>);
+
+type PaintFn = dyn Fn(&Painter, Rect);
+
+impl BackgroundPainter {
+    /// Creates a background painter from a function painting into the given [`Rect`].
+    pub fn new(paint: impl Fn(&Painter, Rect) + 'static) -> Self {
+        Self(Arc::new(paint))
+    }
+
+    /// Paints the background into `rect`.
+    pub fn paint(&self, painter: &Painter, rect: Rect) {
+        (self.0)(painter, rect)
+    }
+}
+
+impl std::fmt::Debug for BackgroundPainter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BackgroundPainter").finish_non_exhaustive()
+    }
 }
 
 /// Specifies the look and feel of an individual tab.
@@ -219,16 +408,37 @@ pub struct TabStyle {
     /// Spacing between tabs.
     pub spacing: f32,
 
+    /// How the active tab is marked. By `Default` it's [`ActiveTabIndicator::Fill`].
+    pub active_indicator: ActiveTabIndicator,
+
     /// The minimum width of the tab.
     ///
     /// The tab title or [`TabBarStyle::fill_tab_bar`] may make the tab
-    /// wider than this but never shorter.
+    /// wider than this but never shorter, unless [`TabBarStyle::equal_tab_width`] is set.
     pub minimum_width: Option<f32>,
     /// The maximum width of the tab.
     ///
-    /// [`TabBarStyle::fill_tab_bar`] may make the tab wider than this.
+    /// [`TabBarStyle::fill_tab_bar`] and [`TabBarStyle::equal_tab_width`] may make the tab wider than this.
     /// If the tab title is wider than this, it will be trimmed
     pub maximum_width: Option<f32>,
+
+    /// Horizontal alignment of the title within the tab,
+    /// which matters when the tab is wider than its title. By `Default` it's [`Align::Center`].
+    pub text_align: Align,
+
+    /// Which side of the tab its close button is on: [`Align::Min`] puts it before the title, as is customary on macOS,
+    /// and any other value after it. By `Default` it's [`Align::Max`].
+    pub close_button_side: Align,
+
+    /// Cursor shown while a draggable tab is hovered. By `Default` it's [`CursorIcon::Grab`].
+    pub cursor_hovered: CursorIcon,
+
+    /// Cursor shown while a tab is dragged. By `Default` it's [`CursorIcon::Grabbing`].
+    pub cursor_dragged: CursorIcon,
+
+    /// Whether pinned tabs only show their [`TabViewer::tab_icon`](crate::TabViewer::tab_icon), without a close
+    /// button. By `Default` it's `false`.
+    pub pinned_icon_only: bool,
 }
 
 /// Specifies the look and feel of individual tabs while they are being interacted with.
@@ -263,6 +473,11 @@ pub struct TabBodyStyle {
 
     /// Colour of the tab's background. By `Default` it's [`Color32::WHITE`].
     pub bg_fill: Color32,
+
+    /// Colour painted over the content of a tab while it's loading, see
+    /// [`TabViewer::tab_loading`](crate::TabViewer::tab_loading).
+    /// By `Default` it's [`Color32::WHITE`] with `0.6` opacity.
+    pub loading_scrim: Color32,
 }
 
 /// Specifies the look and feel of the tab drop overlay.
@@ -301,6 +516,36 @@ pub struct OverlayStyle {
 
     /// The feel of the overlay, timings, detection, etc.
     pub feel: OverlayFeel,
+
+    /// Whether a label describing what happens on drop is shown in the drop zone the dragged tab is over.
+    /// By `Default` it's `false`.
+    pub show_labels: bool,
+
+    /// The text of the labels shown when [`OverlayStyle::show_labels`] is `true`.
+    pub labels: OverlayLabels,
+}
+
+/// The labels shown in drop zones when [`OverlayStyle::show_labels`] is `true`.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct OverlayLabels {
+    /// Shown when the tab would be placed to the left of the leaf. By `Default` it's `"⬅ Split left"`.
+    pub split_left: String,
+
+    /// Shown when the tab would be placed to the right of the leaf. By `Default` it's `"➡ Split right"`.
+    pub split_right: String,
+
+    /// Shown when the tab would be placed above the leaf. By `Default` it's `"⬆ Split above"`.
+    pub split_above: String,
+
+    /// Shown when the tab would be placed below the leaf. By `Default` it's `"⬇ Split below"`.
+    pub split_below: String,
+
+    /// Shown when the tab would be added to the leaf. By `Default` it's `"Add as tab"`.
+    pub add_as_tab: String,
+
+    /// Shown when the tab would be moved to a new window. By `Default` it's `"New window"`.
+    pub new_window: String,
 }
 
 /// Specifies the feel of the tab drop overlay, i.e anything non visual about the overlay.
@@ -355,6 +600,92 @@ pub struct LeafHighlighting {
     pub expansion: f32,
 }
 
+/// How long the animations of the [`DockArea`](crate::DockArea) take, and how they ease in and out.
+///
+/// They only play while [`Style::animations_enabled`] is `true`. A duration of `0.0` turns a single one of them off.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct AnimationStyle {
+    /// Seconds the leaves take to move into their new places after the tree changed, e.g. when a leaf is split,
+    /// removed or collapsed. Moving a separator or resizing the dock area isn't animated.
+    /// By `Default` it's `0.2`.
+    pub split_duration: f32,
+
+    /// Seconds the preview of where a dragged tab would be dropped takes to fade in, and to move to another place.
+    /// By `Default` it's `0.1`.
+    pub drop_preview_duration: f32,
+
+    /// Seconds the tabs of a tab bar take to make room for a tab dragged along it. By `Default` it's `0.15`.
+    pub tab_reorder_duration: f32,
+
+    /// The curve all of the animations follow. By `Default` it's [`Easing::CubicOut`].
+    pub easing: Easing,
+}
+
+/// How an animation speeds up and slows down, see [`AnimationStyle::easing`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum Easing {
+    /// Moves at the same speed all the way.
+    Linear,
+
+    /// Starts fast and slows down towards the end.
+    #[default]
+    CubicOut,
+
+    /// Starts slowly, speeds up and slows down again towards the end.
+    CubicInOut,
+}
+
+impl Easing {
+    /// Returns how far along an animation is once `t` of its time passed, both in range `0.0..=1.0`.
+    pub fn apply(self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Easing::Linear => t,
+            Easing::CubicOut => egui::emath::easing::cubic_out(t),
+            Easing::CubicInOut => egui::emath::easing::cubic_in_out(t),
+        }
+    }
+}
+
+/// Keyboard shortcuts moving the focus around the [`DockArea`](crate::DockArea).
+///
+/// They only apply while a leaf is focused, and are consumed so they don't reach the content of the tabs.
+/// None of them are set by `Default`, so that they don't take keys from the application unless asked to,
+/// use [`Shortcuts::standard`] for the usual bindings.
+/// While [`DockArea::mru_switcher`](crate::DockArea::mru_switcher) is enabled, it takes `Ctrl+Tab` and
+/// `Ctrl+Shift+Tab` first.
+///
+/// ```rust
+/// # use egui::{Key, KeyboardShortcut, Modifiers};
+/// # use egui_dock::{Shortcuts, Style};
+/// let style = Style {
+///     shortcuts: Shortcuts {
+///         previous_leaf: Some(KeyboardShortcut::new(Modifiers::ALT, Key::ArrowLeft)),
+///         next_leaf: Some(KeyboardShortcut::new(Modifiers::ALT, Key::ArrowRight)),
+///         ..Shortcuts::standard()
+///     },
+///     ..Style::default()
+/// };
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct Shortcuts {
+    /// Activates the next tab of the focused leaf, wrapping around at the end.
+    pub next_tab: Option<KeyboardShortcut>,
+
+    /// Activates the previous tab of the focused leaf, wrapping around at the start.
+    pub previous_tab: Option<KeyboardShortcut>,
+
+    /// Focuses the next leaf, going through the main surface and then the windows.
+    /// The leaves of minimized and collapsed windows are skipped.
+    pub next_leaf: Option<KeyboardShortcut>,
+
+    /// Focuses the previous leaf.
+    pub previous_leaf: Option<KeyboardShortcut>,
+}
+
 impl Default for Style {
     fn default() -> Self {
         Self {
@@ -366,6 +697,9 @@ impl Default for Style {
             tab_bar: TabBarStyle::default(),
             tab: TabStyle::default(),
             overlay: OverlayStyle::default(),
+            shortcuts: Shortcuts::default(),
+            animation: AnimationStyle::default(),
+            animations_enabled: true,
         }
     }
 }
@@ -406,11 +740,19 @@ impl Default for SeparatorStyle {
     fn default() -> Self {
         Self {
             width: 1.0,
+            width_hovered: None,
+            width_dragged: None,
             extra_interact_width: 2.0,
             extra: 175.0,
+            min_leaf_width: 0.0,
+            min_leaf_height: 0.0,
             color_idle: Color32::BLACK,
             color_hovered: Color32::GRAY,
             color_dragged: Color32::WHITE,
+            color_snapped: Color32::LIGHT_BLUE,
+            cursor_horizontal: CursorIcon::ResizeHorizontal,
+            cursor_vertical: CursorIcon::ResizeVertical,
+            visible: true,
         }
     }
 }
@@ -422,9 +764,14 @@ impl Default for TabBarStyle {
             height: 24.0,
             inner_margin: Margin::ZERO,
             show_scroll_bar_on_overflow: true,
+            overflow_mode: OverflowMode::Scroll,
+            position: TabBarPosition::Top,
+            vertical_icons_only: false,
             corner_radius: CornerRadius::default(),
             hline_color: Color32::BLACK,
             fill_tab_bar: false,
+            equal_tab_width: false,
+            bg_painter: None,
         }
     }
 }
@@ -455,10 +802,16 @@ impl Default for TabStyle {
                 ..Default::default()
             },
             spacing: 0.0,
+            active_indicator: ActiveTabIndicator::default(),
             tab_body: TabBodyStyle::default(),
             hline_below_active_tab_name: false,
             minimum_width: None,
             maximum_width: None,
+            text_align: Align::Center,
+            close_button_side: Align::Max,
+            cursor_hovered: CursorIcon::Grab,
+            cursor_dragged: CursorIcon::Grabbing,
+            pinned_icon_only: false,
         }
     }
 }
@@ -481,6 +834,7 @@ impl Default for TabBodyStyle {
             stroke: Stroke::default(),
             corner_radius: CornerRadius::default(),
             bg_fill: Color32::WHITE,
+            loading_scrim: Color32::WHITE.gamma_multiply(0.6),
         }
     }
 }
@@ -500,6 +854,21 @@ impl Default for OverlayStyle {
             button_border_stroke: Stroke::new(1.0, Color32::from_gray(60)),
             overlay_type: OverlayType::Widgets,
             feel: Default::default(),
+            show_labels: false,
+            labels: Default::default(),
+        }
+    }
+}
+
+impl Default for OverlayLabels {
+    fn default() -> Self {
+        Self {
+            split_left: "⬅ Split left".to_owned(),
+            split_right: "➡ Split right".to_owned(),
+            split_above: "⬆ Split above".to_owned(),
+            split_below: "⬇ Split below".to_owned(),
+            add_as_tab: "Add as tab".to_owned(),
+            new_window: "New window".to_owned(),
         }
     }
 }
@@ -516,6 +885,17 @@ impl Default for OverlayFeel {
     }
 }
 
+impl Default for AnimationStyle {
+    fn default() -> Self {
+        Self {
+            split_duration: 0.2,
+            drop_preview_duration: 0.1,
+            tab_reorder_duration: 0.15,
+            easing: Easing::default(),
+        }
+    }
+}
+
 impl Default for LeafHighlighting {
     fn default() -> Self {
         Self {
@@ -538,6 +918,12 @@ impl Style {
     pub(crate) const TAB_COLLAPSE_ARROW_SIZE: f32 = 10.0;
     pub(crate) const TAB_EXPAND_BUTTON_SIZE: f32 = 24.0;
     pub(crate) const TAB_EXPAND_ARROW_SIZE: f32 = 10.0;
+    pub(crate) const TAB_DRAG_HANDLE_SIZE: f32 = 12.0;
+    pub(crate) const TAB_SCROLL_BUTTON_SIZE: f32 = 16.0;
+    pub(crate) const TAB_SCROLL_BAR_HEIGHT: f32 = 7.5;
+    pub(crate) const TAB_OVERFLOW_BUTTON_SIZE: f32 = 24.0;
+    pub(crate) const TAB_OVERFLOW_ARROW_SIZE: f32 = 8.0;
+    pub(crate) const TAB_INSERTION_CARET_WIDTH: f32 = 3.0;
 }
 
 impl Style {
@@ -545,6 +931,7 @@ impl Style {
     ///
     /// Fields overwritten by [`egui::Style`] are:
     /// - [`Style::main_surface_border_stroke`]
+    /// - [`Style::animations_enabled`], which is disabled if [`egui::Style::animation_time`] is zero
     ///
     /// See also: [`ButtonsStyle::from_egui`], [`SeparatorStyle::from_egui`], [`TabBarStyle::from_egui`],
     /// [`TabStyle::from_egui`]
@@ -557,9 +944,69 @@ impl Style {
             tab_bar: TabBarStyle::from_egui(style),
             tab: TabStyle::from_egui(style),
             overlay: OverlayStyle::from_egui(style),
+            animations_enabled: style.animation_time > 0.0,
             ..Self::default()
         }
     }
+
+    /// Returns a denser variant of this style, with a shorter tab bar and tighter margins, separators and overlay
+    /// buttons, similar to the compact layouts of code editors. Colors are kept as they are.
+    ///
+    /// The buttons of the tab bars keep their width, the close buttons of the tabs only shrink to fit the shorter
+    /// tab bar.
+    ///
+    /// See also [`Style::comfortable`], which sets the metrics back to those of [`Style::default`].
+    pub fn dense(mut self) -> Self {
+        fn halve(margin: Margin) -> Margin {
+            Margin {
+                left: margin.left / 2,
+                right: margin.right / 2,
+                top: margin.top / 2,
+                bottom: margin.bottom / 2,
+            }
+        }
+
+        self.dock_area_padding = self.dock_area_padding.map(halve);
+        self.tab_bar.height = (self.tab_bar.height * 0.75).round();
+        self.tab_bar.inner_margin = halve(self.tab_bar.inner_margin);
+        self.tab.tab_body.inner_margin = halve(self.tab.tab_body.inner_margin);
+        self.tab.minimum_width = self.tab.minimum_width.map(|width| width * 0.75);
+        self.separator.extra_interact_width *= 0.5;
+        self.overlay.button_spacing *= 0.75;
+        self.overlay.max_button_size *= 0.75;
+        self
+    }
+
+    /// Returns this style with the metrics of [`Style::default`] for the tab bar, margins, separators and overlay
+    /// buttons, undoing [`Style::dense`]. Colors are kept as they are.
+    ///
+    /// The metrics are those of [`Style::default`] even if this style was derived with [`Style::from_egui`], so the
+    /// margin of the tab bodies and the spacing of the overlay buttons no longer follow the [`egui::Style`].
+    pub fn comfortable(mut self) -> Self {
+        let default = Self::default();
+        self.dock_area_padding = default.dock_area_padding;
+        self.tab_bar.height = default.tab_bar.height;
+        self.tab_bar.inner_margin = default.tab_bar.inner_margin;
+        self.tab.tab_body.inner_margin = default.tab.tab_body.inner_margin;
+        self.tab.minimum_width = default.tab.minimum_width;
+        self.separator.extra_interact_width = default.separator.extra_interact_width;
+        self.overlay.button_spacing = default.overlay.button_spacing;
+        self.overlay.max_button_size = default.overlay.max_button_size;
+        self
+    }
+
+    /// Returns the height of the tab bars shown with this style, to align surrounding widgets with them.
+    ///
+    /// This includes the [`TabBarStyle::inner_margin`], within which the tab titles are centered vertically.
+    /// Tab bars hidden with [`DockArea::hide_tab_bar_if_single`](crate::DockArea::hide_tab_bar_if_single) take up no
+    /// height.
+    ///
+    /// While the tabs of a horizontal tab bar overflow it and [`TabBarStyle::show_scroll_bar_on_overflow`] is
+    /// enabled, that tab bar is taller by the height of the scroll bar shown below the tabs.
+    #[inline]
+    pub fn tab_bar_height(&self) -> f32 {
+        self.tab_bar.height
+    }
 }
 
 impl ButtonsStyle {
@@ -615,12 +1062,14 @@ impl SeparatorStyle {
     /// - [`SeparatorStyle::color_idle`]
     /// - [`SeparatorStyle::color_hovered`]
     /// - [`SeparatorStyle::color_dragged`]
+    /// - [`SeparatorStyle::color_snapped`]
     pub fn from_egui(style: &egui::Style) -> Self {
         Self {
             // Same as egui panel resize colors:
             color_idle: style.visuals.widgets.noninteractive.bg_stroke.color, // dim
             color_hovered: style.visuals.widgets.hovered.fg_stroke.color,     // bright
             color_dragged: style.visuals.widgets.active.fg_stroke.color,      // bright
+            color_snapped: style.visuals.selection.bg_fill,
             ..SeparatorStyle::default()
         }
     }
@@ -665,6 +1114,22 @@ impl TabStyle {
             ..Default::default()
         }
     }
+
+    /// Blends `color` into the background of the tab in every state, more so the more prominent the state is.
+    pub(crate) fn tint(&mut self, color: Color32) {
+        let tint = |style: &mut TabInteractionStyle, amount: f32| {
+            // Match the opacity of the background, so that faded tabs stay faded.
+            let color = color.linear_multiply(style.bg_fill.a() as f32 / 255.0);
+            style.bg_fill = style.bg_fill.lerp_to_gamma(color, amount);
+        };
+        tint(&mut self.inactive, 0.2);
+        tint(&mut self.inactive_with_kb_focus, 0.2);
+        tint(&mut self.hovered, 0.3);
+        tint(&mut self.active, 0.4);
+        tint(&mut self.active_with_kb_focus, 0.4);
+        tint(&mut self.focused, 0.5);
+        tint(&mut self.focused_with_kb_focus, 0.5);
+    }
 }
 
 impl TabInteractionStyle {
@@ -782,12 +1247,14 @@ impl TabBodyStyle {
     /// - [`TabBodyStyle::inner_margin`]
     /// - [`TabBodyStyle::stroke]
     /// - [`TabBodyStyle::bg_fill`]
+    /// - [`TabBodyStyle::loading_scrim`]
     pub fn from_egui(style: &egui::Style) -> Self {
         Self {
             inner_margin: style.spacing.window_margin,
             stroke: style.visuals.widgets.noninteractive.bg_stroke,
             corner_radius: style.visuals.widgets.active.corner_radius,
             bg_fill: style.visuals.window_fill(),
+            loading_scrim: style.visuals.window_fill().gamma_multiply(0.6),
         }
     }
 }
@@ -810,3 +1277,19 @@ impl OverlayStyle {
         }
     }
 }
+
+impl Shortcuts {
+    /// The usual bindings of tabbed applications: `Ctrl+Tab` and `Ctrl+Shift+Tab` cycle through the tabs,
+    /// `Ctrl+PageDown` and `Ctrl+PageUp` through the leaves.
+    pub fn standard() -> Self {
+        Self {
+            next_tab: Some(KeyboardShortcut::new(Modifiers::CTRL, Key::Tab)),
+            previous_tab: Some(KeyboardShortcut::new(
+                Modifiers::CTRL | Modifiers::SHIFT,
+                Key::Tab,
+            )),
+            next_leaf: Some(KeyboardShortcut::new(Modifiers::CTRL, Key::PageDown)),
+            previous_leaf: Some(KeyboardShortcut::new(Modifiers::CTRL, Key::PageUp)),
+        }
+    }
+}
@@ -0,0 +1,10 @@
+/// When pressing the primary mouse button on a tab makes it active.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum ActivationTrigger {
+    /// As soon as the button is pressed, before it's known whether the tab is clicked or dragged.
+    OnPress,
+
+    #[default]
+    /// Once the button is released, completing a click.
+    OnRelease,
+}
@@ -0,0 +1,124 @@
+use egui::{emath::lerp, Context, Id, Rect};
+
+use crate::Style;
+
+/// A value which can be animated by moving it towards another one.
+pub(super) trait Animated: Copy + PartialEq + Send + Sync + 'static {
+    /// Returns the value `t` of the way from `self` to `other`.
+    fn lerp(self, other: Self, t: f32) -> Self;
+}
+
+impl Animated for f32 {
+    fn lerp(self, other: Self, t: f32) -> Self {
+        lerp(self..=other, t)
+    }
+}
+
+impl Animated for Rect {
+    fn lerp(self, other: Self, t: f32) -> Self {
+        self.lerp_towards(&other, t)
+    }
+}
+
+/// A value on its way from one place to another, kept in the temporary data of `egui`.
+#[derive(Clone, Copy, Debug)]
+struct Animation<T> {
+    from: T,
+    to: T,
+    start: f64,
+
+    /// The last pass the animation was shown in, older ones are forgotten.
+    pass: u64,
+}
+
+impl<T: Animated> Animation<T> {
+    fn value(&self, time: f64, duration: f32, style: &Style) -> T {
+        let t = ((time - self.start) as f32 / duration).clamp(0.0, 1.0);
+        self.from.lerp(self.to, style.animation.easing.apply(t))
+    }
+}
+
+/// Returns the animation under `id` if it was shown in this pass or the last one.
+fn current<T: Animated>(ctx: &Context, id: Id) -> Option<Animation<T>> {
+    let pass = ctx.cumulative_pass_nr();
+    ctx.data(|data| data.get_temp::<Animation<T>>(id))
+        .filter(|animation| animation.pass + 1 >= pass)
+}
+
+/// Returns where the value animated under `id` is on its way to `target`, which it reaches after `duration` seconds.
+///
+/// A new animation starts `from` the given value, otherwise the current one carries on towards `target`, even if
+/// that moved. Without an animation, or while [`Style::animations_enabled`] is `false`, this is `target` itself.
+pub(super) fn animate<T: Animated>(
+    ctx: &Context,
+    id: Id,
+    from: Option<T>,
+    target: T,
+    duration: f32,
+    style: &Style,
+) -> T {
+    if !style.animations_enabled || duration <= 0.0 {
+        return target;
+    }
+    let (time, pass) = (ctx.input(|i| i.time), ctx.cumulative_pass_nr());
+    let animation = match (from, current::<T>(ctx, id)) {
+        (Some(from), _) => Animation {
+            from,
+            to: target,
+            start: time,
+            pass,
+        },
+        (None, Some(animation)) => Animation {
+            to: target,
+            pass,
+            ..animation
+        },
+        (None, None) => Animation {
+            from: target,
+            to: target,
+            start: time,
+            pass,
+        },
+    };
+    ctx.data_mut(|data| data.insert_temp(id, animation));
+
+    if animation.from != animation.to && time < animation.start + duration as f64 {
+        ctx.request_repaint();
+    }
+    animation.value(time, duration, style)
+}
+
+/// Like [`animate`], but starts over from wherever the value is whenever `target` changes,
+/// and comes from `start` if it wasn't shown in the last pass.
+pub(super) fn follow<T: Animated>(
+    ctx: &Context,
+    id: Id,
+    target: T,
+    start: T,
+    duration: f32,
+    style: &Style,
+) -> T {
+    let from = match current::<T>(ctx, id) {
+        Some(animation) if animation.to == target => None,
+        Some(animation) => {
+            let time = ctx.input(|i| i.time);
+            Some(animation.value(time, duration, style))
+        }
+        None => Some(start),
+    };
+    animate(ctx, id, from, target, duration, style)
+}
+
+/// Returns whether the value animated under `id` is still on its way.
+pub(super) fn is_animating<T: Animated>(
+    ctx: &Context,
+    id: Id,
+    duration: f32,
+    style: &Style,
+) -> bool {
+    style.animations_enabled
+        && current::<T>(ctx, id).is_some_and(|animation| {
+            animation.from != animation.to
+                && ctx.input(|i| i.time) < animation.start + duration as f64
+        })
+}
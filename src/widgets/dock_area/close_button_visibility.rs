@@ -0,0 +1,18 @@
+/// When the close button of a tab is shown.
+///
+/// Tabs which aren't [closeable](crate::TabViewer::is_closeable) never show a close button.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum CloseButtonVisibility {
+    #[default]
+    /// Always show the close button.
+    Always,
+
+    /// Only show the close button while the tab is hovered.
+    HoverTab,
+
+    /// Only show the close button on the active tab of each leaf.
+    ActiveTabOnly,
+
+    /// Never show the close button.
+    Never,
+}
@@ -0,0 +1,213 @@
+use egui::Id;
+
+use crate::{DockState, Node, NodeIndex, SurfaceIndex, TabIndex, TabViewer};
+
+/// A change of the layout of a [`DockState`], see [`DockArea::on_events`](crate::DockArea::on_events).
+///
+/// Events are found by comparing the layout at the end of a frame with the one at the end of the last frame,
+/// telling tabs apart by [`TabViewer::id`]. This way, changes made by dragging, clicking and through the
+/// [`DockState`] API in between frames are all reported alike. The indices before a change are those of the
+/// last frame, and the indices after it those of this frame.
+#[derive(Clone, Debug, PartialEq)]
+pub enum DockEvent {
+    /// A tab was added.
+    TabAdded {
+        /// Where the tab is now.
+        to: (SurfaceIndex, NodeIndex, TabIndex),
+    },
+
+    /// A tab moved to another leaf, or to another place among the tabs of its leaf.
+    ///
+    /// Changes of the tree shifting the indices of its nodes, such as removing a leaf, move the tabs of those nodes.
+    TabMoved {
+        /// Where the tab was in the last frame.
+        from: (SurfaceIndex, NodeIndex, TabIndex),
+
+        /// Where the tab is now.
+        to: (SurfaceIndex, NodeIndex, TabIndex),
+    },
+
+    /// A tab was closed, or otherwise removed from the [`DockState`].
+    TabClosed {
+        /// Where the tab was in the last frame.
+        from: (SurfaceIndex, NodeIndex, TabIndex),
+    },
+
+    /// A node which wasn't split before was split in two.
+    NodeSplit {
+        /// The surface of the node.
+        surface: SurfaceIndex,
+
+        /// The node which is now split.
+        node: NodeIndex,
+    },
+
+    /// A separator was moved.
+    NodeResized {
+        /// The surface of the node.
+        surface: SurfaceIndex,
+
+        /// The split node whose separator moved.
+        node: NodeIndex,
+
+        /// The fraction of the space of the node now taken by its first child.
+        fraction: f32,
+    },
+
+    /// A surface was added, e.g. a new window.
+    SurfaceCreated(SurfaceIndex),
+
+    /// A surface was removed.
+    SurfaceDestroyed(SurfaceIndex),
+
+    /// Another leaf became focused, or none is anymore.
+    FocusChanged {
+        /// The leaf focused in the last frame.
+        from: Option<(SurfaceIndex, NodeIndex)>,
+
+        /// The leaf focused now.
+        to: Option<(SurfaceIndex, NodeIndex)>,
+    },
+}
+
+/// The parts of the layout of a [`DockState`] which [`DockEvent`]s report changes of.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(super) struct LayoutSnapshot {
+    tabs: Vec<(Id, (SurfaceIndex, NodeIndex, TabIndex))>,
+    splits: Vec<((SurfaceIndex, NodeIndex), f32)>,
+    surfaces: Vec<SurfaceIndex>,
+    focused: Option<(SurfaceIndex, NodeIndex)>,
+}
+
+impl LayoutSnapshot {
+    pub(super) fn new<Tab>(
+        dock_state: &mut DockState<Tab>,
+        tab_viewer: &mut impl TabViewer<Tab = Tab>,
+    ) -> Self {
+        let surfaces = dock_state.valid_surface_indices().to_vec();
+        let mut tabs = Vec::new();
+        let mut splits = Vec::new();
+        for &surface in &surfaces {
+            for (node, content) in dock_state[surface].iter_mut().enumerate() {
+                let node = NodeIndex(node);
+                match content {
+                    Node::Leaf(leaf) => {
+                        for (tab, content) in leaf.tabs.iter_mut().enumerate() {
+                            tabs.push((tab_viewer.id(content), (surface, node, TabIndex(tab))));
+                        }
+                    }
+                    Node::Vertical(split) | Node::Horizontal(split) => {
+                        splits.push(((surface, node), split.fraction));
+                    }
+                    Node::Empty => (),
+                }
+            }
+        }
+        Self {
+            tabs,
+            splits,
+            surfaces,
+            focused: dock_state.focused_leaf(),
+        }
+    }
+
+    /// Returns whether the same tabs are at the same places in both layouts, on the same surfaces and splits,
+    /// regardless of where the separators are.
+    pub(super) fn same_structure(&self, other: &Self) -> bool {
+        self.tabs == other.tabs
+            && self.surfaces == other.surfaces
+            && self
+                .splits
+                .iter()
+                .map(|(node, _)| node)
+                .eq(other.splits.iter().map(|(node, _)| node))
+    }
+
+    /// Returns the events leading from the `previous` layout to this one.
+    pub(super) fn events_since(&self, previous: &Self) -> Vec<DockEvent> {
+        let mut events = Vec::new();
+
+        events.extend(
+            previous
+                .surfaces
+                .iter()
+                .filter(|surface| !self.surfaces.contains(surface))
+                .map(|&surface| DockEvent::SurfaceDestroyed(surface)),
+        );
+        events.extend(
+            self.surfaces
+                .iter()
+                .filter(|surface| !previous.surfaces.contains(surface))
+                .map(|&surface| DockEvent::SurfaceCreated(surface)),
+        );
+
+        for &(node, fraction) in &self.splits {
+            match previous.splits.iter().find(|(other, _)| *other == node) {
+                Some(&(_, previous_fraction)) if previous_fraction != fraction => {
+                    events.push(DockEvent::NodeResized {
+                        surface: node.0,
+                        node: node.1,
+                        fraction,
+                    });
+                }
+                Some(_) => (),
+                None => events.push(DockEvent::NodeSplit {
+                    surface: node.0,
+                    node: node.1,
+                }),
+            }
+        }
+
+        // Tabs with the same id are paired up in the order they appear in.
+        let mut remaining: Vec<_> = previous.tabs.iter().map(Some).collect();
+        let mut paired = Vec::new();
+        for (id, to) in &self.tabs {
+            let from = remaining
+                .iter_mut()
+                .find(|tab| tab.is_some_and(|(other, _)| other == id))
+                .and_then(Option::take);
+            match from {
+                Some(&(_, from)) => paired.push((from, *to)),
+                None => events.push(DockEvent::TabAdded { to: *to }),
+            }
+        }
+        events.extend(
+            remaining
+                .into_iter()
+                .flatten()
+                .map(|&(_, from)| DockEvent::TabClosed { from }),
+        );
+
+        // Tabs staying in their leaf only move if their place among the other tabs staying there changes,
+        // not when tabs before them come or go.
+        let leaf = |(surface, node, _): (SurfaceIndex, NodeIndex, TabIndex)| (surface, node);
+        let staying: Vec<_> = paired
+            .iter()
+            .filter(|(from, to)| leaf(*from) == leaf(*to))
+            .collect();
+        for &(from, to) in &paired {
+            let moved = leaf(from) != leaf(to) || {
+                let before = staying
+                    .iter()
+                    .filter(|(other, _)| leaf(*other) == leaf(from) && other.2 < from.2)
+                    .count();
+                let after = staying
+                    .iter()
+                    .filter(|(_, other)| leaf(*other) == leaf(to) && other.2 < to.2)
+                    .count();
+                before != after
+            };
+            if moved {
+                events.push(DockEvent::TabMoved { from, to });
+            }
+        }
+
+        if self.focused != previous.focused {
+            events.push(DockEvent::FocusChanged {
+                from: previous.focused,
+                to: self.focused,
+            });
+        }
+        events
+    }
+}
@@ -1,11 +1,14 @@
 use std::ops::BitOrAssign;
 
+use super::{animation, drag_context::SharedDrag};
 use crate::{
-    AllowedSplits, NodeIndex, Split, Style, SurfaceIndex, TabDestination, TabIndex, TabInsert,
+    AllowedSplits, DockState, NodeIndex, OverlayType, Split, Style, SurfaceIndex, TabDestination,
+    TabIndex, TabInsert,
 };
 use egui::{
     emath::{inverse_lerp, GuiRounding},
-    vec2, Context, Id, LayerId, NumExt, Order, Painter, Pos2, Rect, Stroke, StrokeKind, Ui, Vec2,
+    vec2, Align2, Context, Id, LayerId, NumExt, Order, Painter, Pos2, Rect, Stroke, StrokeKind,
+    TextStyle, Ui, Vec2,
 };
 
 #[derive(Debug, Clone)]
@@ -18,6 +21,9 @@ pub(super) struct HoverData {
 
     /// If a tab title or the tab head is hovered, this is the rect of it.
     pub tab: Option<Rect>,
+
+    /// If the tab bar is hovered, this is the rect of the gap between tabs the dragged tab is inserted into.
+    pub caret: Option<Rect>,
 }
 
 /// Specifies the location of a tab on the tree, used when moving tabs.
@@ -25,6 +31,10 @@ pub(super) struct HoverData {
 pub(super) struct DragData {
     pub src: TreeComponent,
     pub rect: Rect,
+
+    /// Set when the tab is dragged in another [`DockArea`](crate::DockArea) sharing a
+    /// [`DockDragContext`](crate::DockDragContext), `src` is then where it is in that area.
+    pub foreign: Option<SharedDrag>,
 }
 
 #[derive(Debug, Clone)]
@@ -66,6 +76,10 @@ impl TreeComponent {
     pub(super) fn is_surface(&self) -> bool {
         matches!(self, TreeComponent::Surface(_))
     }
+
+    pub(super) fn is_node(&self) -> bool {
+        matches!(self, TreeComponent::Node(..))
+    }
 }
 
 fn make_overlay_painter(ui: &Ui) -> Painter {
@@ -74,42 +88,55 @@ fn make_overlay_painter(ui: &Ui) -> Painter {
     ui.ctx().layer_painter(layer_id)
 }
 
-fn draw_highlight_rect(rect: Rect, ui: &Ui, style: &Style) {
+/// Returns where the preview drawn under `id` is shown this frame and how opaque it is,
+/// as it slides over to `rect` after it moved and fades in after it wasn't shown.
+fn animate_preview(id: Id, rect: Rect, ui: &Ui, style: &Style) -> (Rect, f32) {
+    let duration = style.animation.drop_preview_duration;
+    let rect = animation::follow(ui.ctx(), id, rect, rect, duration, style);
+    let opacity = animation::follow(ui.ctx(), id.with("opacity"), 1.0, 0.0, duration, style);
+    (rect, opacity)
+}
+
+pub(super) fn draw_highlight_rect(rect: Rect, ui: &Ui, style: &Style) {
+    let highlight = &style.overlay.hovered_leaf_highlight;
+    let (rect, opacity) = animate_preview(Id::new("hovered_leaf_highlight"), rect, ui, style);
+    let mut stroke = highlight.stroke;
+    stroke.color = stroke.color.gamma_multiply(opacity);
     let painter = make_overlay_painter(ui);
     painter.rect(
-        rect.expand(style.overlay.hovered_leaf_highlight.expansion),
-        style.overlay.hovered_leaf_highlight.corner_radius,
-        style.overlay.hovered_leaf_highlight.color,
-        style.overlay.hovered_leaf_highlight.stroke,
+        rect.expand(highlight.expansion),
+        highlight.corner_radius,
+        highlight.color.gamma_multiply(opacity),
+        stroke,
         StrokeKind::Inside,
     );
 }
 
 // Draws one of the Tab drop destination icons inside `rect`, which one you get is specified by `is_top_bottom`.
+// Buttons which aren't `allowed` are greyed out and never highlighted.
 fn button_ui(
     rect: Rect,
     ui: &Ui,
     lock: &mut bool,
     mouse_pos: Pos2,
     style: &Style,
-    split: Option<Split>,
+    split: Option<(Split, f32)>,
+    allowed: bool,
 ) -> bool {
     let visuals = &style.overlay;
-    let button_stroke = Stroke::new(1.0, visuals.button_color);
+    let opacity = if allowed { 1.0 } else { 0.3 };
+    let button_color = visuals.button_color.gamma_multiply(opacity);
+    let button_stroke = Stroke::new(1.0, button_color);
+    let mut border_stroke = visuals.button_border_stroke;
+    border_stroke.color = border_stroke.color.gamma_multiply(opacity);
     let painter = make_overlay_painter(ui);
-    painter.rect_stroke(rect, 0.0, visuals.button_border_stroke, StrokeKind::Inside);
+    painter.rect_stroke(rect, 0.0, border_stroke, StrokeKind::Inside);
     let rect = rect.shrink(rect.width() * 0.1);
     painter.rect_stroke(rect, 0.0, button_stroke, StrokeKind::Inside);
     let rim = { Rect::from_two_pos(rect.min, rect.lerp_inside(vec2(1.0, 0.1))) };
-    painter.rect(
-        rim,
-        0.0,
-        visuals.button_color,
-        Stroke::NONE,
-        StrokeKind::Inside,
-    );
+    painter.rect(rim, 0.0, button_color, Stroke::NONE, StrokeKind::Inside);
 
-    if let Some(split) = split {
+    if let Some((split, _)) = split {
         for line in DASHED_LINE_ALPHAS.chunks(2) {
             let start = rect.lerp_inside(lerp_vec(split, line[0]));
             let end = rect.lerp_inside(lerp_vec(split, line[1]));
@@ -119,21 +146,21 @@ fn button_ui(
     let is_mouse_over = rect
         .expand(style.overlay.feel.interact_expansion)
         .contains(mouse_pos);
-    if is_mouse_over && !*lock {
-        let vertical_alphas = vec2(1.0, 0.5);
-        let horizontal_alphas = vec2(0.5, 1.0);
+    if is_mouse_over && !*lock && allowed {
         let rect = match split {
-            Some(Split::Above) => Rect::from_min_size(rect.min, rect.size() * vertical_alphas),
-            Some(Split::Left) => Rect::from_min_size(rect.min, rect.size() * horizontal_alphas),
-            Some(Split::Below) => {
-                let min = rect.lerp_inside(lerp_vec(Split::Below, 0.0));
-                Rect::from_min_size(min, rect.size() * vertical_alphas)
+            Some((Split::Above, fraction)) => {
+                Rect::from_min_size(rect.min, rect.size() * vec2(1.0, fraction))
+            }
+            Some((Split::Left, fraction)) => {
+                Rect::from_min_size(rect.min, rect.size() * vec2(fraction, 1.0))
+            }
+            Some((Split::Below, fraction)) => {
+                Rect::from_min_max(rect.lerp_inside(vec2(0.0, 1.0 - fraction)), rect.max)
             }
-            Some(Split::Right) => {
-                let min = rect.lerp_inside(lerp_vec(Split::Right, 0.0));
-                Rect::from_min_size(min, rect.size() * horizontal_alphas)
+            Some((Split::Right, fraction)) => {
+                Rect::from_min_max(rect.lerp_inside(vec2(1.0 - fraction, 0.0)), rect.max)
             }
-            _ => rect,
+            None => rect,
         };
         painter.rect_filled(rect, 0.0, style.overlay.selection_color);
     }
@@ -172,69 +199,71 @@ impl DragDropState {
         self.hover.tab.is_some()
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub(super) fn resolve_icon_based(
         &mut self,
         ui: &Ui,
         style: &Style,
         allowed_splits: AllowedSplits,
         windows_allowed: bool,
+        torn_off: bool,
         window_bounds: Rect,
+        split_fraction: impl Fn(Split) -> f32,
+        allowed_drop: impl Fn(Option<Split>) -> bool,
     ) -> Option<TabDestination> {
         assert!(!self.is_on_title_bar());
 
         draw_highlight_rect(self.hover.rect, ui, style);
         let mut hovering_buttons = false;
         let total_button_spacing = style.overlay.button_spacing * 2.0;
-        let (rect, pointer) = (self.hover.rect, self.pointer);
-        let rect = rect.shrink(style.overlay.button_spacing);
-        let shortest_side = ((rect.width() - total_button_spacing) / 3.0)
-            .min((rect.height() - total_button_spacing) / 3.0)
-            .min(style.overlay.max_button_size);
-
-        let mut destination: Option<TabDestination> = windows_allowed
-            .then(|| TabDestination::Window(Rect::from_min_size(pointer, self.drag.rect.size())));
-
-        let center = rect.center();
-        let rect = Rect::from_center_size(center, Vec2::splat(shortest_side));
-
-        if button_ui(rect, ui, &mut hovering_buttons, pointer, style, None) {
+        let pointer = self.pointer;
+
+        // Until the tabs are torn off, they're docked into the hovered leaf instead of a new window.
+        let mut destination: Option<TabDestination> = if windows_allowed && torn_off {
+            Some(TabDestination::Window(Rect::from_min_size(
+                pointer,
+                self.drag.rect.size(),
+            )))
+        } else if windows_allowed && allowed_drop(None) {
             match self.hover.dst {
                 TreeComponent::Node(surface, node) => {
-                    destination = Some(TabDestination::Node(surface, node, TabInsert::Append))
+                    Some(TabDestination::Node(surface, node, TabInsert::Append))
                 }
-                TreeComponent::Surface(surface) => {
-                    destination = Some(TabDestination::EmptySurface(surface))
-                }
-                _ => (),
+                TreeComponent::Surface(surface) => Some(TabDestination::EmptySurface(surface)),
+                TreeComponent::Tab(..) => None,
             }
-        }
+        } else {
+            None
+        };
 
-        for split in [Split::Below, Split::Right, Split::Above, Split::Left] {
-            match allowed_splits {
-                AllowedSplits::TopBottomOnly if !split.is_top_bottom() => continue,
-                AllowedSplits::LeftRightOnly if !split.is_left_right() => continue,
-                AllowedSplits::None => continue,
-                _ => {
-                    let offset_value = shortest_side + style.overlay.button_spacing;
-                    let offset_vector = match split {
-                        Split::Above => vec2(0.0, -offset_value),
-                        Split::Below => vec2(0.0, offset_value),
-                        Split::Left => vec2(-offset_value, 0.0),
-                        Split::Right => vec2(offset_value, 0.0),
-                    };
-                    if button_ui(
-                        Rect::from_center_size(center + offset_vector, Vec2::splat(shortest_side)),
-                        ui,
-                        &mut hovering_buttons,
-                        pointer,
-                        style,
-                        Some(split),
-                    ) {
-                        if let TreeComponent::Node(surface, node) = self.hover.dst {
-                            destination =
-                                Some(TabDestination::Node(surface, node, TabInsert::Split(split)))
-                        }
+        let buttons = overlay_buttons(self.hover.rect, style, allowed_splits);
+        let (center, shortest_side) = (buttons[0].1.center(), buttons[0].1.width());
+        for (split, rect) in buttons {
+            let allowed = allowed_drop(split);
+            let split = split.map(|split| (split, split_fraction(split)));
+            if button_ui(
+                rect,
+                ui,
+                &mut hovering_buttons,
+                pointer,
+                style,
+                split,
+                allowed,
+            ) {
+                match (split, &self.hover.dst) {
+                    // Releasing the tab over a disallowed drop doesn't move it.
+                    _ if !allowed => destination = None,
+                    (None, &TreeComponent::Node(surface, node)) => {
+                        destination = Some(TabDestination::Node(surface, node, TabInsert::Append))
+                    }
+                    (None, &TreeComponent::Surface(surface)) => {
+                        destination = Some(TabDestination::EmptySurface(surface))
+                    }
+                    (Some((split, _)), &TreeComponent::Node(surface, node)) => {
+                        destination =
+                            Some(TabDestination::Node(surface, node, TabInsert::Split(split)))
                     }
+                    _ => (),
                 }
             }
         }
@@ -245,21 +274,38 @@ impl DragDropState {
             (true, _) => LockState::SoftLock,
         };
         self.update_lock(target_lock_state, style, ui.ctx());
-        if let Some(TabDestination::Window(rect)) = destination {
-            let rect = self.window_preview_rect(rect);
-            let rect_bounded = constrain_rect_to_area(ui, rect, window_bounds);
-            draw_window_rect(rect_bounded, ui, style);
+        match destination {
+            Some(TabDestination::Window(rect)) => {
+                let rect = self.window_preview_rect(rect);
+                let rect_bounded = constrain_rect_to_area(ui, rect, window_bounds);
+                draw_window_rect(rect_bounded, ui, style);
+                draw_label(
+                    rect_bounded.center(),
+                    &TabDestination::Window(rect),
+                    ui,
+                    style,
+                );
+            }
+            Some(ref destination) => {
+                let below_buttons = center + vec2(0.0, 1.5 * shortest_side + total_button_spacing);
+                draw_label(below_buttons, destination, ui, style);
+            }
+            None => (),
         }
         destination
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub(super) fn resolve_traditional(
         &mut self,
         ui: &Ui,
         style: &Style,
         allowed_splits: AllowedSplits,
         windows_allowed: bool,
+        torn_off: bool,
         window_bounds: Rect,
+        split_fraction: impl Fn(Split) -> f32,
+        allowed_drop: impl Fn(Option<Split>) -> bool,
     ) -> Option<TabDestination> {
         // If windows are not allowed, any hover over a window is immediately disallowed.
         if !windows_allowed && self.hover.dst.surface_address() != SurfaceIndex::main() {
@@ -269,7 +315,10 @@ impl DragDropState {
 
         // Deals with hovers over tab bar and tab titles.
         if let Some(rect) = self.hover.tab {
-            draw_drop_rect(rect, ui, style);
+            if !allowed_drop(None) {
+                return None;
+            }
+            draw_drop_rect(self.hover.caret.unwrap_or(rect), ui, style);
             let target_lock_state = if rect.contains(self.pointer) {
                 LockState::SoftLock
             } else {
@@ -281,7 +330,8 @@ impl DragDropState {
 
         // Main cases, splits, window creations, etc.
         let (hover_rect, pointer) = (self.hover.rect, self.pointer);
-        let center = hover_rect.center();
+        // Until the tabs are torn off, they're docked into the hovered leaf instead of a new window.
+        let new_window = windows_allowed && torn_off;
 
         let (tab_insertion, overlay_rect) = {
             // A reverse lerp of the pointers position relative to the hovered leaf rect.
@@ -305,7 +355,7 @@ impl DragDropState {
             if center_drop_rect.contains(a_pos) {
                 (Some(TabInsert::Append), Rect::EVERYTHING)
             } else if window_drop_rect.contains(a_pos) {
-                match windows_allowed {
+                match new_window {
                     true => (None, Rect::NOTHING),
                     false => (Some(TabInsert::Append), Rect::EVERYTHING),
                 }
@@ -319,40 +369,40 @@ impl DragDropState {
                     AllowedSplits::None => Pos2::ZERO,
                 };
                 if a_pos == Pos2::ZERO {
-                    match windows_allowed {
+                    match new_window {
                         true => (None, Rect::NOTHING),
                         false => (Some(TabInsert::Append), Rect::EVERYTHING),
                     }
                 } else {
-                    match (a_pos.x - a_pos.y > 0., -a_pos.x - a_pos.y > 0.) {
-                        (true, true) => (
-                            Some(TabInsert::Split(Split::Above)),
-                            Rect::everything_above(center.y),
-                        ),
-                        (false, true) => (
-                            Some(TabInsert::Split(Split::Left)),
-                            Rect::everything_left_of(center.x),
-                        ),
-                        (true, false) => (
-                            Some(TabInsert::Split(Split::Right)),
-                            Rect::everything_right_of(center.x),
-                        ),
-                        (false, false) => (
-                            Some(TabInsert::Split(Split::Below)),
-                            Rect::everything_below(center.y),
-                        ),
-                    }
+                    let split = match (a_pos.x - a_pos.y > 0., -a_pos.x - a_pos.y > 0.) {
+                        (true, true) => Split::Above,
+                        (false, true) => Split::Left,
+                        (true, false) => Split::Right,
+                        (false, false) => Split::Below,
+                    };
+                    let overlay_rect = split_overlay_rect(hover_rect, split, split_fraction(split));
+                    (Some(TabInsert::Split(split)), overlay_rect)
                 }
             }
         };
 
-        let default_value = windows_allowed
-            .then(|| TabDestination::Window(Rect::from_min_size(pointer, self.drag.rect.size())));
-        let final_result = tab_insertion.map_or(default_value, |tab| match self.hover.dst {
-            TreeComponent::Surface(surface) => Some(TabDestination::EmptySurface(surface)),
-            TreeComponent::Node(surface, node) => Some(TabDestination::Node(surface, node, tab)),
-            _ => None,
+        let disallowed = tab_insertion.is_some_and(|insert| match insert {
+            TabInsert::Split(split) => !allowed_drop(Some(split)),
+            _ => !allowed_drop(None),
         });
+        let default_value = new_window
+            .then(|| TabDestination::Window(Rect::from_min_size(pointer, self.drag.rect.size())));
+        let final_result = match tab_insertion {
+            _ if disallowed => None,
+            None => default_value,
+            Some(tab) => match self.hover.dst {
+                TreeComponent::Surface(surface) => Some(TabDestination::EmptySurface(surface)),
+                TreeComponent::Node(surface, node) => {
+                    Some(TabDestination::Node(surface, node, tab))
+                }
+                _ => None,
+            },
+        };
 
         self.update_lock(LockState::SoftLock, style, ui.ctx());
 
@@ -362,9 +412,17 @@ impl DragDropState {
                 let rect = self.window_preview_rect(rect);
                 let rect_bounded = constrain_rect_to_area(ui, rect, window_bounds);
                 draw_window_rect(rect_bounded, ui, style);
+                draw_label(
+                    rect_bounded.center(),
+                    &TabDestination::Window(rect),
+                    ui,
+                    style,
+                );
             }
-            Some(_) => {
-                draw_drop_rect(hover_rect.intersect(overlay_rect), ui, style);
+            Some(ref destination) => {
+                let rect = hover_rect.intersect(overlay_rect);
+                draw_drop_rect(rect, ui, style);
+                draw_label(rect.center(), destination, ui, style);
             }
             None => (),
         }
@@ -379,7 +437,6 @@ impl DragDropState {
                     *lock_time = ctx.input(|i| i.time);
                 }
                 let window_hold = if !self.hover.dst.surface_address().is_main() {
-                    ctx.request_repaint();
                     self.is_locked(style, ctx)
                 } else {
                     false
@@ -396,12 +453,16 @@ impl DragDropState {
         }
     }
 
+    /// Whether the lock is still held, in which case a repaint is requested for when it runs out.
     pub(super) fn is_locked(&self, style: &Style, ctx: &Context) -> bool {
         match self.locked.as_ref() {
             Some(lock_time) => {
                 let elapsed = ctx.input(|i| (i.time - lock_time) as f32);
-                ctx.request_repaint();
-                elapsed < style.overlay.feel.max_preference_time
+                let remaining = style.overlay.feel.max_preference_time - elapsed;
+                if remaining > 0.0 {
+                    ctx.request_repaint_after_secs(remaining);
+                }
+                remaining > 0.0
             }
             None => false,
         }
@@ -416,6 +477,128 @@ impl DragDropState {
     }
 }
 
+/// The splits leaving both halves of a leaf occupying `rect` at least as big as
+/// [`SeparatorStyle::min_leaf_width`](crate::SeparatorStyle::min_leaf_width) and
+/// [`SeparatorStyle::min_leaf_height`](crate::SeparatorStyle::min_leaf_height).
+pub(super) fn splits_fitting(rect: Rect, style: &Style) -> AllowedSplits {
+    match (
+        rect.width() >= style.separator.min_leaf_width * 2.0,
+        rect.height() >= style.separator.min_leaf_height * 2.0,
+    ) {
+        (true, true) => AllowedSplits::All,
+        (true, false) => AllowedSplits::LeftRightOnly,
+        (false, true) => AllowedSplits::TopBottomOnly,
+        (false, false) => AllowedSplits::None,
+    }
+}
+
+/// The buttons of the [`OverlayType::Widgets`] overlay over a leaf occupying `rect`, the one adding the tab
+/// to the leaf first and then those splitting it.
+fn overlay_buttons(
+    rect: Rect,
+    style: &Style,
+    allowed_splits: AllowedSplits,
+) -> Vec<(Option<Split>, Rect)> {
+    let total_button_spacing = style.overlay.button_spacing * 2.0;
+    let rect = rect.shrink(style.overlay.button_spacing);
+    let shortest_side = ((rect.width() - total_button_spacing) / 3.0)
+        .min((rect.height() - total_button_spacing) / 3.0)
+        .min(style.overlay.max_button_size);
+    let center = rect.center();
+
+    let mut buttons = vec![(
+        None,
+        Rect::from_center_size(center, Vec2::splat(shortest_side)),
+    )];
+    for split in [Split::Below, Split::Right, Split::Above, Split::Left] {
+        match allowed_splits {
+            AllowedSplits::TopBottomOnly if !split.is_top_bottom() => continue,
+            AllowedSplits::LeftRightOnly if !split.is_left_right() => continue,
+            AllowedSplits::None => continue,
+            _ => {
+                let offset_value = shortest_side + style.overlay.button_spacing;
+                let offset_vector = match split {
+                    Split::Above => vec2(0.0, -offset_value),
+                    Split::Below => vec2(0.0, offset_value),
+                    Split::Left => vec2(-offset_value, 0.0),
+                    Split::Right => vec2(offset_value, 0.0),
+                };
+                buttons.push((
+                    Some(split),
+                    Rect::from_center_size(center + offset_vector, Vec2::splat(shortest_side)),
+                ));
+            }
+        }
+    }
+    buttons
+}
+
+/// The part of the plane a tab dropped into a new split of the leaf at `hover_rect` takes up.
+fn split_overlay_rect(hover_rect: Rect, split: Split, fraction: f32) -> Rect {
+    match split {
+        Split::Above => Rect::everything_above(hover_rect.min.y + hover_rect.height() * fraction),
+        Split::Left => Rect::everything_left_of(hover_rect.min.x + hover_rect.width() * fraction),
+        Split::Right => Rect::everything_right_of(hover_rect.max.x - hover_rect.width() * fraction),
+        Split::Below => Rect::everything_below(hover_rect.max.y - hover_rect.height() * fraction),
+    }
+}
+
+impl<Tab> DockState<Tab> {
+    /// Returns the zones of the leaf at `surface_index` and `node_index` which a dragged tab can be dropped onto,
+    /// as laid out when the leaf was last shown, along with where a tab dropped there would go.
+    ///
+    /// The zones are the same the [`DockArea`](crate::DockArea) shows with the given `style` and `allowed_splits`.
+    /// With [`OverlayType::Widgets`], they are the buttons of the overlay. With [`OverlayType::HighlightedAreas`],
+    /// they are the areas highlighted while a zone is hovered, which is the space the dropped tab would take up given
+    /// the `split_fraction` (see [`DockArea::drop_split_fraction`](crate::DockArea::drop_split_fraction)).
+    ///
+    /// Returns an empty `Vec` if there's no such leaf, or if it wasn't shown yet.
+    pub fn drop_zones(
+        &self,
+        (surface_index, node_index): (SurfaceIndex, NodeIndex),
+        style: &Style,
+        allowed_splits: AllowedSplits,
+        split_fraction: f32,
+    ) -> Vec<(TabDestination, Rect)> {
+        let Some(rect) = self
+            .leaf(surface_index, node_index)
+            .map(|leaf| leaf.rect)
+            .filter(|rect| rect.is_positive())
+        else {
+            return Vec::new();
+        };
+        let allowed_splits = allowed_splits & splits_fitting(rect, style);
+        let destination = |split: Option<Split>| {
+            let insert = split.map_or(TabInsert::Append, TabInsert::Split);
+            TabDestination::Node(surface_index, node_index, insert)
+        };
+
+        match style.overlay.overlay_type {
+            OverlayType::Widgets => overlay_buttons(rect, style, allowed_splits)
+                .into_iter()
+                .map(|(split, button)| (destination(split), button))
+                .collect(),
+            OverlayType::HighlightedAreas => {
+                let splits = [Split::Above, Split::Left, Split::Right, Split::Below]
+                    .into_iter()
+                    .filter(|split| match allowed_splits {
+                        AllowedSplits::All => true,
+                        AllowedSplits::LeftRightOnly => split.is_left_right(),
+                        AllowedSplits::TopBottomOnly => split.is_top_bottom(),
+                        AllowedSplits::None => false,
+                    })
+                    .map(|split| {
+                        let area = rect.intersect(split_overlay_rect(rect, split, split_fraction));
+                        (destination(Some(split)), area)
+                    });
+                std::iter::once((destination(None), rect))
+                    .chain(splits)
+                    .collect()
+            }
+        }
+    }
+}
+
 #[inline(always)]
 const fn lerp_vec(split: Split, alpha: f32) -> Vec2 {
     if split.is_top_bottom() {
@@ -426,10 +609,39 @@ const fn lerp_vec(split: Split, alpha: f32) -> Vec2 {
 }
 
 // Draws a filled rect describing where a tab will be dropped.
-#[inline(always)]
 fn draw_drop_rect(rect: Rect, ui: &Ui, style: &Style) {
+    let (rect, opacity) = animate_preview(Id::new("drop_preview"), rect, ui, style);
     let painter = make_overlay_painter(ui);
-    painter.rect_filled(rect, 0.0, style.overlay.selection_color);
+    painter.rect_filled(
+        rect,
+        0.0,
+        style.overlay.selection_color.gamma_multiply(opacity),
+    );
+}
+
+// Draws the label describing what happens when a tab is dropped at `destination`, centered at `pos`.
+fn draw_label(pos: Pos2, destination: &TabDestination, ui: &Ui, style: &Style) {
+    if !style.overlay.show_labels {
+        return;
+    }
+    let labels = &style.overlay.labels;
+    let text = match destination {
+        TabDestination::Window(_) => &labels.new_window,
+        TabDestination::Node(_, _, TabInsert::Split(split)) => match split {
+            Split::Left => &labels.split_left,
+            Split::Right => &labels.split_right,
+            Split::Above => &labels.split_above,
+            Split::Below => &labels.split_below,
+        },
+        TabDestination::Node(..) | TabDestination::EmptySurface(_) => &labels.add_as_tab,
+    };
+    make_overlay_painter(ui).text(
+        pos,
+        Align2::CENTER_CENTER,
+        text,
+        TextStyle::Button.resolve(ui.style()),
+        ui.visuals().strong_text_color(),
+    );
 }
 
 // Draws a stroked rect describing where a tab will be dropped.
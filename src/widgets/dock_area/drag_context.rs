@@ -0,0 +1,123 @@
+use std::any::Any;
+
+use egui::{Id, Rect};
+
+use crate::{NodeIndex, SurfaceIndex, TabDestination, TabIndex};
+
+/// Lets tabs be dragged from one [`DockArea`](crate::DockArea) into another.
+///
+/// Keep one around for as long as the areas, and pass it to each of them with
+/// [`DockArea::drag_context`](crate::DockArea::drag_context) every frame. A tab dropped into another area is taken out
+/// of its [`DockState`](crate::DockState), and added to the other one the next time that area is shown.
+/// If the areas show different types of tabs, the receiving one converts it with
+/// [`TabViewer::accept_foreign_tab`](crate::TabViewer::accept_foreign_tab).
+///
+/// Only single tabs can be dragged between areas, not leaves or groups of selected tabs.
+///
+/// # Examples
+///
+/// ```rust
+/// # use egui_dock::{DockArea, DockDragContext, DockState, TabViewer};
+/// # use egui::{Id, SidePanel, Ui, WidgetText};
+/// # struct MyTabViewer;
+/// # impl TabViewer for MyTabViewer {
+/// #     type Tab = String;
+/// #     fn title(&mut self, tab: &mut String) -> WidgetText { tab.as_str().into() }
+/// #     fn ui(&mut self, ui: &mut Ui, tab: &mut String) {}
+/// # }
+/// struct MyApp {
+///     left: DockState<String>,
+///     right: DockState<String>,
+///     drag_context: DockDragContext,
+/// }
+///
+/// impl MyApp {
+///     fn ui(&mut self, ctx: &egui::Context) {
+///         SidePanel::left("left").show(ctx, |ui| {
+///             DockArea::new(&mut self.left)
+///                 .id(Id::new("left"))
+///                 .drag_context(&mut self.drag_context)
+///                 .show_inside(ui, &mut MyTabViewer);
+///         });
+///         DockArea::new(&mut self.right)
+///             .id(Id::new("right"))
+///             .drag_context(&mut self.drag_context)
+///             .show(ctx, &mut MyTabViewer);
+///     }
+/// }
+/// # let mut app = MyApp {
+/// #     left: DockState::new(vec!["a".to_owned()]),
+/// #     right: DockState::new(vec!["b".to_owned()]),
+/// #     drag_context: DockDragContext::new(),
+/// # };
+/// # egui::__run_test_ctx(|ctx| app.ui(ctx));
+/// ```
+#[derive(Debug, Default)]
+pub struct DockDragContext {
+    /// The tab being dragged in one of the areas.
+    pub(super) drag: Option<SharedDrag>,
+
+    /// Where the dragged tab lands if it's dropped now, when that's in another area than the one it's dragged in.
+    pub(super) drop: Option<(Id, TabDestination)>,
+
+    /// A dropped tab which wasn't added to the area it's dropped into yet.
+    pub(super) transfer: Option<Transfer>,
+}
+
+/// A tab being dragged in a [`DockArea`](crate::DockArea) sharing a [`DockDragContext`].
+#[derive(Clone, Debug)]
+pub(super) struct SharedDrag {
+    /// The area the tab is dragged in.
+    pub area: Id,
+    pub tab: (SurfaceIndex, NodeIndex, TabIndex),
+    pub rect: Rect,
+    pub allowed_in_windows: bool,
+}
+
+/// A tab on its way from one [`DockArea`](crate::DockArea) to another.
+pub(super) struct Transfer {
+    /// The area to add the tab to.
+    pub area: Id,
+    pub destination: TabDestination,
+
+    /// The area the tab comes from, and where it was there, to give it back to if it's refused.
+    pub origin: (Id, TabDestination),
+    pub tab: Box<dyn Any>,
+}
+
+impl std::fmt::Debug for Transfer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Transfer")
+            .field("area", &self.area)
+            .field("destination", &self.destination)
+            .field("origin", &self.origin)
+            .finish_non_exhaustive()
+    }
+}
+
+impl DockDragContext {
+    /// Creates a context with no tab being dragged.
+    pub const fn new() -> Self {
+        Self {
+            drag: None,
+            drop: None,
+            transfer: None,
+        }
+    }
+
+    /// Returns whether a tab is being dragged in one of the areas sharing this context,
+    /// or was dropped into an area which didn't take it in yet.
+    pub fn is_active(&self) -> bool {
+        self.drag.is_some() || self.transfer.is_some()
+    }
+}
+
+/// Takes a tab of some type back out of a type-erased box, or gives the box back if it holds another type.
+type FromAny<Tab> = fn(Box<dyn Any>) -> Result<Tab, Box<dyn Any>>;
+
+/// A [`DockDragContext`] given to a [`DockArea`](crate::DockArea), along with how to type-erase its tabs.
+pub(super) struct DragContextRef<'a, Tab> {
+    pub context: &'a mut DockDragContext,
+    pub into_any: fn(Tab) -> Box<dyn Any>,
+    pub from_any: FromAny<Tab>,
+}
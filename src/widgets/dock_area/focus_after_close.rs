@@ -0,0 +1,20 @@
+/// Which tab of a leaf becomes active when its active tab is closed, see [`DockArea::focus_after_close`](crate::DockArea::focus_after_close).
+///
+/// Closing a tab which isn't active never changes which tab is active.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum FocusAfterClose {
+    #[default]
+    /// The tab before the closed one becomes active, or the first tab if the closed one was first,
+    /// without moving the focus to the leaf.
+    None,
+
+    /// Focus the leaf, activating the tab before the closed one, or the first tab if the closed one was first.
+    Left,
+
+    /// Focus the leaf, activating the tab after the closed one, or the last tab if the closed one was last.
+    Right,
+
+    /// Focus the leaf, activating the tab of the leaf which was most recently active in a focused leaf.
+    /// Falls back to [`FocusAfterClose::Left`] if none of the tabs were.
+    MostRecentlyUsed,
+}
@@ -0,0 +1,57 @@
+use egui::Rect;
+
+use crate::{NodeIndex, SurfaceIndex, TabIndex};
+
+/// Where the leaves and windows of a [`DockArea`](crate::DockArea) were laid out in a frame,
+/// see [`DockArea::on_layout`](crate::DockArea::on_layout).
+///
+/// The indices refer to the leaves as they were shown,
+/// before the tabs closed or detached in that frame were removed.
+/// The leaves of windows shown in their own viewport are in the coordinates of that viewport.
+#[derive(Clone, Debug, Default)]
+pub struct LayoutInfo {
+    /// The leaves which were shown, in the order they were shown in.
+    pub leaves: Vec<LeafLayout>,
+
+    /// The windows which were shown inside the viewport of the [`DockArea`](crate::DockArea),
+    /// along with the [`Rect`] each of them took up.
+    pub windows: Vec<(SurfaceIndex, Rect)>,
+}
+
+/// Where a leaf was laid out, see [`LayoutInfo`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LeafLayout {
+    /// The surface the leaf is on.
+    pub surface: SurfaceIndex,
+
+    /// The index of the leaf within its surface.
+    pub node: NodeIndex,
+
+    /// The tab which was shown in the leaf.
+    pub active: TabIndex,
+
+    /// The tab bar of the leaf, which has no height when it's hidden.
+    pub tab_bar: Rect,
+
+    /// The content of the active tab, or [`None`] if the leaf is collapsed.
+    pub content: Option<Rect>,
+}
+
+impl LayoutInfo {
+    /// Returns the layout of the leaf at `surface` and `node`, if it was shown.
+    pub fn leaf(&self, surface: SurfaceIndex, node: NodeIndex) -> Option<&LeafLayout> {
+        self.leaves
+            .iter()
+            .find(|leaf| leaf.surface == surface && leaf.node == node)
+    }
+
+    /// Returns the content [`Rect`] of every tab which was shown.
+    pub fn content_rects(
+        &self,
+    ) -> impl Iterator<Item = ((SurfaceIndex, NodeIndex, TabIndex), Rect)> + '_ {
+        self.leaves.iter().filter_map(|leaf| {
+            leaf.content
+                .map(|content| ((leaf.surface, leaf.node, leaf.active), content))
+        })
+    }
+}
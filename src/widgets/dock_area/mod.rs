@@ -4,43 +4,115 @@
 mod show;
 
 // Various components of the `DockArea` which is used when rendering
+mod activation_trigger;
 mod allowed_splits;
+mod animation;
+mod close_button_visibility;
+mod dock_event;
 mod drag_and_drop;
+mod drag_context;
+mod focus_after_close;
+mod layout_info;
+mod reclick_action;
+mod standard_actions;
 mod state;
 mod tab_removal;
 
-use crate::{dock_state::DockState, NodeIndex, Style, SurfaceIndex, TabIndex};
+use crate::{dock_state::DockState, NodeIndex, Split, Style, SurfaceIndex, TabIndex};
+pub use activation_trigger::ActivationTrigger;
 pub use allowed_splits::AllowedSplits;
+pub use close_button_visibility::CloseButtonVisibility;
+pub use dock_event::DockEvent;
+pub use drag_context::DockDragContext;
+use drag_context::DragContextRef;
+pub use focus_after_close::FocusAfterClose;
+pub use layout_info::{LayoutInfo, LeafLayout};
+pub use reclick_action::ReclickAction;
+pub use standard_actions::StandardActions;
 use tab_removal::TabRemoval;
 
-use egui::{emath::*, Id, Modifiers};
+use egui::{emath::*, Id, KeyboardShortcut, Modifiers, PointerButton, PopupCloseBehavior};
+
+/// Adjusts a [`Style`] derived from the `egui` style, see [`DockArea::style_from_egui_with`].
+type StyleModifier<'a> = Box<dyn Fn(&mut Style) + 'a>;
+
+/// Selects the tabs to force close, see [`DockArea::force_close_if`].
+type ForceCloseFilter<'a, Tab> = Box<dyn Fn(&Tab) -> bool + 'a>;
+
+/// Receives the layout of a frame, see [`DockArea::on_layout`].
+type LayoutCallback<'a> = Box<dyn FnOnce(&LayoutInfo) + 'a>;
+
+/// Receives the changes of the layout since the last frame, see [`DockArea::on_events`].
+type EventsCallback<'a> = Box<dyn FnOnce(Vec<DockEvent>) + 'a>;
 
 /// Displays a [`DockState`] in `egui`.
 pub struct DockArea<'tree, Tab> {
     id: Id,
     dock_state: &'tree mut DockState<Tab>,
     style: Option<Style>,
+    style_modifier: Option<StyleModifier<'tree>>,
     show_add_popup: bool,
+    add_popup_width: Option<f32>,
+    add_popup_close_behavior: PopupCloseBehavior,
     show_add_buttons: bool,
     show_close_buttons: bool,
+    close_button_visibility: CloseButtonVisibility,
+    reserve_close_button_space: bool,
     tab_context_menus: bool,
+    standard_context_menu: StandardActions,
     draggable_tabs: bool,
+    interactive: bool,
+    enabled: bool,
+    external_drag_active: bool,
     show_tab_name_on_hover: bool,
+    rename_tabs_on_double_click: bool,
+    maximize_on_double_click: bool,
+    multi_tab_selection: bool,
+    mru_switcher: bool,
+    close_tab_shortcut: Option<KeyboardShortcut>,
+    focus_after_close: FocusAfterClose,
+    touch_gestures: bool,
+    double_tap_max_delay: f64,
+    double_tap_max_distance: f32,
+    drag_window_on_empty_tab_bar: bool,
+    block_loading_tabs: bool,
+    hide_tab_bar_if_single: bool,
+    drop_split_fraction: f32,
+    proportional_resize: bool,
+    split_snap: Vec<f32>,
+    split_snap_tolerance: f32,
+    split_snap_bypass_modifiers: Modifiers,
+    allow_os_window_detach: bool,
+    native_windows: bool,
+    allow_window_merging: bool,
+    tearoff_distance: f32,
+    active_tab_reclick: ReclickAction,
+    tab_activation: ActivationTrigger,
+    context_menu_button: PointerButton,
     show_window_close_buttons: bool,
     show_window_collapse_buttons: bool,
     show_leaf_close_all_buttons: bool,
     show_leaf_collapse_buttons: bool,
+    show_leaf_drag_handles: bool,
     show_secondary_button_hint: bool,
     secondary_button_modifiers: Modifiers,
     secondary_button_on_modifier: bool,
     secondary_button_context_menu: bool,
     allowed_splits: AllowedSplits,
     window_bounds: Option<Rect>,
+    force_close_if: Option<ForceCloseFilter<'tree, Tab>>,
+    on_layout: Option<LayoutCallback<'tree>>,
+    on_events: Option<EventsCallback<'tree>>,
+    drag_context: Option<DragContextRef<'tree, Tab>>,
 
     to_remove: Vec<TabRemoval>,
     to_detach: Vec<(SurfaceIndex, NodeIndex, TabIndex)>,
+    to_pin: Vec<(SurfaceIndex, NodeIndex, TabIndex)>,
+    to_split: Vec<((SurfaceIndex, NodeIndex, TabIndex), Split)>,
     new_focused: Option<(SurfaceIndex, NodeIndex)>,
+    focused_content: Option<Id>,
     tab_hover_rect: Option<(Rect, TabIndex)>,
+    layout_info: LayoutInfo,
 }
 
 // Builder
@@ -52,22 +124,64 @@ impl<'tree, Tab> DockArea<'tree, Tab> {
             id: Id::new("egui_dock::DockArea"),
             dock_state: tree,
             style: None,
+            style_modifier: None,
             show_add_popup: false,
+            add_popup_width: None,
+            add_popup_close_behavior: PopupCloseBehavior::CloseOnClickOutside,
             show_add_buttons: false,
             show_close_buttons: true,
+            close_button_visibility: CloseButtonVisibility::Always,
+            reserve_close_button_space: true,
             tab_context_menus: true,
+            standard_context_menu: StandardActions::NONE,
             draggable_tabs: true,
+            interactive: true,
+            enabled: true,
+            external_drag_active: false,
             show_tab_name_on_hover: false,
+            rename_tabs_on_double_click: false,
+            maximize_on_double_click: false,
+            multi_tab_selection: false,
+            mru_switcher: false,
+            close_tab_shortcut: None,
+            focus_after_close: FocusAfterClose::None,
+            touch_gestures: false,
+            double_tap_max_delay: 0.3,
+            double_tap_max_distance: 8.0,
+            drag_window_on_empty_tab_bar: false,
+            block_loading_tabs: true,
+            hide_tab_bar_if_single: false,
+            drop_split_fraction: 0.5,
+            proportional_resize: true,
+            split_snap: Vec::new(),
+            split_snap_tolerance: 6.0,
+            split_snap_bypass_modifiers: Modifiers::ALT,
+            allow_os_window_detach: false,
+            native_windows: false,
+            allow_window_merging: false,
+            tearoff_distance: 0.0,
+            active_tab_reclick: ReclickAction::None,
+            tab_activation: ActivationTrigger::OnRelease,
+            context_menu_button: PointerButton::Secondary,
             allowed_splits: AllowedSplits::default(),
             to_remove: Vec::new(),
             to_detach: Vec::new(),
+            to_pin: Vec::new(),
+            to_split: Vec::new(),
             new_focused: None,
+            focused_content: None,
             tab_hover_rect: None,
+            layout_info: LayoutInfo::default(),
             window_bounds: None,
+            force_close_if: None,
+            on_layout: None,
+            on_events: None,
+            drag_context: None,
             show_window_close_buttons: true,
             show_window_collapse_buttons: true,
             show_leaf_close_all_buttons: true,
             show_leaf_collapse_buttons: true,
+            show_leaf_drag_handles: false,
             show_secondary_button_hint: true,
             secondary_button_modifiers: Modifiers::SHIFT,
             secondary_button_on_modifier: true,
@@ -86,6 +200,18 @@ impl<'tree, Tab> DockArea<'tree, Tab> {
     #[inline(always)]
     pub fn style(mut self, style: Style) -> Self {
         self.style = Some(style);
+        self.style_modifier = None;
+        self
+    }
+
+    /// Derives the look and feel of the [`DockArea`] from the [`egui::Style`] of the [`Ui`](egui::Ui) it's shown in
+    /// with [`Style::from_egui`], then lets `modify` adjust it.
+    ///
+    /// This is done every frame, so the style stays in sync with changes to the `egui` theme.
+    /// Replaces any style set with [`style`](Self::style).
+    pub fn style_from_egui_with(mut self, modify: impl Fn(&mut Style) + 'tree) -> Self {
+        self.style = None;
+        self.style_modifier = Some(Box::new(modify));
         self
     }
 
@@ -96,6 +222,29 @@ impl<'tree, Tab> DockArea<'tree, Tab> {
         self
     }
 
+    /// The width of the add button popup.
+    ///
+    /// If set, the content of the popup is also put inside a vertical [`ScrollArea`](egui::ScrollArea),
+    /// which is useful when there are a lot of entries to choose from.
+    ///
+    /// By default it's `None`, which means the popup is sized to fit its content.
+    pub fn add_popup_width(mut self, add_popup_width: Option<f32>) -> Self {
+        self.add_popup_width = add_popup_width;
+        self
+    }
+
+    /// When the add button popup closes.
+    ///
+    /// With [`PopupCloseBehavior::IgnoreClicks`] the popup stays open until a selection is made, which
+    /// [`TabViewer::add_popup`](crate::TabViewer::add_popup) signals by calling [`Ui::close`](egui::Ui::close),
+    /// or until escape is pressed.
+    ///
+    /// By default it's [`PopupCloseBehavior::CloseOnClickOutside`].
+    pub fn add_popup_close_behavior(mut self, close_behavior: PopupCloseBehavior) -> Self {
+        self.add_popup_close_behavior = close_behavior;
+        self
+    }
+
     /// Shows or hides the tab add buttons.
     /// By default it's `false`.
     pub fn show_add_buttons(mut self, show_add_buttons: bool) -> Self {
@@ -110,13 +259,49 @@ impl<'tree, Tab> DockArea<'tree, Tab> {
         self
     }
 
-    /// Whether tabs show a context menu when right-clicked.
+    /// When the close buttons of tabs are shown, if [`show_close_buttons`](Self::show_close_buttons) is enabled.
+    ///
+    /// This can be overridden for specific tabs with
+    /// [`TabViewer::close_button_visibility`](crate::TabViewer::close_button_visibility).
+    ///
+    /// By default it's [`CloseButtonVisibility::Always`].
+    pub fn close_button_visibility(
+        mut self,
+        close_button_visibility: CloseButtonVisibility,
+    ) -> Self {
+        self.close_button_visibility = close_button_visibility;
+        self
+    }
+
+    /// Whether tabs keep the space of their close button while it's hidden because of
+    /// [`close_button_visibility`](Self::close_button_visibility), so they don't change size when it appears.
+    ///
+    /// Tabs never reserve space for [`CloseButtonVisibility::Never`].
+    ///
+    /// By default it's `true`.
+    pub fn reserve_close_button_space(mut self, reserve_close_button_space: bool) -> Self {
+        self.reserve_close_button_space = reserve_close_button_space;
+        self
+    }
+
+    /// Whether tabs show a context menu when right-clicked, or clicked with the
+    /// [`context_menu_button`](Self::context_menu_button).
     /// By default it's `true`.
     pub fn tab_context_menus(mut self, tab_context_menus: bool) -> Self {
         self.tab_context_menus = tab_context_menus;
         self
     }
 
+    /// Which of the [`StandardActions`] are added to the context menu of tabs,
+    /// see [`DockArea::tab_context_menus`]. Their labels come from
+    /// [`TabContextMenuTranslations`](crate::TabContextMenuTranslations).
+    ///
+    /// By default it's [`StandardActions::NONE`].
+    pub fn standard_context_menu(mut self, standard_context_menu: StandardActions) -> Self {
+        self.standard_context_menu = standard_context_menu;
+        self
+    }
+
     /// Whether tabs can be dragged between nodes and reordered on the tab bar.
     /// By default it's `true`.
     pub fn draggable_tabs(mut self, draggable_tabs: bool) -> Self {
@@ -124,6 +309,61 @@ impl<'tree, Tab> DockArea<'tree, Tab> {
         self
     }
 
+    /// Whether the user can change the layout.
+    ///
+    /// When `false`, tabs and leaves can't be dragged, separators and windows can't be moved or resized,
+    /// renaming is disabled, and the add, close, collapse and leaf drag buttons are hidden, overriding the options
+    /// which would enable any of these. Tabs can still be activated, and their content stays interactive.
+    ///
+    /// By default it's `true`.
+    pub fn interactive(mut self, interactive: bool) -> Self {
+        self.interactive = interactive;
+        self
+    }
+
+    /// Whether the dock area is enabled.
+    ///
+    /// When `false`, everything in the dock area is greyed out the way `egui` shows disabled widgets,
+    /// and ignores all input, including the content of the tabs. Unlike with [`DockArea::interactive`],
+    /// tabs can't be activated either.
+    ///
+    /// By default it's `true`.
+    pub fn enabled(mut self, enabled: bool) -> Self {
+        self.enabled = enabled;
+        self
+    }
+
+    /// Whether something is being dragged from outside the [`DockArea`], e.g. an item of a file tree.
+    ///
+    /// While `true`, the leaf under the pointer is highlighted with
+    /// [`OverlayStyle::hovered_leaf_highlight`](crate::OverlayStyle::hovered_leaf_highlight).
+    /// Use [`DockState::hit_test`] to know which leaf that is, and [`DockState::accept_external_drop`]
+    /// to add a tab there once the item is dropped.
+    ///
+    /// By default it's `false`.
+    pub fn external_drag_active(mut self, external_drag_active: bool) -> Self {
+        self.external_drag_active = external_drag_active;
+        self
+    }
+
+    /// Shares a [`DockDragContext`] with other [`DockArea`]s, so that tabs can be dragged between them.
+    ///
+    /// Each area needs its own [`id`](Self::id). Tabs dropped from an area showing another type of tabs
+    /// are converted with [`TabViewer::accept_foreign_tab`](crate::TabViewer::accept_foreign_tab).
+    ///
+    /// By default it's [`None`].
+    pub fn drag_context(mut self, drag_context: &'tree mut DockDragContext) -> Self
+    where
+        Tab: 'static,
+    {
+        self.drag_context = Some(DragContextRef {
+            context: drag_context,
+            into_any: |tab| Box::new(tab),
+            from_any: |tab| tab.downcast().map(|tab| *tab),
+        });
+        self
+    }
+
     /// Whether tabs show their name when hovered over them.
     /// By default it's `false`.
     pub fn show_tab_name_on_hover(mut self, show_tab_name_on_hover: bool) -> Self {
@@ -131,6 +371,262 @@ impl<'tree, Tab> DockArea<'tree, Tab> {
         self
     }
 
+    /// Whether double-clicking a tab turns its title into a text field,
+    /// calling [`TabViewer::on_rename`](crate::TabViewer::on_rename) when editing is finished.
+    /// By default it's `false`.
+    pub fn rename_tabs_on_double_click(mut self, rename_tabs_on_double_click: bool) -> Self {
+        self.rename_tabs_on_double_click = rename_tabs_on_double_click;
+        self
+    }
+
+    /// Whether double-clicking a tab [maximizes](DockState::maximize) its leaf, or restores the layout if it's
+    /// already maximized. Renaming takes precedence if [`rename_tabs_on_double_click`](Self::rename_tabs_on_double_click)
+    /// is also set. By default it's `false`.
+    pub fn maximize_on_double_click(mut self, maximize_on_double_click: bool) -> Self {
+        self.maximize_on_double_click = maximize_on_double_click;
+        self
+    }
+
+    /// Whether several tabs of a leaf can be selected and dragged together, keeping their relative order.
+    ///
+    /// Clicking a tab while holding the command key (ctrl on most platforms) adds it to or removes it from the
+    /// selection, holding shift selects all tabs between the active and the clicked one,
+    /// and a plain click clears the selection.
+    ///
+    /// By default it's `false`.
+    pub fn multi_tab_selection(mut self, multi_tab_selection: bool) -> Self {
+        self.multi_tab_selection = multi_tab_selection;
+        self
+    }
+
+    /// Whether holding ctrl and pressing tab shows a list of all tabs, the most recently used ones first.
+    ///
+    /// Each press of tab highlights the next tab in the list (shift+tab the previous one),
+    /// and releasing ctrl activates the highlighted tab. Escape closes the list without switching.
+    ///
+    /// By default it's `false`.
+    pub fn mru_switcher(mut self, mru_switcher: bool) -> Self {
+        self.mru_switcher = mru_switcher;
+        self
+    }
+
+    /// A keyboard shortcut which closes the active tab of the focused leaf, e.g. `Ctrl+W`.
+    ///
+    /// The tab is closed the same way its close button would, see [`DockState::close_tab`].
+    /// The shortcut is only consumed while a leaf is focused and no widget outside of the [`DockArea`] has
+    /// keyboard focus, so it doesn't reach the content of the tabs but still reaches e.g. a side panel.
+    ///
+    /// By default it's `None`.
+    pub fn close_tab_shortcut(mut self, close_tab_shortcut: Option<KeyboardShortcut>) -> Self {
+        self.close_tab_shortcut = close_tab_shortcut;
+        self
+    }
+
+    /// Which tab becomes active when the active tab of a leaf is closed, and whether the leaf gets focused.
+    ///
+    /// This applies to tabs closed through the dock area, with their close button, middle click, context menu
+    /// or [`DockArea::close_tab_shortcut`].
+    ///
+    /// By default it's [`FocusAfterClose::None`].
+    pub fn focus_after_close(mut self, focus_after_close: FocusAfterClose) -> Self {
+        self.focus_after_close = focus_after_close;
+        self
+    }
+
+    /// Whether double-tapping a tab or the empty part of a tab bar maximizes its leaf, or restores it if it's
+    /// already maximized, for touch screens where double-clicking isn't natural.
+    ///
+    /// Two taps count as a double-tap when they are no further apart than [`DockArea::double_tap_max_delay`]
+    /// and [`DockArea::double_tap_max_distance`].
+    ///
+    /// By default it's `false`.
+    pub fn touch_gestures(mut self, touch_gestures: bool) -> Self {
+        self.touch_gestures = touch_gestures;
+        self
+    }
+
+    /// The longest time in seconds between two taps recognized as a double-tap, see [`DockArea::touch_gestures`].
+    ///
+    /// By default it's `0.3`.
+    pub fn double_tap_max_delay(mut self, double_tap_max_delay: f64) -> Self {
+        self.double_tap_max_delay = double_tap_max_delay;
+        self
+    }
+
+    /// The furthest distance in points between two taps recognized as a double-tap, see [`DockArea::touch_gestures`].
+    ///
+    /// By default it's `8.0`.
+    pub fn double_tap_max_distance(mut self, double_tap_max_distance: f32) -> Self {
+        self.double_tap_max_distance = double_tap_max_distance;
+        self
+    }
+
+    /// Whether dragging the empty part of a tab bar of the main surface moves the OS window of the app,
+    /// with [`egui::ViewportCommand::StartDrag`]. This is meant for apps without window decorations.
+    ///
+    /// Dragging tabs and buttons, or the tab bars of windows, works as usual.
+    ///
+    /// By default it's `false`.
+    pub fn drag_window_on_empty_tab_bar(mut self, drag_window_on_empty_tab_bar: bool) -> Self {
+        self.drag_window_on_empty_tab_bar = drag_window_on_empty_tab_bar;
+        self
+    }
+
+    /// Whether the content of tabs which are loading, as reported by
+    /// [`TabViewer::tab_loading`](crate::TabViewer::tab_loading), ignores the pointer and keyboard
+    /// while the loading spinner is shown over it.
+    ///
+    /// By default it's `true`.
+    pub fn block_loading_tabs(mut self, block_loading_tabs: bool) -> Self {
+        self.block_loading_tabs = block_loading_tabs;
+        self
+    }
+
+    /// Whether the tab bar is hidden for leaves containing exactly one tab, giving that tab the whole leaf.
+    ///
+    /// Without a tab bar, the tab can't be dragged, and its context menu and close button aren't available.
+    /// It can still be closed using [`TabViewer::force_close`](crate::TabViewer::force_close) or the
+    /// [`DockState`] API, and moved by dragging another tab in or out of its leaf, which brings the tab bar back.
+    /// A collapsed leaf keeps its tab bar.
+    ///
+    /// By default it's `false`.
+    pub fn hide_tab_bar_if_single(mut self, hide_tab_bar_if_single: bool) -> Self {
+        self.hide_tab_bar_if_single = hide_tab_bar_if_single;
+        self
+    }
+
+    /// How much of a node's area (in range 0..=1) a tab occupies when it's dropped next to the node,
+    /// creating a new split.
+    ///
+    /// This can be overridden for specific tabs with
+    /// [`TabViewer::drop_split_fraction`](crate::TabViewer::drop_split_fraction).
+    ///
+    /// By default it's `0.5`.
+    pub fn default_drop_split_fraction(mut self, fraction: f32) -> Self {
+        self.drop_split_fraction = fraction.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Whether dragging a separator resizes every node on both of its sides proportionally.
+    ///
+    /// Nested splits in the same direction keep their fractions when `true`, so the space gained or lost
+    /// by each side of the separator is spread over all of its nodes. When `false`, their fractions are adjusted
+    /// instead, so that only the nodes touching the separator change size, and the others keep theirs.
+    ///
+    /// See also [`TabViewer::fixed_size_leaf`](crate::TabViewer::fixed_size_leaf).
+    ///
+    /// By default it's `true`.
+    pub fn proportional_resize(mut self, proportional_resize: bool) -> Self {
+        self.proportional_resize = proportional_resize;
+        self
+    }
+
+    /// Fractions which splits snap to while their separator is dragged, e.g. `&[0.25, 1.0 / 3.0, 0.5, 2.0 / 3.0, 0.75]`.
+    ///
+    /// A split snaps when the pointer is within [`DockArea::split_snap_tolerance`] of a snap point,
+    /// and its separator is then drawn with [`SeparatorStyle::color_snapped`](crate::SeparatorStyle::color_snapped).
+    /// Snapping is disabled with an empty slice.
+    ///
+    /// By default it's empty.
+    pub fn split_snap(mut self, split_snap: &[f32]) -> Self {
+        self.split_snap = split_snap.to_vec();
+        self
+    }
+
+    /// How close in points the pointer has to be to a snap point for a split to snap to it, see [`DockArea::split_snap`].
+    ///
+    /// By default it's `6.0`.
+    pub fn split_snap_tolerance(mut self, split_snap_tolerance: f32) -> Self {
+        self.split_snap_tolerance = split_snap_tolerance;
+        self
+    }
+
+    /// The modifiers which, while held, temporarily turn off snapping for finer control, see [`DockArea::split_snap`].
+    ///
+    /// By default it's [`Modifiers::ALT`].
+    pub fn split_snap_bypass_modifiers(mut self, split_snap_bypass_modifiers: Modifiers) -> Self {
+        self.split_snap_bypass_modifiers = split_snap_bypass_modifiers;
+        self
+    }
+
+    /// Whether dragging a window onto the title bar of another one merges it into that one when it's released,
+    /// see [`DockState::merge_windows`].
+    ///
+    /// Windows have no title bar of their own, the tab bars along their top serve as one, so releasing a window
+    /// anywhere else over another one just leaves it there. Windows shown in their own OS-level viewport aren't
+    /// merged this way, since backends don't report them being dragged.
+    ///
+    /// By default it's `false`.
+    pub fn allow_window_merging(mut self, allow_window_merging: bool) -> Self {
+        self.allow_window_merging = allow_window_merging;
+        self
+    }
+
+    /// Whether dropping a tab outside the viewport of the [`DockArea`] detaches it into a window shown in its own
+    /// OS-level viewport, see [`WindowState::set_viewport`](crate::WindowState::set_viewport).
+    ///
+    /// Closing such a viewport puts its tabs back into the main surface, and so does dragging one of its tabs onto
+    /// a leaf of the main surface. Dragging the OS-level window itself onto the [`DockArea`] doesn't, since
+    /// backends don't report it.
+    /// Viewports are shown with [`egui::Context::show_viewport_immediate`], so if the `egui` backend doesn't
+    /// support multiple viewports, the window is shown inside the [`DockArea`] instead.
+    ///
+    /// By default it's `false`.
+    pub fn allow_os_window_detach(mut self, allow_os_window_detach: bool) -> Self {
+        self.allow_os_window_detach = allow_os_window_detach;
+        self
+    }
+
+    /// Whether every window is shown in its own OS-level viewport, which can be moved anywhere on screen,
+    /// including onto other monitors.
+    ///
+    /// Windows created inside the [`DockArea`], e.g. by dropping a tab onto empty space, are turned into viewports
+    /// as soon as they're created. Tabs dragged out of a viewport are dropped into the leaf they're released over
+    /// in any other viewport, or detached into a new viewport anywhere else. Closing a viewport puts its tabs back
+    /// into the main surface.
+    /// See [`allow_os_window_detach`](Self::allow_os_window_detach) for backends which don't support multiple
+    /// viewports.
+    ///
+    /// By default it's `false`.
+    pub fn native_windows(mut self, native_windows: bool) -> Self {
+        self.native_windows = native_windows;
+        self
+    }
+
+    /// How far the pointer has to move away from where a drag started before dropping the tabs
+    /// into a new window is suggested.
+    ///
+    /// Until then, dropping them where a new window would be created docks them into the hovered leaf instead,
+    /// so that reordering tabs doesn't accidentally create windows.
+    ///
+    /// By default it's `0.0`.
+    pub fn tearoff_distance(mut self, tearoff_distance: f32) -> Self {
+        self.tearoff_distance = tearoff_distance;
+        self
+    }
+
+    /// What happens when the user clicks a tab which is already active.
+    /// By default it's [`ReclickAction::None`].
+    pub fn on_active_tab_reclick(mut self, action: ReclickAction) -> Self {
+        self.active_tab_reclick = action;
+        self
+    }
+
+    /// Whether tabs become active when the primary mouse button is pressed on them or once it's released.
+    /// By default it's [`ActivationTrigger::OnRelease`].
+    pub fn tab_activation(mut self, tab_activation: ActivationTrigger) -> Self {
+        self.tab_activation = tab_activation;
+        self
+    }
+
+    /// The mouse button which opens the context menu of tabs when clicked,
+    /// see [`DockArea::tab_context_menus`].
+    /// By default it's [`PointerButton::Secondary`].
+    pub fn context_menu_button(mut self, context_menu_button: PointerButton) -> Self {
+        self.context_menu_button = context_menu_button;
+        self
+    }
+
     /// What directions can a node be split in: left-right, top-bottom, all, or none.
     /// By default it's all.
     pub fn allowed_splits(mut self, allowed_splits: AllowedSplits) -> Self {
@@ -166,8 +662,12 @@ impl<'tree, Tab> DockArea<'tree, Tab> {
         self
     }
 
-    /// The bounds for any windows inside the [`DockArea`]. Defaults to the screen rect.
-    /// By default it's set to [`egui::Context::screen_rect`].
+    /// The bounds for any windows inside the [`DockArea`].
+    ///
+    /// By default it's the [`Ui::max_rect`](egui::Ui::max_rect) of the `ui` given to
+    /// [`show_inside`](Self::show_inside) inset by [`Style::dock_area_padding`],
+    /// so that the windows stay within the dock area,
+    /// or [`egui::Context::screen_rect`] when using [`show`](Self::show).
     #[inline(always)]
     pub fn window_bounds(mut self, bounds: Rect) -> Self {
         self.window_bounds = Some(bounds);
@@ -207,6 +707,43 @@ impl<'tree, Tab> DockArea<'tree, Tab> {
         self.show_leaf_collapse_buttons = show_leaf_collapse_buttons;
         self
     }
+
+    /// Whether tab bars show a handle which can be dragged to move the whole leaf with all its tabs,
+    /// instead of a single tab.
+    /// By default it's `false`.
+    pub fn show_leaf_drag_handles(mut self, show_leaf_drag_handles: bool) -> Self {
+        self.show_leaf_drag_handles = show_leaf_drag_handles;
+        self
+    }
+
+    /// Closes the tabs for which `filter` returns `true` without calling
+    /// [`TabViewer::on_close`](crate::TabViewer::on_close), in addition to those closed by
+    /// [`TabViewer::force_close`](crate::TabViewer::force_close).
+    ///
+    /// Like [`TabViewer::force_close`](crate::TabViewer::force_close), this applies to the tabs of the leaves being shown.
+    /// By default no tabs are force closed this way.
+    pub fn force_close_if(mut self, filter: impl Fn(&Tab) -> bool + 'tree) -> Self {
+        self.force_close_if = Some(Box::new(filter));
+        self
+    }
+
+    /// Calls `callback` once all surfaces are shown, with where their leaves and windows were laid out.
+    ///
+    /// This is useful to draw overlays which span several tabs, such as lines connecting them.
+    pub fn on_layout(mut self, callback: impl FnOnce(&LayoutInfo) + 'tree) -> Self {
+        self.on_layout = Some(Box::new(callback));
+        self
+    }
+
+    /// Calls `callback` at the end of the frame with how the layout changed since the last frame, if it did.
+    ///
+    /// Changes made through the [`DockState`] in between frames are reported along with those made in the
+    /// [`DockArea`], see [`DockEvent`]. Events are reported relative to the previous frame, as long as this was
+    /// set in it too; no events are reported in the first frame of a run of frames this is set in.
+    pub fn on_events(mut self, callback: impl FnOnce(Vec<DockEvent>) + 'tree) -> Self {
+        self.on_events = Some(Box::new(callback));
+        self
+    }
 }
 
 impl<Tab> std::fmt::Debug for DockArea<'_, Tab> {
@@ -214,3 +751,1083 @@ impl<Tab> std::fmt::Debug for DockArea<'_, Tab> {
         f.debug_struct("DockArea").finish_non_exhaustive()
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{Node, OverflowMode, Shortcuts, Split, TabBarPosition, TabViewer};
+    use egui::{
+        ahash::HashMap, collapsing_header::CollapsingState, CollapsingHeader, Context, Event, Key,
+        PointerButton, RawInput, Ui, WidgetText,
+    };
+
+    /// Shows a collapsing header in each tab and records what it's told about the tabs.
+    ///
+    /// The other widgets it shows and the drops it allows are set by its fields.
+    #[derive(Default)]
+    struct TestViewer {
+        /// The id of the collapsing header in each tab, and whether it's open.
+        headers: HashMap<&'static str, (Id, bool)>,
+        /// The tabs shown with `ui`, in order.
+        shown: Vec<&'static str>,
+        first_shown: usize,
+        rect_changes: usize,
+        focus_changes: Vec<bool>,
+        maximize_changes: Vec<(&'static str, bool)>,
+
+        /// Shows the titles of the tabs as buttons wider than their text.
+        title_buttons: bool,
+        /// The size of a button shown before the tabs.
+        leading_button: Option<Vec2>,
+        /// The size of a button shown after the tabs.
+        trailing_button: Option<Vec2>,
+        /// Shows a text edit in the content, and one before the tabs which is tall enough to reach into it.
+        text_edits: bool,
+        text: String,
+        /// The ids of the text edits before the tabs and in the content.
+        edit_ids: (Option<Id>, Option<Id>),
+        /// Keeps tabs from being dropped into this node.
+        blocked: Option<NodeIndex>,
+        /// Keeps the content of tabs while they're hidden.
+        keep_hidden_content: bool,
+        /// Tells that the content of tabs isn't ready.
+        content_pending: bool,
+    }
+
+    impl TabViewer for TestViewer {
+        type Tab = &'static str;
+
+        fn title(&mut self, tab: &mut Self::Tab) -> WidgetText {
+            (*tab).into()
+        }
+
+        fn title_ui(&mut self, ui: &mut Ui, tab: &mut Self::Tab) {
+            if self.title_buttons {
+                ui.add_sized(vec2(100.0, 16.0), egui::Button::new(*tab));
+            } else {
+                ui.label(*tab);
+            }
+        }
+
+        fn ui(&mut self, ui: &mut Ui, tab: &mut Self::Tab) {
+            let id = CollapsingHeader::new("header")
+                .show(ui, |ui| ui.label(*tab))
+                .header_response
+                .id;
+            let open = CollapsingState::load(ui.ctx(), id).is_some_and(|state| state.is_open());
+            self.headers.insert(*tab, (id, open));
+            self.shown.push(*tab);
+            if self.text_edits {
+                self.edit_ids.1 = Some(ui.text_edit_singleline(&mut self.text).id);
+            }
+        }
+
+        fn tab_bar_leading_ui(&mut self, ui: &mut Ui, _surface: SurfaceIndex, _node: NodeIndex) {
+            if self.text_edits {
+                let edit = egui::TextEdit::multiline(&mut self.text);
+                self.edit_ids.0 = Some(ui.add_sized(vec2(40.0, 100.0), edit).id);
+            }
+            if let Some(size) = self.leading_button {
+                ui.add_sized(size, egui::Button::new("search"));
+            }
+        }
+
+        fn tab_bar_trailing_ui(&mut self, ui: &mut Ui, _surface: SurfaceIndex, _node: NodeIndex) {
+            if let Some(size) = self.trailing_button {
+                ui.add_sized(size, egui::Button::new("search"));
+            }
+        }
+
+        fn allowed_drop(
+            &self,
+            _tab: &Self::Tab,
+            _surface: SurfaceIndex,
+            node: NodeIndex,
+            _split: Option<Split>,
+        ) -> bool {
+            self.blocked != Some(node)
+        }
+
+        fn cache_when_hidden(&self, _tab: &Self::Tab) -> bool {
+            self.keep_hidden_content
+        }
+
+        fn content_ready(&self, _tab: &Self::Tab) -> bool {
+            !self.content_pending
+        }
+
+        fn on_first_shown(&mut self, _tab: &mut Self::Tab) {
+            self.first_shown += 1;
+        }
+
+        fn on_content_rect_changed(&mut self, _tab: &mut Self::Tab, _rect: Rect) {
+            self.rect_changes += 1;
+        }
+
+        fn on_content_focus_changed(&mut self, _tab: &mut Self::Tab, focused: bool) {
+            self.focus_changes.push(focused);
+        }
+
+        fn on_maximize_changed(&mut self, tab: &mut Self::Tab, maximized: bool) {
+            self.maximize_changes.push((*tab, maximized));
+        }
+    }
+
+    /// Shows `dock_state` with `viewer` in a frame for each of the `frames`.
+    fn run_frames(
+        ctx: &Context,
+        dock_state: &mut DockState<&'static str>,
+        viewer: &mut TestViewer,
+        frames: impl IntoIterator<Item = RawInput>,
+    ) {
+        run_frames_with(ctx, dock_state, viewer, frames, |area| area);
+    }
+
+    /// Like [`run_frames`], with the [`DockArea`] set up by `area`.
+    fn run_frames_with(
+        ctx: &Context,
+        dock_state: &mut DockState<&'static str>,
+        viewer: &mut TestViewer,
+        frames: impl IntoIterator<Item = RawInput>,
+        area: impl for<'a> Fn(DockArea<'a, &'static str>) -> DockArea<'a, &'static str>,
+    ) {
+        for input in frames {
+            let _ = ctx.run(input, |ctx| {
+                area(DockArea::new(dock_state)).show(ctx, viewer);
+            });
+        }
+    }
+
+    /// A frame of a 800×600 screen with `events`.
+    fn frame(events: Vec<Event>) -> RawInput {
+        RawInput {
+            screen_rect: Some(Rect::from_min_size(Pos2::ZERO, vec2(800.0, 600.0))),
+            events,
+            ..RawInput::default()
+        }
+    }
+
+    /// Presses or releases the primary button at `pos`.
+    fn press(pos: Pos2, pressed: bool) -> Event {
+        Event::PointerButton {
+            pos,
+            button: PointerButton::Primary,
+            pressed,
+            modifiers: Modifiers::NONE,
+        }
+    }
+
+    #[test]
+    fn content_state_follows_reordered_tabs() {
+        let ctx = Context::default();
+        let mut dock_state = DockState::new(vec!["a", "b"]);
+        let mut viewer = TestViewer::default();
+
+        run_frames(&ctx, &mut dock_state, &mut viewer, [RawInput::default()]);
+        let (id, open) = viewer.headers["a"];
+        assert!(!open);
+        let mut header = CollapsingState::load_with_default_open(&ctx, id, false);
+        header.set_open(true);
+        header.store(&ctx);
+
+        // Move the tab behind the other one, then into a split of its own.
+        let tree = dock_state.main_surface_mut();
+        tree[NodeIndex::root()].tabs_mut().unwrap().swap(0, 1);
+        tree.set_active_tab(NodeIndex::root(), TabIndex(1));
+        run_frames(&ctx, &mut dock_state, &mut viewer, [RawInput::default()]);
+        assert_eq!(viewer.headers["a"], (id, true));
+
+        let (node, tab) = dock_state.find_main_surface_tab(&"a").unwrap();
+        let tab = dock_state
+            .main_surface_mut()
+            .remove_tab((node, tab))
+            .unwrap();
+        dock_state
+            .main_surface_mut()
+            .split(NodeIndex::root(), Split::Right, 0.5, Node::leaf(tab));
+        run_frames(&ctx, &mut dock_state, &mut viewer, [RawInput::default()]);
+        assert_eq!(viewer.headers["a"], (id, true));
+    }
+
+    #[test]
+    fn layout_reports_shown_leaves_and_windows() {
+        let ctx = Context::default();
+        let mut dock_state = DockState::new(vec!["a", "b"]);
+        dock_state
+            .main_surface_mut()
+            .split_right(NodeIndex::root(), 0.5, vec!["c"]);
+        let window = dock_state.add_window(vec!["d"]);
+        let mut layout = None;
+        let _ = ctx.run(RawInput::default(), |ctx| {
+            DockArea::new(&mut dock_state)
+                .on_layout(|info| layout = Some(info.clone()))
+                .show(ctx, &mut TestViewer::default());
+        });
+
+        let layout = layout.unwrap();
+        assert_eq!(layout.leaves.len(), 3);
+        let left = layout
+            .leaf(SurfaceIndex::main(), NodeIndex::root().left())
+            .unwrap();
+        let right = layout
+            .leaf(SurfaceIndex::main(), NodeIndex::root().right())
+            .unwrap();
+        let (left_content, right_content) = (left.content.unwrap(), right.content.unwrap());
+        assert!(left_content.max.x <= right_content.min.x);
+        assert!(left.tab_bar.max.y <= left_content.min.y);
+        assert_eq!(layout.content_rects().count(), 3);
+        assert_eq!(layout.windows.len(), 1);
+        assert_eq!(layout.windows[0].0, window);
+        assert_eq!(
+            dock_state.get_window_state(window).unwrap().rect(),
+            layout.windows[0].1
+        );
+    }
+
+    #[test]
+    fn invisible_separator_stays_draggable() {
+        let ctx = Context::default();
+        let mut dock_state = DockState::new(vec!["a"]);
+        dock_state
+            .main_surface_mut()
+            .split_right(NodeIndex::root(), 0.5, vec!["b"]);
+        let mut style = Style::default();
+        style.separator.width = 0.0;
+        style.separator.visible = false;
+
+        let frames = [
+            Event::PointerMoved(pos2(400.0, 300.0)),
+            press(pos2(400.0, 300.0), true),
+            Event::PointerMoved(pos2(420.0, 300.0)),
+            Event::PointerMoved(pos2(480.0, 300.0)),
+        ]
+        .map(|event| frame(vec![event]));
+        run_frames_with(
+            &ctx,
+            &mut dock_state,
+            &mut TestViewer::default(),
+            frames,
+            |area| area.style(style.clone()),
+        );
+
+        let Node::Horizontal(split) = &dock_state.main_surface()[NodeIndex::root()] else {
+            panic!("the root must still be split");
+        };
+        assert!(split.fraction > 0.5);
+    }
+
+    #[test]
+    fn shortcuts_cycle_tabs_and_leaves() {
+        let ctx = Context::default();
+        let mut dock_state = DockState::new(vec!["a", "b"]);
+        let tree = dock_state.main_surface_mut();
+        let [left, right] = tree.split_right(NodeIndex::root(), 0.5, vec!["c"]);
+        dock_state.set_focused_node_and_surface((SurfaceIndex::main(), left));
+        let window = dock_state.add_window(vec!["d"]);
+        dock_state
+            .get_window_state_mut(window)
+            .unwrap()
+            .toggle_minimized();
+        let style = Style {
+            shortcuts: Shortcuts {
+                next_tab: Some(KeyboardShortcut::new(Modifiers::CTRL, Key::Tab)),
+                previous_tab: Some(KeyboardShortcut::new(
+                    Modifiers::CTRL | Modifiers::SHIFT,
+                    Key::Tab,
+                )),
+                next_leaf: Some(KeyboardShortcut::new(Modifiers::CTRL, Key::PageDown)),
+                previous_leaf: None,
+            },
+            ..Style::default()
+        };
+
+        let press = |dock_state: &mut DockState<_>, modifiers: Modifiers, key: Key| {
+            let input = RawInput {
+                modifiers,
+                events: vec![Event::Key {
+                    key,
+                    physical_key: None,
+                    pressed: true,
+                    repeat: false,
+                    modifiers,
+                }],
+                ..RawInput::default()
+            };
+            let viewer = &mut TestViewer::default();
+            run_frames_with(&ctx, dock_state, viewer, [input], |area| {
+                area.style(style.clone())
+            });
+        };
+
+        press(&mut dock_state, Modifiers::CTRL, Key::Tab);
+        assert_eq!(
+            dock_state.active_tab((SurfaceIndex::main(), left)),
+            Some(TabIndex(1))
+        );
+        press(
+            &mut dock_state,
+            Modifiers::CTRL | Modifiers::SHIFT,
+            Key::Tab,
+        );
+        assert_eq!(
+            dock_state.active_tab((SurfaceIndex::main(), left)),
+            Some(TabIndex(0))
+        );
+        press(&mut dock_state, Modifiers::CTRL, Key::PageDown);
+        assert_eq!(
+            dock_state.focused_leaf(),
+            Some((SurfaceIndex::main(), right))
+        );
+        // The leaf of the minimized window is skipped.
+        press(&mut dock_state, Modifiers::CTRL, Key::PageDown);
+        assert_eq!(
+            dock_state.focused_leaf(),
+            Some((SurfaceIndex::main(), left))
+        );
+    }
+
+    #[test]
+    fn close_tab_shortcut_leaves_widgets_outside_alone() {
+        let ctx = Context::default();
+        let mut dock_state = DockState::new(vec!["a", "b"]);
+        dock_state.set_focused_node_and_surface((SurfaceIndex::main(), NodeIndex::root()));
+        let shortcut = KeyboardShortcut::new(Modifiers::CTRL, Key::W);
+        let edit = Id::new("side panel edit");
+        let mut text = String::new();
+        let mut viewer = TestViewer {
+            text_edits: true,
+            ..TestViewer::default()
+        };
+        let mut run = |dock_state: &mut DockState<_>, viewer: &mut TestViewer, pressed: bool| {
+            let events = pressed.then(|| Event::Key {
+                key: Key::W,
+                physical_key: None,
+                pressed: true,
+                repeat: false,
+                modifiers: Modifiers::CTRL,
+            });
+            let input = RawInput {
+                modifiers: Modifiers::CTRL,
+                ..frame(events.into_iter().collect())
+            };
+            let _ = ctx.run(input, |ctx| {
+                egui::SidePanel::left("side panel").show(ctx, |ui| {
+                    ui.add(egui::TextEdit::singleline(&mut text).id(edit));
+                });
+                DockArea::new(dock_state)
+                    .close_tab_shortcut(Some(shortcut))
+                    .show(ctx, viewer);
+            });
+        };
+
+        run(&mut dock_state, &mut viewer, false);
+        ctx.memory_mut(|m| m.request_focus(edit));
+        run(&mut dock_state, &mut viewer, false);
+        run(&mut dock_state, &mut viewer, true);
+        assert_eq!(dock_state.main_surface().num_tabs(), 2);
+
+        ctx.memory_mut(|m| m.surrender_focus(edit));
+        run(&mut dock_state, &mut viewer, false);
+        run(&mut dock_state, &mut viewer, true);
+        assert_eq!(dock_state.main_surface().num_tabs(), 1);
+
+        // Widgets in the content of the tabs belong to the dock area.
+        ctx.memory_mut(|m| m.request_focus(viewer.edit_ids.1.unwrap()));
+        run(&mut dock_state, &mut viewer, false);
+        run(&mut dock_state, &mut viewer, true);
+        assert_eq!(dock_state.main_surface().num_tabs(), 0);
+    }
+
+    #[test]
+    fn only_widgets_in_the_content_focus_it() {
+        let ctx = Context::default();
+        let mut dock_state = DockState::new(vec!["a"]);
+        let mut viewer = TestViewer {
+            text_edits: true,
+            ..TestViewer::default()
+        };
+        let frames = || [RawInput::default(), RawInput::default()];
+
+        run_frames(&ctx, &mut dock_state, &mut viewer, frames());
+        ctx.memory_mut(|m| m.request_focus(viewer.edit_ids.0.unwrap()));
+        run_frames(&ctx, &mut dock_state, &mut viewer, frames());
+        assert!(viewer.focus_changes.is_empty());
+        ctx.memory_mut(|m| m.request_focus(viewer.edit_ids.1.unwrap()));
+        run_frames(&ctx, &mut dock_state, &mut viewer, frames());
+        assert_eq!(viewer.focus_changes, [true]);
+    }
+
+    #[test]
+    fn reopened_tabs_are_reported_anew() {
+        let ctx = Context::default();
+        let mut dock_state = DockState::new(vec!["a", "b"]);
+        let mut viewer = TestViewer::default();
+
+        run_frames(&ctx, &mut dock_state, &mut viewer, [RawInput::default()]);
+        assert_eq!((viewer.rect_changes, viewer.first_shown), (1, 1));
+        let tree = dock_state.main_surface_mut();
+        tree.remove_tab((NodeIndex::root(), TabIndex(0)));
+        run_frames(&ctx, &mut dock_state, &mut viewer, [RawInput::default()]);
+        assert_eq!((viewer.rect_changes, viewer.first_shown), (2, 2));
+
+        // The tab comes back where it was, as a new tab.
+        let tree = dock_state.main_surface_mut();
+        tree[NodeIndex::root()].insert_tab(TabIndex(0), "a");
+        tree.set_active_tab(NodeIndex::root(), TabIndex(0));
+        run_frames(&ctx, &mut dock_state, &mut viewer, [RawInput::default()]);
+        assert_eq!((viewer.rect_changes, viewer.first_shown), (3, 3));
+    }
+
+    #[test]
+    fn kept_content_is_shown_until_ready() {
+        let ctx = Context::default();
+        let mut dock_state = DockState::new(vec!["a", "b"]);
+        let mut viewer = TestViewer {
+            keep_hidden_content: true,
+            ..TestViewer::default()
+        };
+        let activate = |dock_state: &mut DockState<_>, viewer: &mut TestViewer, tab| {
+            let tree = dock_state.main_surface_mut();
+            tree.set_active_tab(NodeIndex::root(), TabIndex(tab));
+            run_frames(&ctx, dock_state, viewer, [RawInput::default()]);
+            std::mem::take(&mut viewer.shown)
+        };
+
+        assert_eq!(activate(&mut dock_state, &mut viewer, 0), ["a"]);
+        assert_eq!(activate(&mut dock_state, &mut viewer, 1), ["b"]);
+        viewer.content_pending = true;
+        assert!(activate(&mut dock_state, &mut viewer, 0).is_empty());
+        assert!(activate(&mut dock_state, &mut viewer, 0).is_empty());
+        viewer.content_pending = false;
+        assert_eq!(activate(&mut dock_state, &mut viewer, 0), ["a"]);
+    }
+
+    #[test]
+    fn windows_dropped_onto_title_bars_are_merged() {
+        let ctx = Context::default();
+        let mut dock_state = DockState::new(vec!["a"]);
+        let windows = [("b", pos2(100.0, 100.0)), ("c", pos2(200.0, 200.0))].map(|(tab, pos)| {
+            let window = dock_state.add_window(vec![tab]);
+            let window_state = dock_state.get_window_state_mut(window).unwrap();
+            window_state.set_position(pos).set_size(vec2(200.0, 150.0));
+            window
+        });
+
+        let to = pos2(200.0, 112.0);
+        let mut frames = vec![
+            Vec::new(),
+            vec![Event::PointerMoved(pos2(350.0, 340.0))],
+            vec![press(pos2(350.0, 340.0), true)],
+            vec![Event::PointerMoved(pos2(340.0, 330.0))],
+        ];
+        frames.extend((0..3).map(|_| vec![Event::PointerMoved(to)]));
+        frames.push(vec![press(to, false)]);
+        frames.push(Vec::new());
+        let frames = frames
+            .into_iter()
+            .enumerate()
+            .map(|(index, events)| RawInput {
+                time: Some(index as f64 * 0.1),
+                ..frame(events)
+            });
+        let viewer = &mut TestViewer::default();
+        run_frames_with(&ctx, &mut dock_state, viewer, frames, |area| {
+            area.allow_window_merging(true)
+        });
+
+        assert!(!dock_state.is_surface_valid(windows[1]));
+        let tabs: Vec<_> = dock_state[windows[0]].tabs().copied().collect();
+        assert_eq!(tabs, ["b", "c"]);
+    }
+
+    #[test]
+    fn hit_test_follows_window_stacking() {
+        let ctx = Context::default();
+        let mut dock_state = DockState::new(vec!["a"]);
+        let windows = [("b", pos2(100.0, 100.0)), ("c", pos2(200.0, 200.0))].map(|(tab, pos)| {
+            let window = dock_state.add_window(vec![tab]);
+            let window_state = dock_state.get_window_state_mut(window).unwrap();
+            window_state.set_position(pos).set_size(vec2(200.0, 150.0));
+            window
+        });
+        let viewer = &mut TestViewer::default();
+        let overlap = pos2(280.0, 240.0);
+        run_frames(&ctx, &mut dock_state, viewer, [frame(Vec::new())]);
+        assert_eq!(
+            dock_state.hit_test(overlap).map(|(surface, _)| surface),
+            Some(windows[1])
+        );
+
+        // Raising the first window puts it on top, even though it was added first.
+        dock_state.get_window_state_mut(windows[0]).unwrap().raise();
+        run_frames(
+            &ctx,
+            &mut dock_state,
+            viewer,
+            [frame(Vec::new()), frame(Vec::new())],
+        );
+        assert_eq!(
+            dock_state.hit_test(overlap).map(|(surface, _)| surface),
+            Some(windows[0])
+        );
+    }
+
+    #[test]
+    fn shrunk_tabs_never_overflow() {
+        let ctx = Context::default();
+        let overflows = |mode: OverflowMode| {
+            let mut dock_state = DockState::new(vec!["a rather long title"; 10]);
+            let mut style = Style::default();
+            style.tab_bar.overflow_mode = mode;
+            let input = RawInput {
+                screen_rect: Some(Rect::from_min_size(Pos2::ZERO, vec2(300.0, 200.0))),
+                ..RawInput::default()
+            };
+            let viewer = &mut TestViewer::default();
+            run_frames_with(
+                &ctx,
+                &mut dock_state,
+                viewer,
+                [input.clone(), input],
+                |area| area.style(style.clone()),
+            );
+            dock_state.main_surface()[NodeIndex::root()]
+                .get_leaf()
+                .unwrap()
+                .tab_overflow()
+        };
+
+        assert_eq!(overflows(OverflowMode::Scroll), Some(true));
+        assert_eq!(overflows(OverflowMode::Menu), Some(true));
+        assert_eq!(overflows(OverflowMode::Shrink), Some(false));
+    }
+
+    #[test]
+    fn maximize_changes_are_reported() {
+        let ctx = Context::default();
+        let mut dock_state = DockState::new(vec!["a", "b"]);
+        let [_, right] =
+            dock_state
+                .main_surface_mut()
+                .split_right(NodeIndex::root(), 0.5, vec!["c"]);
+        let mut viewer = TestViewer::default();
+        let mut run = |dock_state: &mut DockState<_>| {
+            run_frames(&ctx, dock_state, &mut viewer, [RawInput::default()]);
+            std::mem::take(&mut viewer.maximize_changes)
+        };
+
+        assert!(run(&mut dock_state).is_empty());
+        dock_state
+            .maximize(SurfaceIndex::main(), right, TabIndex(0))
+            .unwrap();
+        assert_eq!(run(&mut dock_state), vec![("c", true)]);
+        assert!(run(&mut dock_state).is_empty());
+        dock_state.restore_maximized();
+        assert_eq!(run(&mut dock_state), vec![("c", false)]);
+    }
+
+    #[test]
+    fn tab_bars_follow_their_position() {
+        let ctx = Context::default();
+        let mut dock_state = DockState::new(vec!["a", "b"]);
+        let [left, right] =
+            dock_state
+                .main_surface_mut()
+                .split_right(NodeIndex::root(), 0.5, vec!["c"]);
+        let mut style = Style::default();
+        style.tab_bar.position = TabBarPosition::Bottom;
+        dock_state.main_surface_mut()[right].set_tab_bar_position(TabBarPosition::Right);
+        let mut layout = None;
+        let _ = ctx.run(RawInput::default(), |ctx| {
+            DockArea::new(&mut dock_state)
+                .style(style.clone())
+                .on_layout(|info| layout = Some(info.clone()))
+                .show(ctx, &mut TestViewer::default());
+        });
+
+        let layout = layout.unwrap();
+        let left = layout.leaf(SurfaceIndex::main(), left).unwrap();
+        let right = layout.leaf(SurfaceIndex::main(), right).unwrap();
+        assert!(left.content.unwrap().max.y <= left.tab_bar.min.y);
+        assert!(right.content.unwrap().max.x <= right.tab_bar.min.x);
+        assert!(right.tab_bar.height() > right.tab_bar.width());
+    }
+
+    #[test]
+    fn size_ranges_are_kept_when_resized() {
+        let ctx = Context::default();
+        let mut dock_state = DockState::new(vec!["a"]);
+        let [left, right] =
+            dock_state
+                .main_surface_mut()
+                .split_right(NodeIndex::root(), 0.9, vec!["b"]);
+        dock_state.main_surface_mut()[left].set_size_range(Rangef::new(0.0, 500.0), None);
+        dock_state.main_surface_mut()[right].set_size_range(Rangef::new(200.0, 600.0), None);
+
+        let widths = |dock_state: &mut DockState<&'static str>, width: f32| {
+            let input = RawInput {
+                screen_rect: Some(Rect::from_min_size(Pos2::ZERO, vec2(width, 600.0))),
+                ..Default::default()
+            };
+            run_frames(&ctx, dock_state, &mut TestViewer::default(), [input]);
+            let tree = dock_state.main_surface();
+            (
+                tree[left].rect().unwrap().width(),
+                tree[right].rect().unwrap().width(),
+            )
+        };
+
+        let (_, right_width) = widths(&mut dock_state, 800.0);
+        assert!(right_width >= 199.0, "{right_width}");
+        let (left_width, right_width) = widths(&mut dock_state, 1000.0);
+        assert!(left_width <= 501.0, "{left_width}");
+        assert!(right_width <= 601.0, "{right_width}");
+        let (_, right_width) = widths(&mut dock_state, 300.0);
+        assert!(right_width >= 199.0, "{right_width}");
+    }
+
+    #[test]
+    fn tabs_are_dragged_between_areas() {
+        let ctx = Context::default();
+        let mut left = DockState::new(vec!["a", "b"]);
+        let mut right = DockState::new(vec!["c"]);
+        let mut drag_context = DockDragContext::new();
+
+        let mut frames = vec![
+            vec![Event::PointerMoved(pos2(50.0, 20.0))],
+            vec![press(pos2(50.0, 20.0), true)],
+            vec![Event::PointerMoved(pos2(60.0, 20.0))],
+            vec![Event::PointerMoved(pos2(120.0, 20.0))],
+        ];
+        frames.extend((0..5).map(|_| vec![Event::PointerMoved(pos2(600.0, 300.0))]));
+        frames.push(vec![press(pos2(600.0, 300.0), false)]);
+        frames.push(Vec::new());
+
+        for (index, events) in frames.into_iter().enumerate() {
+            let input = RawInput {
+                time: Some(index as f64 * 0.1),
+                ..frame(events)
+            };
+            let _ = ctx.run(input, |ctx| {
+                egui::CentralPanel::default().show(ctx, |ui| {
+                    let (left_rect, right_rect) = ui.max_rect().split_left_right_at_x(400.0);
+                    ui.scope_builder(egui::UiBuilder::new().max_rect(left_rect), |ui| {
+                        DockArea::new(&mut left)
+                            .id(Id::new("left"))
+                            .drag_context(&mut drag_context)
+                            .show_inside(ui, &mut TestViewer::default());
+                    });
+                    ui.scope_builder(egui::UiBuilder::new().max_rect(right_rect), |ui| {
+                        DockArea::new(&mut right)
+                            .id(Id::new("right"))
+                            .drag_context(&mut drag_context)
+                            .show_inside(ui, &mut TestViewer::default());
+                    });
+                });
+            });
+        }
+
+        let tabs = |dock_state: &DockState<&'static str>| {
+            let mut tabs: Vec<_> = dock_state.iter_all_tabs().map(|(_, tab)| *tab).collect();
+            tabs.sort_unstable();
+            tabs
+        };
+        assert_eq!(tabs(&left), ["b"]);
+        assert_eq!(tabs(&right), ["a", "c"]);
+        assert!(!drag_context.is_active());
+    }
+
+    #[test]
+    fn title_ui_doesnt_take_clicks() {
+        let ctx = Context::default();
+        let mut dock_state = DockState::new(vec!["a", "b"]);
+        let mut viewer = TestViewer {
+            title_buttons: true,
+            ..TestViewer::default()
+        };
+        let frames = [
+            Vec::new(),
+            vec![Event::PointerMoved(pos2(200.0, 12.0))],
+            vec![press(pos2(200.0, 12.0), true)],
+            vec![press(pos2(200.0, 12.0), false)],
+            Vec::new(),
+        ]
+        .map(frame);
+        run_frames(&ctx, &mut dock_state, &mut viewer, frames);
+
+        // The second tab is only under the pointer if its button is as wide as its custom title.
+        let leaf = dock_state.main_surface()[NodeIndex::root()]
+            .get_leaf()
+            .unwrap();
+        assert_eq!(leaf.active, TabIndex(1));
+    }
+
+    #[test]
+    fn disallowed_drops_are_rejected() {
+        let drop_into_right = |blocked: bool| {
+            let ctx = Context::default();
+            let mut dock_state = DockState::new(vec!["a", "b"]);
+            let [_, right] =
+                dock_state
+                    .main_surface_mut()
+                    .split_right(NodeIndex::root(), 0.5, vec!["c"]);
+            let mut viewer = TestViewer {
+                blocked: blocked.then_some(right),
+                ..TestViewer::default()
+            };
+
+            let mut frames = vec![
+                vec![Event::PointerMoved(pos2(40.0, 12.0))],
+                vec![press(pos2(40.0, 12.0), true)],
+                vec![Event::PointerMoved(pos2(50.0, 12.0))],
+                vec![Event::PointerMoved(pos2(300.0, 200.0))],
+            ];
+            frames.extend((0..5).map(|_| vec![Event::PointerMoved(pos2(600.0, 312.0))]));
+            frames.push(vec![press(pos2(600.0, 312.0), false)]);
+            frames.push(Vec::new());
+            let frames = frames
+                .into_iter()
+                .enumerate()
+                .map(|(index, events)| RawInput {
+                    time: Some(index as f64 * 0.1),
+                    ..frame(events)
+                });
+            run_frames(&ctx, &mut dock_state, &mut viewer, frames);
+            assert_eq!(dock_state.surfaces_count(), 1);
+            dock_state.main_surface()[right].tabs().unwrap().to_vec()
+        };
+
+        assert_eq!(drop_into_right(false), ["c", "a"]);
+        assert_eq!(drop_into_right(true), ["c"]);
+    }
+
+    #[test]
+    fn layout_changes_are_reported_as_events() {
+        let ctx = Context::default();
+        let mut dock_state = DockState::new(vec!["a", "b"]);
+        let run = |dock_state: &mut DockState<_>| {
+            let mut events = Vec::new();
+            let _ = ctx.run(RawInput::default(), |ctx| {
+                DockArea::new(dock_state)
+                    .on_events(|new_events| events = new_events)
+                    .show(ctx, &mut TestViewer::default());
+            });
+            events
+        };
+
+        assert!(run(&mut dock_state).is_empty());
+        let [left, right] =
+            dock_state
+                .main_surface_mut()
+                .split_right(NodeIndex::root(), 0.5, vec!["c"]);
+        let main = SurfaceIndex::main();
+        assert_eq!(
+            run(&mut dock_state),
+            [
+                DockEvent::NodeSplit {
+                    surface: main,
+                    node: NodeIndex::root(),
+                },
+                DockEvent::TabAdded {
+                    to: (main, right, TabIndex(0)),
+                },
+                DockEvent::TabMoved {
+                    from: (main, NodeIndex::root(), TabIndex(0)),
+                    to: (main, left, TabIndex(0)),
+                },
+                DockEvent::TabMoved {
+                    from: (main, NodeIndex::root(), TabIndex(1)),
+                    to: (main, left, TabIndex(1)),
+                },
+            ]
+        );
+
+        // Closing the first tab shifts the index of the second one without moving it.
+        dock_state.remove_tab((main, left, TabIndex(0)));
+        if let Node::Vertical(split) | Node::Horizontal(split) =
+            &mut dock_state.main_surface_mut()[NodeIndex::root()]
+        {
+            split.fraction = 0.25;
+        }
+        assert_eq!(
+            run(&mut dock_state),
+            [
+                DockEvent::NodeResized {
+                    surface: main,
+                    node: NodeIndex::root(),
+                    fraction: 0.25,
+                },
+                DockEvent::TabClosed {
+                    from: (main, left, TabIndex(0)),
+                },
+            ]
+        );
+        assert!(run(&mut dock_state).is_empty());
+    }
+
+    #[test]
+    fn closed_tabs_come_back_with_undo() {
+        let ctx = Context::default();
+        let mut dock_state = DockState::new(vec!["a", "b"]);
+        dock_state.set_layout_history(10);
+        let run = |dock_state: &mut DockState<_>| {
+            run_frames(
+                &ctx,
+                dock_state,
+                &mut TestViewer::default(),
+                [RawInput::default()],
+            );
+        };
+        let tabs = |dock_state: &DockState<&'static str>| {
+            dock_state
+                .iter_all_tabs()
+                .map(|(_, tab)| *tab)
+                .collect::<Vec<_>>()
+        };
+
+        run(&mut dock_state);
+        assert!(!dock_state.can_undo_layout());
+        dock_state.remove_tab((SurfaceIndex::main(), NodeIndex::root(), TabIndex(0)));
+        run(&mut dock_state);
+        dock_state
+            .main_surface_mut()
+            .split_below(NodeIndex::root(), 0.5, vec!["c"]);
+        run(&mut dock_state);
+        assert_eq!(tabs(&dock_state), ["b", "c"]);
+
+        assert!(dock_state.undo_layout());
+        run(&mut dock_state);
+        assert_eq!(tabs(&dock_state), ["b"]);
+        assert!(dock_state.undo_layout());
+        run(&mut dock_state);
+        assert_eq!(tabs(&dock_state), ["a", "b"]);
+        assert!(!dock_state.undo_layout());
+
+        assert!(dock_state.redo_layout());
+        run(&mut dock_state);
+        assert_eq!(tabs(&dock_state), ["b"]);
+
+        // A new change drops the undone steps.
+        dock_state.push_to_first_leaf("d");
+        run(&mut dock_state);
+        assert!(!dock_state.can_redo_layout());
+        assert!(dock_state.undo_layout());
+        assert_eq!(tabs(&dock_state), ["b"]);
+        run(&mut dock_state);
+
+        // Undoing and redoing in between frames changes nothing, the changes after that are still steps.
+        assert!(dock_state.undo_layout());
+        assert!(dock_state.redo_layout());
+        run(&mut dock_state);
+        dock_state.push_to_first_leaf("e");
+        run(&mut dock_state);
+        assert!(dock_state.undo_layout());
+        assert_eq!(tabs(&dock_state), ["b"]);
+    }
+
+    #[test]
+    fn tab_bar_widgets_take_up_space() {
+        let ctx = Context::default();
+        let overflows = |width: f32| {
+            let mut dock_state = DockState::new(vec!["first tab", "second tab"]);
+            let mut viewer = TestViewer {
+                trailing_button: (width > 0.0).then_some(vec2(width, 16.0)),
+                ..TestViewer::default()
+            };
+            let input = RawInput {
+                screen_rect: Some(Rect::from_min_size(Pos2::ZERO, vec2(300.0, 200.0))),
+                ..RawInput::default()
+            };
+            let frames = [input.clone(), input.clone(), input];
+            run_frames_with(&ctx, &mut dock_state, &mut viewer, frames, |area| {
+                area.id(Id::new(width.to_bits()))
+            });
+            dock_state.main_surface()[NodeIndex::root()]
+                .get_leaf()
+                .unwrap()
+                .tab_overflow()
+        };
+
+        assert_eq!(overflows(0.0), Some(false));
+        assert_eq!(overflows(200.0), Some(true));
+    }
+
+    #[test]
+    fn vertical_tab_bar_widgets_take_up_space() {
+        let ctx = Context::default();
+        let overflows = |length: f32| {
+            let mut dock_state = DockState::new(vec!["first tab", "second tab"]);
+            dock_state.main_surface_mut()[NodeIndex::root()]
+                .set_tab_bar_position(TabBarPosition::Left);
+            let mut viewer = TestViewer {
+                leading_button: (length > 0.0).then_some(vec2(16.0, length)),
+                ..TestViewer::default()
+            };
+            let input = RawInput {
+                screen_rect: Some(Rect::from_min_size(Pos2::ZERO, vec2(300.0, 400.0))),
+                ..RawInput::default()
+            };
+            let frames = [input.clone(), input.clone(), input];
+            run_frames_with(&ctx, &mut dock_state, &mut viewer, frames, |area| {
+                area.id(Id::new(length.to_bits()))
+            });
+            dock_state.main_surface()[NodeIndex::root()]
+                .get_leaf()
+                .unwrap()
+                .tab_overflow()
+        };
+
+        assert_eq!(overflows(0.0), Some(false));
+        assert_eq!(overflows(300.0), Some(true));
+    }
+
+    #[test]
+    fn new_leaves_grow_into_place() {
+        let ctx = Context::default();
+        let mut dock_state = DockState::new(vec!["a"]);
+        let show = |dock_state: &mut DockState<&'static str>, time: f64| {
+            let input = RawInput {
+                time: Some(time),
+                ..frame(Vec::new())
+            };
+            run_frames(&ctx, dock_state, &mut TestViewer::default(), [input]);
+            let tree = dock_state.main_surface();
+            tree.iter()
+                .nth(NodeIndex::root().right().0)
+                .and_then(Node::rect)
+                .map(|rect| rect.width())
+        };
+
+        show(&mut dock_state, 0.0);
+        dock_state
+            .main_surface_mut()
+            .split_right(NodeIndex::root(), 0.5, vec!["b"]);
+        let growing = [0.1, 0.15, 0.2].map(|time| show(&mut dock_state, time).unwrap());
+        let grown = show(&mut dock_state, 1.0).unwrap();
+
+        assert_eq!(growing[0], 0.0);
+        assert!(growing[0] < growing[1] && growing[1] < growing[2] && growing[2] < grown);
+        assert!((grown - 400.0).abs() < 2.0, "{grown}");
+    }
+
+    #[test]
+    fn dragged_tabs_make_room() {
+        let ctx = Context::default();
+        let mut dock_state = DockState::new(vec!["a", "b", "c"]);
+        let tab_rect = |tab: usize| {
+            let id = Id::new("area")
+                .with((SurfaceIndex::main(), "surface"))
+                .with((NodeIndex::root(), "node"))
+                .with((tab, "tab"));
+            ctx.read_response(id).unwrap().rect
+        };
+        let show = |dock_state: &mut DockState<&'static str>, time: f64, events| {
+            let input = RawInput {
+                time: Some(time),
+                ..frame(events)
+            };
+            let viewer = &mut TestViewer::default();
+            run_frames_with(&ctx, dock_state, viewer, [input], |area| {
+                area.id(Id::new("area"))
+            });
+        };
+
+        show(&mut dock_state, 0.0, Vec::new());
+        let (first, second) = (tab_rect(0), tab_rect(1));
+        show(
+            &mut dock_state,
+            0.1,
+            vec![Event::PointerMoved(first.center())],
+        );
+        show(&mut dock_state, 0.2, vec![press(first.center(), true)]);
+
+        // Over the start of the second tab, which moves over to where the first tab was.
+        let pos = pos2(second.left() + 2.0, first.center().y + 8.0);
+        let nudged = first.center() + vec2(10.0, 0.0);
+        show(&mut dock_state, 0.25, vec![Event::PointerMoved(nudged)]);
+        show(&mut dock_state, 0.3, vec![Event::PointerMoved(pos)]);
+        let sliding: Vec<f32> = (4..8)
+            .map(|frame| {
+                let events = vec![Event::PointerMoved(pos)];
+                show(&mut dock_state, frame as f64 * 0.05 + 0.1, events);
+                tab_rect(1).left()
+            })
+            .collect();
+        show(&mut dock_state, 1.0, Vec::new());
+        assert!(sliding.windows(2).all(|x| x[0] >= x[1]), "{sliding:?}");
+        assert!(sliding[0] > first.left(), "{sliding:?}");
+        assert!((tab_rect(1).left() - first.left()).abs() < 0.5);
+
+        show(&mut dock_state, 1.1, vec![press(pos, false)]);
+        show(&mut dock_state, 1.2, Vec::new());
+        let tabs: Vec<_> = dock_state.iter_all_tabs().map(|(_, tab)| *tab).collect();
+        assert_eq!(tabs, ["b", "a", "c"]);
+    }
+
+    #[test]
+    fn maximized_leaves_can_be_closed() {
+        let ctx = Context::default();
+        let mut dock_state = DockState::new(vec!["a"]);
+        dock_state.set_maximized_leaf(Some((SurfaceIndex::main(), NodeIndex::root())));
+        let viewer = &mut TestViewer::default();
+        run_frames_with(
+            &ctx,
+            &mut dock_state,
+            viewer,
+            [RawInput::default()],
+            |area| area.force_close_if(|_| true),
+        );
+        assert_eq!(dock_state.iter_all_tabs().count(), 0);
+        assert_eq!(dock_state.maximized_leaf(), None);
+    }
+
+    #[test]
+    fn tabs_dropped_outside_get_their_own_viewport() {
+        let ctx = Context::default();
+        ctx.set_embed_viewports(false);
+        let mut dock_state = DockState::new(vec!["a", "b"]);
+        let show = |dock_state: &mut DockState<&'static str>, time: f64, events| {
+            let input = RawInput {
+                time: Some(time),
+                ..frame(events)
+            };
+            let viewer = &mut TestViewer::default();
+            run_frames_with(&ctx, dock_state, viewer, [input], |area| {
+                area.id(Id::new("area")).allow_os_window_detach(true)
+            });
+        };
+
+        show(&mut dock_state, 0.0, Vec::new());
+        let id = Id::new("area")
+            .with((SurfaceIndex::main(), "surface"))
+            .with((NodeIndex::root(), "node"))
+            .with((0, "tab"));
+        let tab = ctx.read_response(id).unwrap().rect.center();
+        show(&mut dock_state, 0.1, vec![Event::PointerMoved(tab)]);
+        show(&mut dock_state, 0.2, vec![press(tab, true)]);
+        let pos = tab + vec2(0.0, 100.0);
+        show(
+            &mut dock_state,
+            0.25,
+            vec![Event::PointerMoved(tab + vec2(10.0, 0.0))],
+        );
+        show(&mut dock_state, 0.3, vec![Event::PointerMoved(pos)]);
+        show(&mut dock_state, 0.4, vec![Event::PointerMoved(pos)]);
+
+        // Backends only tell that the pointer left, and report the release where it was last seen.
+        show(&mut dock_state, 0.5, vec![Event::PointerGone]);
+        show(
+            &mut dock_state,
+            0.6,
+            vec![press(pos, false), Event::PointerGone],
+        );
+        let windows: Vec<_> = dock_state
+            .iter_windows()
+            .map(|(_, tree, window_state)| {
+                let tabs: Vec<_> = tree.tabs().copied().collect();
+                (tabs, window_state.is_viewport())
+            })
+            .collect();
+        assert_eq!(windows, [(vec!["a"], true)]);
+    }
+}
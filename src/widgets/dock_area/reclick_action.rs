@@ -0,0 +1,15 @@
+/// What happens when the user clicks a tab which is already active.
+///
+/// [`TabViewer::on_active_tab_clicked`](crate::TabViewer::on_active_tab_clicked) is called regardless of the action.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum ReclickAction {
+    #[default]
+    /// Do nothing.
+    None,
+
+    /// Move keyboard focus away from the tab, to the content of its leaf.
+    FocusContent,
+
+    /// Toggle whether the leaf of the tab is maximized to fill the whole [`DockArea`](crate::DockArea).
+    ToggleMaximize,
+}
@@ -1,8 +1,8 @@
 use egui::{
-    emath::TSTransform, epaint::TextShape, lerp, pos2, vec2, Align, Align2, Button, Color32,
-    CornerRadius, CursorIcon, Frame, Id, Key, LayerId, Layout, NumExt, Order, Popup,
-    PopupCloseBehavior, Rect, Response, ScrollArea, Sense, Shape, Stroke, StrokeKind, TextStyle,
-    Ui, UiBuilder, Vec2, WidgetText,
+    epaint::TextShape, lerp, pos2, vec2, Align, Align2, Button, Color32, CornerRadius, CursorIcon,
+    Frame, Id, Key, LayerId, Layout, NumExt, Order, Painter, PointerButton, Popup, Pos2, Rect,
+    Response, ScrollArea, Sense, SetOpenCommand, Shape, Spinner, Stroke, StrokeKind, TextEdit,
+    TextStyle, Ui, UiBuilder, Vec2, ViewportCommand, WidgetText,
 };
 use std::ops::RangeInclusive;
 
@@ -10,14 +10,17 @@ use crate::dock_area::tab_removal::{ForcedRemoval, TabRemoval};
 use crate::node::LeafNode;
 use crate::{
     dock_area::{
+        animation,
         drag_and_drop::{DragData, DragDropState, HoverData, TreeComponent},
-        state::State,
+        state::{ContentCache, State, TabRename},
     },
     utils::{fade_visuals, rect_set_size_centered, rect_stroke_box},
-    DockArea, Node, NodeIndex, Style, SurfaceIndex, TabAddAlign, TabIndex, TabStyle, TabViewer,
+    ActiveTabIndicator, AllowedSplits, CloseButtonVisibility, DockArea, LeafLayout, Node,
+    NodeIndex, OverflowMode, Split, StandardActions, Style, SurfaceIndex, TabBarPosition, TabIndex,
+    TabInteractionStyle, TabStyle, TabViewer,
 };
 
-use crate::tab_viewer::OnCloseResponse;
+use crate::tab_viewer::{OnCloseResponse, TabButtonState, TabContext};
 
 impl<Tab> DockArea<'_, Tab> {
     pub(super) fn show_leaf(
@@ -47,14 +50,86 @@ impl<Tab> DockArea<'_, Tab> {
         if self.dock_state[surface_index][node_index].tabs_count() == 0 {
             return;
         }
-        let tabbar_rect = self.tab_bar(
-            ui,
-            state,
+        self.tab_hover_rect = None;
+        // A collapsed leaf only consists of its tab bar, so it's never hidden.
+        let hide_tab_bar = self.hide_tab_bar_if_single
+            && !collapsed
+            && self.dock_state[surface_index][node_index].tabs_count() == 1;
+        let position = self.tab_bar_position(
             (surface_index, node_index),
-            tab_viewer,
-            fade_style.map(|(style, _)| style),
-            collapsed,
+            fade_style.map_or_else(|| self.style.as_ref().unwrap(), |(style, _)| style),
         );
+        let mut content_ui = None;
+        let tabbar_rect = if hide_tab_bar {
+            Rect::from_min_size(rect.min, vec2(rect.width(), 0.0))
+        } else if position == TabBarPosition::Top {
+            self.tab_bar(
+                ui,
+                state,
+                (surface_index, node_index),
+                tab_viewer,
+                fade_style.map(|(style, _)| style),
+                collapsed,
+            )
+        } else {
+            // The tab bar is shown in a strip along the edge of the leaf, and the content in the rest of it.
+            let thickness =
+                self.tab_bar_thickness((surface_index, node_index), position, fade_style);
+            let (bar_rect, content_rect) = match position {
+                TabBarPosition::Bottom => {
+                    let (content, bar) = rect.split_top_bottom_at_y(rect.bottom() - thickness);
+                    (bar, content)
+                }
+                TabBarPosition::Left => rect.split_left_right_at_x(rect.left() + thickness),
+                _ => {
+                    let (content, bar) = rect.split_left_right_at_x(rect.right() - thickness);
+                    (bar, content)
+                }
+            };
+            let bar_ui = &mut ui.new_child(
+                UiBuilder::new()
+                    .max_rect(bar_rect)
+                    .layout(Layout::top_down_justified(Align::Min))
+                    .id_salt("tab_bar"),
+            );
+            content_ui = Some(
+                ui.new_child(
+                    UiBuilder::new()
+                        .max_rect(content_rect)
+                        .layout(Layout::top_down_justified(Align::Min))
+                        .id_salt("content"),
+                ),
+            );
+            if position == TabBarPosition::Bottom {
+                self.tab_bar(
+                    bar_ui,
+                    state,
+                    (surface_index, node_index),
+                    tab_viewer,
+                    fade_style.map(|(style, _)| style),
+                    collapsed,
+                )
+            } else {
+                self.vertical_tab_bar(
+                    bar_ui,
+                    state,
+                    (surface_index, node_index),
+                    tab_viewer,
+                    fade_style.map(|(style, _)| style),
+                    position,
+                )
+            }
+        };
+        let ui = content_ui.as_mut().unwrap_or(ui);
+        if !collapsed {
+            self.leaf_toolbar(
+                ui,
+                (surface_index, node_index),
+                tab_viewer,
+                spacing,
+                fade_style,
+            );
+        }
         self.tab_body(
             ui,
             state,
@@ -66,11 +141,28 @@ impl<Tab> DockArea<'_, Tab> {
             collapsed,
         );
 
+        if self.on_layout.is_some() {
+            let leaf = self.dock_state[surface_index][node_index]
+                .get_leaf()
+                .expect("This node must be a leaf here");
+            self.layout_info.leaves.push(LeafLayout {
+                surface: surface_index,
+                node: node_index,
+                active: leaf.active,
+                tab_bar: tabbar_rect,
+                content: (!collapsed).then_some(leaf.viewport),
+            });
+        }
+
         let tabs = self.dock_state[surface_index][node_index]
             .tabs_mut()
             .expect("This node must be a leaf here");
         for (tab_index, tab) in tabs.iter_mut().enumerate() {
-            if tab_viewer.force_close(tab) {
+            let filtered = self
+                .force_close_if
+                .as_ref()
+                .is_some_and(|filter| filter(tab));
+            if tab_viewer.force_close(tab) || filtered {
                 self.to_remove.push(TabRemoval::Tab(
                     surface_index,
                     node_index,
@@ -81,6 +173,42 @@ impl<Tab> DockArea<'_, Tab> {
         }
     }
 
+    /// Returns which side of a leaf its tab bar is placed on. Collapsed leaves always have it on top.
+    pub(super) fn tab_bar_position(
+        &self,
+        (surface_index, node_index): (SurfaceIndex, NodeIndex),
+        style: &Style,
+    ) -> TabBarPosition {
+        let leaf = self.dock_state[surface_index][node_index]
+            .get_leaf()
+            .expect("This node must be a leaf");
+        if leaf.collapsed {
+            TabBarPosition::Top
+        } else {
+            leaf.tab_bar_position.unwrap_or(style.tab_bar.position)
+        }
+    }
+
+    /// Returns the height of a bottom tab bar, including its scroll bar if the tabs overflowed in the last frame,
+    /// or the width of a vertical one.
+    fn tab_bar_thickness(
+        &self,
+        (surface_index, node_index): (SurfaceIndex, NodeIndex),
+        position: TabBarPosition,
+        fade_style: Option<(&Style, f32)>,
+    ) -> f32 {
+        let style = fade_style.map_or_else(|| self.style.as_ref().unwrap(), |(style, _)| style);
+        let overflowed = self.dock_state[surface_index][node_index]
+            .get_leaf()
+            .and_then(|leaf| leaf.tab_overflow)
+            .unwrap_or(false);
+        if overflowed && style.tab_bar.show_scroll_bar_on_overflow && !position.is_vertical() {
+            style.tab_bar_height() + Style::TAB_SCROLL_BAR_HEIGHT
+        } else {
+            style.tab_bar_height()
+        }
+    }
+
     fn tab_bar(
         &mut self,
         ui: &mut Ui,
@@ -94,36 +222,54 @@ impl<Tab> DockArea<'_, Tab> {
 
         let style = fade_style.unwrap_or_else(|| self.style.as_ref().unwrap());
         let (tabbar_outer_rect, tabbar_response) = ui.allocate_exact_size(
-            vec2(ui.available_width(), style.tab_bar.height),
+            vec2(ui.available_width(), style.tab_bar_height()),
             Sense::hover(),
         );
-        ui.painter().rect_filled(
-            tabbar_outer_rect,
-            style.tab_bar.corner_radius,
-            style.tab_bar.bg_fill,
-        );
+        // Registered before the tabs and buttons so that it only receives presses on the empty part of the tab bar.
+        let drag_window = self.drag_window_on_empty_tab_bar && surface_index.is_main();
+        let empty_response = (self.touch_gestures || drag_window).then(|| {
+            let id = self
+                .id
+                .with((surface_index, "surface"))
+                .with((node_index, "node"))
+                .with("tab_bar_empty");
+            let mut sense = Sense::hover();
+            if self.touch_gestures {
+                sense |= Sense::click();
+            }
+            if drag_window {
+                sense |= Sense::drag();
+            }
+            ui.interact(tabbar_outer_rect, id, sense)
+        });
+        let clear_background = self.dock_state[surface_index][node_index]
+            .get_leaf()
+            .and_then(|leaf| leaf.tabs.get(leaf.active.0))
+            .is_none_or(|tab| tab_viewer.clear_tab_bar_background(tab));
+        if clear_background {
+            match &style.tab_bar.bg_painter {
+                Some(bg_painter) => bg_painter.paint(ui.painter(), tabbar_outer_rect),
+                None => {
+                    ui.painter().rect_filled(
+                        tabbar_outer_rect,
+                        style.tab_bar.corner_radius,
+                        style.tab_bar.bg_fill,
+                    );
+                }
+            }
+        }
 
         let tabbar_outer_rect = tabbar_outer_rect - style.tab_bar.inner_margin;
 
-        let mut available_width = tabbar_outer_rect.width();
-        let scroll_bar_width = available_width;
-        if available_width == 0.0 {
+        let scroll_bar_width = tabbar_outer_rect.width();
+        if scroll_bar_width == 0.0 {
             return tabbar_outer_rect;
         }
 
-        // Reserve space for the buttons at the ends of the tab bar.
-
-        if self.show_add_buttons {
-            available_width -= Style::TAB_ADD_BUTTON_SIZE;
-        }
-
-        if self.show_leaf_close_all_buttons {
-            available_width -= Style::TAB_CLOSE_ALL_BUTTON_SIZE;
-        }
-
-        if self.show_leaf_collapse_buttons {
-            available_width -= Style::TAB_COLLAPSE_BUTTON_SIZE;
-        }
+        // Reserve space for the buttons and widgets at the ends of the tab bar.
+        let ends = self.tab_bar_ends(ui, (surface_index, node_index), tab_viewer, style);
+        let available_width = tabbar_outer_rect.width() - ends.leading() - ends.trailing();
+        let leading_width = ends.leading();
 
         let actual_width = {
             let leaf = self.dock_state[surface_index][node_index]
@@ -131,16 +277,8 @@ impl<Tab> DockArea<'_, Tab> {
                 .expect("This node must be a leaf");
 
             let tabbar_inner_rect = Rect::from_min_size(
-                (tabbar_outer_rect.min - pos2(-leaf.scroll, 0.0)
-                    + vec2(
-                        if self.show_leaf_collapse_buttons {
-                            Style::TAB_COLLAPSE_BUTTON_SIZE
-                        } else {
-                            0.0
-                        },
-                        0.0,
-                    ))
-                .to_pos2(),
+                (tabbar_outer_rect.min - pos2(-leaf.scroll, 0.0) + vec2(leading_width, 0.0))
+                    .to_pos2(),
                 vec2(tabbar_outer_rect.width(), tabbar_outer_rect.height()),
             );
 
@@ -153,96 +291,63 @@ impl<Tab> DockArea<'_, Tab> {
 
             let mut clip_rect = tabbar_outer_rect;
             clip_rect.set_width(available_width);
-            if self.show_leaf_collapse_buttons {
-                clip_rect = clip_rect.translate(vec2(Style::TAB_COLLAPSE_BUTTON_SIZE, 0.0));
-            }
+            clip_rect = clip_rect.translate(vec2(leading_width, 0.0));
             tabs_ui.set_clip_rect(clip_rect);
 
-            // Desired size for tabs in "expanded" mode.
-            let prefered_width = style
-                .tab_bar
-                .fill_tab_bar
-                .then_some(available_width / (leaf.tabs.len() as f32));
+            // Desired size for tabs in "expanded" mode, or the exact size of each when they're equal.
+            let tabs_len = leaf.tabs.len() as f32;
+            let prefered_width = if style.tab_bar.equal_tab_width {
+                let spacing = style.tab.spacing * (tabs_len - 1.0).at_least(0.0);
+                Some(((available_width - spacing) / tabs_len).at_least(0.0))
+            } else {
+                style
+                    .tab_bar
+                    .fill_tab_bar
+                    .then_some(available_width / tabs_len)
+            };
+            let shrunk_width = (style.tab_bar.overflow_mode == OverflowMode::Shrink).then(|| {
+                let spacing = style.tab.spacing * (tabs_len - 1.0).at_least(0.0);
+                ((available_width - spacing) / tabs_len).at_least(0.0)
+            });
 
-            self.tabs(
+            let tab_rects = self.tabs(
                 tabs_ui,
                 state,
                 (surface_index, node_index),
                 tab_viewer,
                 tabbar_outer_rect,
-                prefered_width,
+                (prefered_width, shrunk_width),
                 fade_style,
             );
 
-            // Draw hline from tab end to edge of tab bar.
+            // Draw hline from tab end to edge of tab bar, on the side of the content.
             let px = ui.ctx().pixels_per_point().recip();
             let style = fade_style.unwrap_or_else(|| self.style.as_ref().unwrap());
+            let at_bottom =
+                self.tab_bar_position((surface_index, node_index), style) == TabBarPosition::Bottom;
 
             ui.painter().hline(
                 tabs_ui.min_rect().right().min(clip_rect.right())..=tabbar_outer_rect.right(),
-                tabbar_outer_rect.bottom() - px,
+                if at_bottom {
+                    tabbar_outer_rect.top()
+                } else {
+                    tabbar_outer_rect.bottom() - px
+                },
                 (px, style.tab_bar.hline_color),
             );
 
-            // Add button at the ends of the tab bar.
-            if self.show_add_buttons {
-                let offset = match style.buttons.add_tab_align {
-                    TabAddAlign::Left => {
-                        (clip_rect.width() - tabs_ui.min_rect().width()).at_least(0.0)
-                    }
-                    TabAddAlign::Right => 0.0,
-                } + if self.show_leaf_close_all_buttons {
-                    Style::TAB_CLOSE_ALL_BUTTON_SIZE
-                } else {
-                    0.0
-                };
-                self.tab_plus(
-                    ui,
-                    surface_index,
-                    node_index,
-                    tab_viewer,
-                    tabbar_outer_rect,
-                    offset,
-                    fade_style,
-                );
-            }
-
-            if self.show_leaf_close_all_buttons {
-                // Current leaf contains non-closable tabs.
-                let disabled = self.dock_state[surface_index][node_index]
-                    .get_leaf_mut()
-                    .map(|leaf| !leaf.tabs.iter_mut().all(|tab| tab_viewer.is_closeable(tab)))
-                    .expect("This node must be a leaf");
-
-                // Current window contains non-closable tabs.
-                let close_window_disabled = disabled
-                    || !self.dock_state[surface_index].iter_mut().all(|node| {
-                        node.get_leaf_mut().is_none_or(|leaf| {
-                            leaf.tabs.iter_mut().all(|tab| tab_viewer.is_closeable(tab))
-                        })
-                    });
-
-                self.tab_close_all(
-                    ui,
-                    surface_index,
-                    node_index,
-                    tabbar_outer_rect,
-                    fade_style,
-                    disabled,
-                    close_window_disabled,
-                )
-            }
-
-            if self.show_leaf_collapse_buttons {
-                self.tab_collapse(
-                    ui,
-                    surface_index,
-                    node_index,
-                    tabbar_outer_rect,
-                    fade_style,
-                    collapsed,
-                )
-            }
+            self.tab_bar_ends_ui(
+                ui,
+                state,
+                (surface_index, node_index),
+                tab_viewer,
+                fade_style,
+                &ends,
+                (tabbar_outer_rect, clip_rect, tabs_ui.min_rect().width()),
+                &tab_rects,
+                false,
+                collapsed,
+            );
 
             tabs_ui.min_rect().width()
         };
@@ -258,9 +363,72 @@ impl<Tab> DockArea<'_, Tab> {
             fade_style,
         );
 
+        if let Some(response) = empty_response {
+            if self.touch_gestures && self.double_tapped(ui, state, &response) {
+                self.dock_state
+                    .toggle_maximized_leaf((surface_index, node_index));
+            }
+            if drag_window && response.drag_started_by(PointerButton::Primary) {
+                ui.ctx().send_viewport_cmd(ViewportCommand::StartDrag);
+            }
+        }
+
         tabbar_outer_rect
     }
 
+    /// Shows [`TabViewer::tab_bar_leading_ui`] from the left of `rect`, or [`TabViewer::tab_bar_trailing_ui`] from
+    /// its right, only visible within the `reserved_length`. In a `vertical` tab bar, they're shown from the top and
+    /// the bottom of `rect` instead.
+    ///
+    /// Returns the length taken up by the widgets along the tab bar, to reserve in the next frame.
+    #[allow(clippy::too_many_arguments)]
+    pub(super) fn tab_bar_custom_ui(
+        &mut self,
+        ui: &mut Ui,
+        (surface_index, node_index): (SurfaceIndex, NodeIndex),
+        tab_viewer: &mut impl TabViewer<Tab = Tab>,
+        rect: Rect,
+        reserved_length: f32,
+        trailing: bool,
+        vertical: bool,
+    ) -> f32 {
+        let (layout, visible_rect) = match (vertical, trailing) {
+            (false, false) => (
+                Layout::left_to_right(Align::Center),
+                rect.with_max_x(rect.left() + reserved_length),
+            ),
+            (false, true) => (
+                Layout::right_to_left(Align::Center),
+                rect.with_min_x(rect.right() - reserved_length),
+            ),
+            (true, false) => (
+                Layout::top_down(Align::Center),
+                rect.with_max_y(rect.top() + reserved_length),
+            ),
+            (true, true) => (
+                Layout::bottom_up(Align::Center),
+                rect.with_min_y(rect.bottom() - reserved_length),
+            ),
+        };
+        let custom_ui = &mut ui.new_child(
+            UiBuilder::new()
+                .max_rect(rect)
+                .layout(layout)
+                .id_salt((node_index, if trailing { "trailing" } else { "leading" })),
+        );
+        custom_ui.set_clip_rect(visible_rect.intersect(ui.clip_rect()));
+        if trailing {
+            tab_viewer.tab_bar_trailing_ui(custom_ui, surface_index, node_index);
+        } else {
+            tab_viewer.tab_bar_leading_ui(custom_ui, surface_index, node_index);
+        }
+        if vertical {
+            custom_ui.min_rect().height()
+        } else {
+            custom_ui.min_rect().width()
+        }
+    }
+
     #[allow(clippy::too_many_arguments)]
     fn tabs(
         &mut self,
@@ -269,12 +437,16 @@ impl<Tab> DockArea<'_, Tab> {
         (surface_index, node_index): (SurfaceIndex, NodeIndex),
         tab_viewer: &mut impl TabViewer<Tab = Tab>,
         tabbar_outer_rect: Rect,
-        preferred_width: Option<f32>,
+        (preferred_width, shrunk_width): (Option<f32>, Option<f32>),
         fade: Option<&Style>,
-    ) {
+    ) -> Vec<Rect> {
         assert!(self.dock_state[surface_index][node_index].is_leaf());
 
         let focused = self.dock_state.focused_leaf();
+        let at_bottom = self.tab_bar_position(
+            (surface_index, node_index),
+            fade.unwrap_or_else(|| self.style.as_ref().unwrap()),
+        ) == TabBarPosition::Bottom;
         let tabs_len = {
             let tabs = self.dock_state[surface_index][node_index]
                 .tabs()
@@ -282,103 +454,194 @@ impl<Tab> DockArea<'_, Tab> {
             tabs.len()
         };
 
+        let leaf_id = self
+            .id
+            .with((surface_index, "surface"))
+            .with((node_index, "node"));
+        let dragged = (0..tabs_len).map(TabIndex).find(|&tab_index| {
+            self.is_tab_dragged(tabs_ui, leaf_id, (surface_index, node_index, tab_index))
+        });
+
+        // While a tab is dragged along the tab bar, the other tabs make room for it where it would be dropped.
+        let slide_id = leaf_id.with("tab_slide");
+        let slide = dragged
+            .filter(|_| self.style.as_ref().unwrap().animations_enabled)
+            .and_then(|dragged| {
+                tabs_ui
+                    .data(|data| data.get_temp::<TabSlide>(slide_id))
+                    .filter(|slide| slide.dragged == dragged && slide.gap < tabs_len)
+            });
+        let tabs_origin = tabs_ui.cursor().min.x;
+        let mut gaps_width = 0.0;
+        let mut spacings = Vec::with_capacity(tabs_len);
+
+        let mut tab_rects = Vec::with_capacity(tabs_len);
         for tab_index in 0..tabs_len {
-            let id = self
-                .id
-                .with((surface_index, "surface"))
-                .with((node_index, "node"))
-                .with((tab_index, "tab"));
+            let id = leaf_id.with((tab_index, "tab"));
             let tab_index = TabIndex(tab_index);
-            let is_being_dragged = tabs_ui.ctx().is_being_dragged(id)
-                && tabs_ui.input(|i| i.pointer.is_decidedly_dragging())
-                && self.draggable_tabs;
-
-            if is_being_dragged {
-                tabs_ui.output_mut(|o| o.cursor_icon = CursorIcon::Grabbing);
+            // Pinned tabs can't leave their leaf.
+            let pinned = self.dock_state[surface_index][node_index]
+                .get_leaf()
+                .expect("This node must be a leaf")
+                .is_pinned(tab_index);
+            let is_being_dragged = dragged == Some(tab_index);
+
+            if let Some(slide) = slide.filter(|slide| slide.dragged != tab_index) {
+                let gap = tab_index.0 - usize::from(tab_index > slide.dragged);
+                gaps_width += self.tab_slide_gap(tabs_ui, leaf_id, slide, gap);
             }
 
-            let (is_active, label, tab_style, closeable) = {
+            let (is_active, icon, tab_style, closeable, close_button_visibility) = {
                 let leaf = self.dock_state[surface_index][node_index]
                     .get_leaf_mut()
                     .expect("This node must be a leaf");
                 let style = fade.unwrap_or_else(|| self.style.as_ref().unwrap());
-                let tab_style = tab_viewer.tab_style_override(&leaf.tabs[tab_index.0], &style.tab);
+                let mut tab_style = tab_viewer
+                    .tab_style_override(&leaf.tabs[tab_index.0], &style.tab)
+                    .unwrap_or_else(|| style.tab.clone());
+                if let Some(color) = tab_viewer.tab_color(&leaf.tabs[tab_index.0]) {
+                    tab_style.tint(color);
+                }
+                if at_bottom {
+                    flip_corners(&mut tab_style);
+                }
+                if let Some(width) = shrunk_width {
+                    tab_style.maximum_width =
+                        Some(tab_style.maximum_width.map_or(width, |max| max.min(width)));
+                }
+                let icon = if pinned && tab_style.pinned_icon_only {
+                    tab_viewer.tab_icon(&mut leaf.tabs[tab_index.0])
+                } else {
+                    None
+                };
                 (
                     leaf.active == tab_index || is_being_dragged,
-                    tab_viewer.title(&mut leaf.tabs[tab_index.0]),
-                    tab_style.unwrap_or(style.tab.clone()),
+                    icon,
+                    tab_style,
                     tab_viewer.is_closeable(&leaf.tabs[tab_index.0]),
+                    tab_viewer
+                        .close_button_visibility(&leaf.tabs[tab_index.0])
+                        .unwrap_or(self.close_button_visibility),
                 )
             };
 
+            if is_being_dragged {
+                tabs_ui.output_mut(|o| o.cursor_icon = tab_style.cursor_dragged);
+            }
+            spacings.push(tab_style.spacing);
+
             let show_close_button = self.show_close_buttons && closeable;
+            let close_button = (show_close_button && !(pinned && tab_style.pinned_icon_only))
+                .then_some(close_button_visibility);
+
+            // Selected tabs look like active ones.
+            let is_highlighted = is_active
+                || (self.multi_tab_selection
+                    && state.is_selected((surface_index, node_index, tab_index)));
 
             let (response, title_id) = if is_being_dragged {
                 let layer_id = LayerId::new(Order::Tooltip, id);
-                let response = tabs_ui
-                    .scope_builder(UiBuilder::new().layer_id(layer_id), |ui| {
-                        self.tab_title(
-                            ui,
-                            &tab_style,
-                            id,
-                            label,
-                            is_active && Some((surface_index, node_index)) == focused,
-                            is_active,
-                            is_being_dragged,
-                            preferred_width,
-                            show_close_button,
-                            fade,
-                        )
-                    })
-                    .response;
-                let title_id = response.id;
-
-                let response =
-                    tabs_ui.interact(response.rect, id.with("dragged"), Sense::click_and_drag());
-
-                if let Some(pointer_pos) = tabs_ui.ctx().pointer_interact_pos() {
-                    let start = *state.drag_start.get_or_insert(pointer_pos);
-                    let delta = pointer_pos - start;
-                    if delta.x.abs() > 30.0 || delta.y.abs() > 6.0 {
-                        tabs_ui
-                            .ctx()
-                            .transform_layer_shapes(layer_id, TSTransform::new(delta, 1.0));
-
-                        tabs_ui.memory_mut(|mem| {
-                            mem.data.insert_temp(
-                                self.id.with("drag_data"),
-                                Some(DragData {
-                                    src: TreeComponent::Tab(surface_index, node_index, tab_index),
-                                    rect: self.dock_state[surface_index][node_index]
-                                        .rect()
-                                        .unwrap(),
-                                }),
-                            );
-                        });
+                let builder = UiBuilder::new().layer_id(layer_id);
+                let add_title = |ui: &mut Ui| {
+                    let tab = &mut self.dock_state[surface_index][node_index]
+                        .tabs_mut()
+                        .expect("This node must be a leaf")[tab_index.0];
+                    Self::tab_title(
+                        ui,
+                        fade.unwrap_or_else(|| self.style.as_ref().unwrap()),
+                        &tab_style,
+                        id,
+                        icon,
+                        is_active && Some((surface_index, node_index)) == focused,
+                        is_highlighted,
+                        is_being_dragged,
+                        preferred_width,
+                        close_button,
+                        self.reserve_close_button_space,
+                        self.draggable_tabs,
+                        at_bottom,
+                        (tab_viewer, tab),
+                    )
+                };
+                let (rect, title_id) = match slide {
+                    None => {
+                        let response = tabs_ui.scope_builder(builder, add_title).response;
+                        (response.rect, response.id)
                     }
-                }
+                    // The dragged tab is taken out from between the others, starting out where it was.
+                    Some(_) => {
+                        let mut rect = tabs_ui.available_rect_before_wrap();
+                        rect.min.x -= gaps_width;
+                        let mut title_ui = tabs_ui.new_child(builder.max_rect(rect));
+                        add_title(&mut title_ui);
+                        (title_ui.min_rect(), title_ui.unique_id())
+                    }
+                };
+
+                let response = tabs_ui.interact(rect, id.with("dragged"), Sense::click_and_drag());
+                self.drag_from_tab_bar(
+                    tabs_ui,
+                    state,
+                    TreeComponent::Tab(surface_index, node_index, tab_index),
+                    Some(layer_id),
+                    false,
+                );
 
+                (response, title_id)
+            } else if state
+                .renaming
+                .as_ref()
+                .is_some_and(|rename| rename.tab == (surface_index, node_index, tab_index))
+            {
+                if tab_index.0 != 0 {
+                    tabs_ui.allocate_space(vec2(tab_style.spacing, 0.0));
+                }
+                let response = self.tab_rename(
+                    tabs_ui,
+                    state,
+                    id,
+                    (surface_index, node_index, tab_index),
+                    tab_viewer,
+                );
+                let title_id = response.id;
                 (response, title_id)
             } else {
                 if tab_index.0 != 0 {
                     tabs_ui.allocate_space(vec2(tab_style.spacing, 0.0));
                 }
-                let (mut response, close_response) = self.tab_title(
+                let tab = &mut self.dock_state[surface_index][node_index]
+                    .tabs_mut()
+                    .expect("This node must be a leaf")[tab_index.0];
+                let (mut response, close_response) = Self::tab_title(
                     tabs_ui,
+                    fade.unwrap_or_else(|| self.style.as_ref().unwrap()),
                     &tab_style,
                     id,
-                    label,
+                    icon,
                     is_active && Some((surface_index, node_index)) == focused,
-                    is_active,
+                    is_highlighted,
                     is_being_dragged,
                     preferred_width,
-                    show_close_button,
-                    fade,
+                    close_button,
+                    self.reserve_close_button_space,
+                    self.draggable_tabs,
+                    at_bottom,
+                    (tab_viewer, tab),
                 );
                 let title_id = response.id;
                 let close_clicked = close_response.is_some_and(|res| res.clicked());
-                let is_lonely_tab = self.dock_state[surface_index].num_tabs() == 1;
 
+                if self.rename_tabs_on_double_click && response.double_clicked() {
+                    let tab = &mut self.dock_state[surface_index][node_index]
+                        .tabs_mut()
+                        .expect("This node must be a leaf")[tab_index.0];
+                    state.renaming = Some(TabRename {
+                        tab: (surface_index, node_index, tab_index),
+                        text: tab_viewer.title(tab).text().to_owned(),
+                        width: response.rect.width(),
+                        request_focus: true,
+                    });
+                }
                 if self.show_tab_name_on_hover {
                     let tabs = self.dock_state[surface_index][node_index]
                         .tabs_mut()
@@ -390,42 +653,12 @@ impl<Tab> DockArea<'_, Tab> {
                 }
 
                 if self.tab_context_menus {
-                    let eject_button =
-                        Button::new(&self.dock_state.translations.tab_context_menu.eject_button);
-                    let close_button =
-                        Button::new(&self.dock_state.translations.tab_context_menu.close_button);
-
-                    response.context_menu(|ui| {
-                        let leaf = self.dock_state[surface_index][node_index]
-                            .get_leaf_mut()
-                            .expect("This node must be a leaf");
-                        let tab = &mut leaf.tabs[tab_index.0];
-
-                        tab_viewer.context_menu(ui, tab, surface_index, node_index);
-                        if (surface_index.is_main() || !is_lonely_tab)
-                            && tab_viewer.allowed_in_windows(tab)
-                            && ui.add(eject_button).clicked()
-                        {
-                            self.to_detach.push((surface_index, node_index, tab_index));
-                            ui.close();
-                        }
-                        if show_close_button && ui.add(close_button).clicked() {
-                            match tab_viewer.on_close(tab) {
-                                OnCloseResponse::Close => self.to_remove.push(TabRemoval::Tab(
-                                    surface_index,
-                                    node_index,
-                                    tab_index,
-                                    ForcedRemoval(false),
-                                )),
-                                OnCloseResponse::Focus => {
-                                    leaf.active = tab_index;
-                                    self.new_focused = Some((surface_index, node_index));
-                                }
-                                OnCloseResponse::Ignore => (),
-                            }
-                            ui.close();
-                        }
-                    });
+                    self.tab_context_menu(
+                        &response,
+                        (surface_index, node_index, tab_index),
+                        tab_viewer,
+                        show_close_button,
+                    );
                 }
 
                 if close_clicked {
@@ -437,23 +670,14 @@ impl<Tab> DockArea<'_, Tab> {
                     ));
                 }
 
-                if let Some(pos) = state.last_hover_pos {
-                    // Use response.rect.contains instead of
-                    // response.hovered as the dragged tab covers
-                    // the underlying tab
-                    if state.drag_start.is_some() && response.rect.contains(pos) {
-                        self.tab_hover_rect = Some((response.rect, tab_index));
-                    }
-                }
-
                 (response, title_id)
             };
+            tab_rects.push(response.rect);
 
             // Paint hline below each tab unless its active (or option says otherwise).
-            let leaf = self.dock_state[surface_index][node_index]
-                .get_leaf_mut()
-                .unwrap();
-            let tab = &mut leaf.tabs[tab_index.0];
+            let tab = &self.dock_state[surface_index][node_index]
+                .tabs()
+                .expect("This node must be a leaf")[tab_index.0];
             let style = fade.unwrap_or_else(|| self.style.as_ref().unwrap());
             let tab_style = tab_viewer.tab_style_override(tab, &style.tab);
             let tab_style = tab_style.as_ref().unwrap_or(&style.tab);
@@ -462,63 +686,643 @@ impl<Tab> DockArea<'_, Tab> {
                 let px = tabs_ui.ctx().pixels_per_point().recip();
                 tabs_ui.painter().hline(
                     response.rect.x_range(),
-                    tabbar_outer_rect.bottom() - px,
+                    if at_bottom {
+                        tabbar_outer_rect.top()
+                    } else {
+                        tabbar_outer_rect.bottom() - px
+                    },
                     (px, style.tab_bar.hline_color),
                 );
             }
 
-            if response.clicked()
-                || (tabs_ui.memory(|m| m.has_focus(title_id))
-                    && tabs_ui.input(|i| i.key_pressed(Key::Enter) || i.key_pressed(Key::Space)))
-            {
-                leaf.active = tab_index;
-                self.new_focused = Some((surface_index, node_index));
-            }
+            self.tab_button_interaction(
+                tabs_ui,
+                state,
+                (surface_index, node_index, tab_index),
+                (id, title_id),
+                &response,
+                show_close_button,
+                false,
+                tab_viewer,
+            );
+        }
+
+        if let Some(slide) = slide {
+            self.tab_slide_gap(tabs_ui, leaf_id, slide, tabs_len - 1);
+        }
+
+        // Use rect.contains instead of response.hovered as the dragged tab covers the underlying tabs.
+        let visible_rect = tabs_ui.clip_rect().intersect(tabbar_outer_rect);
+        let hover_pos = state
+            .last_hover_pos
+            .filter(|&pos| state.drag_start.is_some() && visible_rect.contains(pos));
+        if let Some(pos) = hover_pos {
+            self.tab_hover_rect = tab_insertion_caret(&tab_rects, pos.x, visible_rect);
+        }
+
+        match dragged.filter(|_| self.style.as_ref().unwrap().animations_enabled) {
+            Some(dragged) => {
+                let pinned_count = self.dock_state[surface_index][node_index]
+                    .get_leaf()
+                    .expect("This node must be a leaf")
+                    .pinned_count();
+                let (gap, rect) = match hover_pos {
+                    Some(pos) => tab_slide_target(
+                        (&tab_rects, &spacings),
+                        dragged,
+                        pinned_count,
+                        tabs_origin,
+                        pos.x,
+                    ),
+                    None => (dragged.0, Rect::NOTHING),
+                };
+                // The index a tab is inserted at counts the dragged tab, which is taken out first.
+                if slide.is_some() && hover_pos.is_some() {
+                    let index = gap + usize::from(gap >= dragged.0);
+                    let caret = Rect::from_x_y_ranges(rect.x_range(), visible_rect.y_range());
+                    self.tab_hover_rect = Some((caret.intersect(visible_rect), TabIndex(index)));
+                }
+                let slide = TabSlide {
+                    dragged,
+                    width: tab_rects[dragged.0].width(),
+                    gap,
+                };
+                tabs_ui.data_mut(|data| data.insert_temp(slide_id, slide));
+            }
+            None => tabs_ui.data_mut(|data| data.remove::<TabSlide>(slide_id)),
+        }
+
+        tab_rects
+    }
+
+    /// Returns whether a tab is being dragged. Pinned tabs can't be dragged out of their leaf.
+    fn is_tab_dragged(
+        &self,
+        ui: &Ui,
+        leaf_id: Id,
+        (surface_index, node_index, tab_index): (SurfaceIndex, NodeIndex, TabIndex),
+    ) -> bool {
+        let pinned = self.dock_state[surface_index][node_index]
+            .get_leaf()
+            .expect("This node must be a leaf")
+            .is_pinned(tab_index);
+        ui.ctx()
+            .is_being_dragged(leaf_id.with((tab_index.0, "tab")))
+            && ui.input(|i| i.pointer.is_decidedly_dragging())
+            && self.draggable_tabs
+            && !pinned
+    }
+
+    /// Adds the `gap`-th gap between the tabs other than the dragged one, which opens up where the dragged tab would
+    /// be dropped, returning how wide it is. Gaps start out as the tabs were laid out before the drag, with only the
+    /// one the dragged tab was taken out of open.
+    fn tab_slide_gap(&self, ui: &mut Ui, leaf_id: Id, slide: TabSlide, gap: usize) -> f32 {
+        let style = self.style.as_ref().unwrap();
+        let width_if = |open: bool| if open { slide.width } else { 0.0 };
+        let (target, start) = (width_if(gap == slide.gap), width_if(gap == slide.dragged.0));
+        let id = leaf_id.with(("tab_gap", gap));
+        let duration = style.animation.tab_reorder_duration;
+        let width = animation::follow(ui.ctx(), id, target, start, duration, style);
+        ui.add_space(width);
+        width
+    }
+
+    /// Shows the [`TabViewer::leaf_toolbar`] of the active tab, reserving the height it takes up.
+    fn leaf_toolbar(
+        &mut self,
+        ui: &mut Ui,
+        (surface_index, node_index): (SurfaceIndex, NodeIndex),
+        tab_viewer: &mut impl TabViewer<Tab = Tab>,
+        spacing: Vec2,
+        fade: Option<(&Style, f32)>,
+    ) {
+        let Some(leaf) = self.dock_state[surface_index][node_index].get_leaf_mut() else {
+            return;
+        };
+        let Some(tab) = leaf.tabs.get_mut(leaf.active.0) else {
+            return;
+        };
+        let (style, fade_factor) = fade.unwrap_or_else(|| (self.style.as_ref().unwrap(), 1.0));
+
+        // The background is only known to be needed once the toolbar is shown.
+        let background = ui.painter().add(Shape::Noop);
+        // Like `Ui::horizontal`, start with a single row which grows with what's added to it.
+        let row = Rect::from_min_size(
+            ui.cursor().min,
+            vec2(ui.available_width(), ui.spacing().interact_size.y),
+        );
+        // Like the content, the toolbar's id follows the tab rather than the leaf it's in.
+        let mut builder = UiBuilder::new()
+            .max_rect(row)
+            .layout(Layout::left_to_right(Align::Center))
+            .layer_id(ui.layer_id());
+        if !ui.is_enabled() {
+            builder = builder.disabled();
+        }
+        let id = self.id.with(tab_viewer.id(tab)).with("toolbar");
+        let toolbar_ui = &mut Ui::new(ui.ctx().clone(), id, builder);
+        toolbar_ui.set_clip_rect(ui.clip_rect());
+        toolbar_ui.spacing_mut().item_spacing = spacing;
+        if fade_factor != 1.0 {
+            fade_visuals(toolbar_ui.visuals_mut(), fade_factor);
+        }
+        tab_viewer.leaf_toolbar(toolbar_ui, tab, surface_index, node_index);
+
+        let toolbar_rect = toolbar_ui.min_rect();
+        if toolbar_rect.height() > 0.0 {
+            let toolbar_rect =
+                Rect::from_x_y_ranges(ui.max_rect().x_range(), toolbar_rect.y_range());
+            ui.painter().set(
+                background,
+                Shape::rect_filled(toolbar_rect, 0.0, style.tab.tab_body.bg_fill),
+            );
+            ui.advance_cursor_after_rect(toolbar_rect);
+        }
+    }
+
+    /// Draws the text field replacing the title of a tab that is being renamed.
+    fn tab_rename(
+        &mut self,
+        ui: &mut Ui,
+        state: &mut State,
+        id: Id,
+        (surface_index, node_index, tab_index): (SurfaceIndex, NodeIndex, TabIndex),
+        tab_viewer: &mut impl TabViewer<Tab = Tab>,
+    ) -> Response {
+        let rename = state
+            .renaming
+            .as_mut()
+            .expect("A tab must be being renamed here");
+        let response = ui.add_sized(
+            vec2(rename.width, ui.available_height()),
+            TextEdit::singleline(&mut rename.text).id(id.with("rename")),
+        );
+
+        if std::mem::take(&mut rename.request_focus) {
+            response.request_focus();
+        }
+
+        // The edit is committed when the field loses focus, unless it was cancelled with escape.
+        if response.lost_focus() {
+            let rename = state.renaming.take().unwrap();
+            if !ui.input(|i| i.key_pressed(Key::Escape)) {
+                let tab = &mut self.dock_state[surface_index][node_index]
+                    .tabs_mut()
+                    .expect("This node must be a leaf")[tab_index.0];
+                tab_viewer.on_rename(tab, rename.text);
+            }
+        }
+
+        response
+    }
+
+    /// Draws the tab add button in `rect`.
+    #[allow(clippy::too_many_arguments)]
+    pub(super) fn tab_plus(
+        &mut self,
+        ui: &mut Ui,
+        surface_index: SurfaceIndex,
+        node_index: NodeIndex,
+        tab_viewer: &mut impl TabViewer<Tab = Tab>,
+        rect: Rect,
+        vertical: bool,
+        fade_style: Option<&Style>,
+    ) {
+        let ui = &mut ui.new_child(
+            UiBuilder::new()
+                .max_rect(rect)
+                .layout(Layout::left_to_right(Align::Center))
+                .id_salt((node_index, "tab_add")),
+        );
+
+        let (rect, mut response) = ui.allocate_exact_size(ui.available_size(), Sense::click());
+
+        response = response.on_hover_cursor(CursorIcon::PointingHand);
+
+        let style = fade_style.unwrap_or_else(|| self.style.as_ref().unwrap());
+        let color = if response.hovered() || response.has_focus() {
+            ui.painter()
+                .rect_filled(rect, CornerRadius::ZERO, style.buttons.add_tab_bg_fill);
+            style.buttons.add_tab_active_color
+        } else {
+            style.buttons.add_tab_color
+        };
+
+        let mut plus_rect = rect;
+
+        rect_set_size_centered(&mut plus_rect, Vec2::splat(Style::TAB_ADD_PLUS_SIZE));
+
+        ui.painter().line_segment(
+            [plus_rect.center_top(), plus_rect.center_bottom()],
+            Stroke::new(1.0, color),
+        );
+        ui.painter().line_segment(
+            [plus_rect.right_center(), plus_rect.left_center()],
+            Stroke::new(1.0, color),
+        );
+
+        paint_button_border(
+            ui,
+            rect,
+            vertical,
+            false,
+            style.buttons.add_tab_border_color,
+        );
+
+        let popup_id = ui.id().with("tab_add_popup");
+        if self.show_add_popup {
+            let mut popup = Popup::from_toggle_button_response(&response)
+                .id(popup_id)
+                .close_behavior(self.add_popup_close_behavior);
+            if let Some(width) = self.add_popup_width {
+                popup = popup.width(width);
+            }
+            popup.show(|ui| match self.add_popup_width {
+                Some(width) => {
+                    ui.set_width(width);
+                    ScrollArea::vertical().show(ui, |ui| {
+                        tab_viewer.add_popup(ui, surface_index, node_index);
+                    });
+                }
+                None => tab_viewer.add_popup(ui, surface_index, node_index),
+            });
+        }
+
+        if response.clicked() {
+            if let Some(tab) = tab_viewer.on_add_tab(surface_index, node_index) {
+                let leaf = self.dock_state[surface_index][node_index]
+                    .get_leaf_mut()
+                    .expect("This node must be a leaf");
+                leaf.append_tab(tab);
+                self.new_focused = Some((surface_index, node_index));
+            }
+        }
+    }
+
+    /// Shows the context menu of a tab when its button is clicked with [`DockArea::context_menu_button`].
+    pub(super) fn tab_context_menu(
+        &mut self,
+        response: &Response,
+        (surface_index, node_index, tab_index): (SurfaceIndex, NodeIndex, TabIndex),
+        tab_viewer: &mut impl TabViewer<Tab = Tab>,
+        show_close_button: bool,
+    ) {
+        let pinned = self.dock_state[surface_index][node_index]
+            .get_leaf()
+            .expect("This node must be a leaf")
+            .is_pinned(tab_index);
+        let is_lonely_tab = self.dock_state[surface_index].num_tabs() == 1;
+        let eject_button = Button::new(&self.dock_state.translations.tab_context_menu.eject_button);
+        let close_button = Button::new(&self.dock_state.translations.tab_context_menu.close_button);
+        let pin_button = Button::new(if pinned {
+            &self.dock_state.translations.tab_context_menu.unpin_button
+        } else {
+            &self.dock_state.translations.tab_context_menu.pin_button
+        });
+
+        let open = if response.clicked_by(self.context_menu_button) {
+            Some(SetOpenCommand::Bool(true))
+        } else if response.clicked() {
+            Some(SetOpenCommand::Bool(false))
+        } else {
+            None
+        };
+        Popup::menu(response)
+            .open_memory(open)
+            .at_pointer_fixed()
+            .show(|ui| {
+                if self.standard_context_menu_entries(
+                    ui,
+                    (surface_index, node_index, tab_index),
+                    tab_viewer,
+                    is_lonely_tab,
+                ) {
+                    ui.separator();
+                }
+
+                let leaf = self.dock_state[surface_index][node_index]
+                    .get_leaf_mut()
+                    .expect("This node must be a leaf");
+                let tab = &mut leaf.tabs[tab_index.0];
+
+                tab_viewer.tab_context_menu(ui, tab, surface_index, node_index, tab_index);
+                if self.interactive
+                    && !self.standard_context_menu.contains(StandardActions::FLOAT)
+                    && (surface_index.is_main() || !is_lonely_tab)
+                    && tab_viewer.allowed_in_windows(tab)
+                    && ui.add(eject_button).clicked()
+                {
+                    self.to_detach.push((surface_index, node_index, tab_index));
+                    ui.close();
+                }
+                if self.interactive && tab_viewer.is_pinnable(tab) && ui.add(pin_button).clicked() {
+                    self.to_pin.push((surface_index, node_index, tab_index));
+                    ui.close();
+                }
+                if show_close_button && ui.add(close_button).clicked() {
+                    match tab_viewer.on_close(tab) {
+                        OnCloseResponse::Close => self.to_remove.push(TabRemoval::Tab(
+                            surface_index,
+                            node_index,
+                            tab_index,
+                            ForcedRemoval(false),
+                        )),
+                        OnCloseResponse::Focus => {
+                            leaf.active = tab_index;
+                            self.new_focused = Some((surface_index, node_index));
+                        }
+                        OnCloseResponse::Ignore => (),
+                    }
+                    ui.close();
+                }
+            });
+    }
+
+    /// Shows the [`StandardActions`] enabled with [`DockArea::standard_context_menu`] in the context menu of a tab,
+    /// returning whether any entry was shown.
+    fn standard_context_menu_entries(
+        &mut self,
+        ui: &mut Ui,
+        (surface_index, node_index, tab_index): (SurfaceIndex, NodeIndex, TabIndex),
+        tab_viewer: &mut impl TabViewer<Tab = Tab>,
+        is_lonely_tab: bool,
+    ) -> bool {
+        let actions = self.standard_context_menu;
+        if !self.interactive || actions.is_empty() {
+            return false;
+        }
+        let translations = &self.dock_state.translations.tab_context_menu;
+        let close_entries = [
+            (
+                StandardActions::CLOSE_OTHERS,
+                Button::new(&translations.close_others_button),
+            ),
+            (
+                StandardActions::CLOSE_ALL,
+                Button::new(&translations.close_all_button),
+            ),
+            (
+                StandardActions::CLOSE_TO_THE_RIGHT,
+                Button::new(&translations.close_to_the_right_button),
+            ),
+        ];
+        let float_button = Button::new(&translations.float_button);
+        let split_entries = [
+            (
+                StandardActions::SPLIT_RIGHT,
+                Split::Right,
+                Button::new(&translations.split_right_button),
+            ),
+            (
+                StandardActions::SPLIT_BELOW,
+                Split::Below,
+                Button::new(&translations.split_below_button),
+            ),
+        ];
+
+        let leaf = self.dock_state[surface_index][node_index]
+            .get_leaf_mut()
+            .expect("This node must be a leaf");
+        let pinned = leaf.pinned_count();
+        let closeable: Vec<bool> = leaf
+            .tabs
+            .iter_mut()
+            .enumerate()
+            .map(|(index, tab)| index >= pinned && tab_viewer.is_closeable(tab))
+            .collect();
+        let allowed_in_window = tab_viewer.allowed_in_windows(&mut leaf.tabs[tab_index.0]);
+        let allowed_splits =
+            tab_viewer.allowed_splits(surface_index, node_index, self.allowed_splits);
+        let mut shown = false;
+
+        if self.show_close_buttons {
+            for (action, button) in close_entries {
+                if !actions.contains(action) {
+                    continue;
+                }
+                shown = true;
+                let closed: Vec<TabIndex> = (0..closeable.len())
+                    .filter(|&index| match action {
+                        StandardActions::CLOSE_OTHERS => index != tab_index.0,
+                        StandardActions::CLOSE_TO_THE_RIGHT => index > tab_index.0,
+                        _ => true,
+                    })
+                    .filter(|&index| closeable[index])
+                    .map(TabIndex)
+                    .collect();
+                if ui.add_enabled(!closed.is_empty(), button).clicked() {
+                    // Removals are handled from the last one, so the indices stay valid.
+                    self.to_remove.extend(closed.into_iter().map(|tab| {
+                        TabRemoval::Tab(surface_index, node_index, tab, ForcedRemoval(false))
+                    }));
+                    ui.close();
+                }
+            }
+        }
+
+        if actions.contains(StandardActions::FLOAT) {
+            shown = true;
+            let enabled = (surface_index.is_main() || !is_lonely_tab) && allowed_in_window;
+            if ui.add_enabled(enabled, float_button).clicked() {
+                self.to_detach.push((surface_index, node_index, tab_index));
+                ui.close();
+            }
+        }
+
+        for (action, split, button) in split_entries {
+            if !actions.contains(action) {
+                continue;
+            }
+            shown = true;
+            let allowed = match allowed_splits {
+                AllowedSplits::All => true,
+                AllowedSplits::LeftRightOnly => split.is_left_right(),
+                AllowedSplits::TopBottomOnly => split.is_top_bottom(),
+                AllowedSplits::None => false,
+            };
+            // A maximized leaf must keep its tree intact.
+            let enabled =
+                allowed && closeable.len() > 1 && self.dock_state.maximized_leaf().is_none();
+            if ui.add_enabled(enabled, button).clicked() {
+                self.to_split
+                    .push(((surface_index, node_index, tab_index), split));
+                ui.close();
+            }
+        }
+
+        shown
+    }
 
-            tab_viewer.on_tab_button(tab, &response);
+    /// Draws the arrow buttons at both ends of the tabs, which scroll them by half of their visible length.
+    pub(super) fn tab_scroll_buttons(
+        &mut self,
+        ui: &mut Ui,
+        (surface_index, node_index): (SurfaceIndex, NodeIndex),
+        clip_rect: Rect,
+        tabs_length: f32,
+        fade_style: Option<&Style>,
+        vertical: bool,
+    ) {
+        let id = self
+            .id
+            .with((surface_index, "surface"))
+            .with((node_index, "node"));
+        let along = |rect: Rect| if vertical { transpose(rect) } else { rect };
+        let clip = along(clip_rect);
+        let overflow = (tabs_length - clip.width()).at_least(0.0);
+        let scroll = self.dock_state[surface_index][node_index]
+            .get_leaf()
+            .expect("This node must be a leaf")
+            .scroll;
+
+        let left_rect = Rect::from_min_max(
+            clip.left_top() - vec2(Style::TAB_SCROLL_BUTTON_SIZE, 0.0),
+            clip.left_bottom(),
+        );
+        let right_rect = Rect::from_min_max(
+            clip.right_top(),
+            clip.right_bottom() + vec2(Style::TAB_SCROLL_BUTTON_SIZE, 0.0),
+        );
+        let scroll_left = self
+            .tab_bar_button(
+                ui,
+                along(left_rect),
+                id.with("scroll_left"),
+                scroll < 0.0,
+                fade_style,
+                |painter, rect, stroke| paint_chevron(painter, rect, -1.0, vertical, stroke),
+            )
+            .clicked();
+        let scroll_right = self
+            .tab_bar_button(
+                ui,
+                along(right_rect),
+                id.with("scroll_right"),
+                scroll > -overflow,
+                fade_style,
+                |painter, rect, stroke| paint_chevron(painter, rect, 1.0, vertical, stroke),
+            )
+            .clicked();
 
-            if self.show_close_buttons && tab_viewer.is_closeable(tab) && response.middle_clicked()
-            {
-                self.to_remove.push(TabRemoval::Tab(
-                    surface_index,
-                    node_index,
-                    tab_index,
-                    ForcedRemoval(false),
-                ));
-            }
+        // The scroll is clamped to the overflow afterwards.
+        let leaf = self.dock_state[surface_index][node_index]
+            .get_leaf_mut()
+            .expect("This node must be a leaf");
+        if scroll_left {
+            leaf.scroll += clip.width() / 2.0;
+        }
+        if scroll_right {
+            leaf.scroll -= clip.width() / 2.0;
         }
     }
 
-    /// Draws the tab add button.
+    /// Draws the "»" button after the tabs, listing the ones which don't fully fit in the tab bar.
+    ///
+    /// Choosing a tab from the list activates it and scrolls it into view.
     #[allow(clippy::too_many_arguments)]
-    fn tab_plus(
+    pub(super) fn tab_overflow_menu(
         &mut self,
         ui: &mut Ui,
-        surface_index: SurfaceIndex,
-        node_index: NodeIndex,
+        (surface_index, node_index): (SurfaceIndex, NodeIndex),
         tab_viewer: &mut impl TabViewer<Tab = Tab>,
-        tabbar_outer_rect: Rect,
-        offset: f32,
+        clip_rect: Rect,
+        tab_rects: &[Rect],
         fade_style: Option<&Style>,
+        vertical: bool,
     ) {
+        let id = self
+            .id
+            .with((surface_index, "surface"))
+            .with((node_index, "node"))
+            .with("tab_overflow");
+        let along = |rect: Rect| if vertical { transpose(rect) } else { rect };
+        let clip = along(clip_rect);
         let rect = Rect::from_min_max(
-            tabbar_outer_rect.right_top() - vec2(Style::TAB_ADD_BUTTON_SIZE + offset, 0.0),
-            tabbar_outer_rect.right_bottom() - vec2(offset, 2.0),
+            clip.right_top(),
+            clip.right_bottom() + vec2(Style::TAB_OVERFLOW_BUTTON_SIZE, 0.0),
         );
-
-        let ui = &mut ui.new_child(
-            UiBuilder::new()
-                .max_rect(rect)
-                .layout(Layout::left_to_right(Align::Center))
-                .id_salt((node_index, "tab_add")),
+        let response = self.tab_bar_button(
+            ui,
+            along(rect),
+            id,
+            true,
+            fade_style,
+            |painter, rect, stroke| {
+                let offset = if vertical {
+                    vec2(0.0, rect.height() / 4.0)
+                } else {
+                    vec2(rect.width() / 4.0, 0.0)
+                };
+                paint_chevron(painter, rect.translate(-offset), 1.0, vertical, stroke);
+                paint_chevron(painter, rect.translate(offset), 1.0, vertical, stroke);
+            },
         );
 
-        let (rect, mut response) = ui.allocate_exact_size(ui.available_size(), Sense::click());
+        let hidden = tab_rects
+            .iter()
+            .map(|&rect| along(rect))
+            .enumerate()
+            .filter(|(_, rect)| {
+                rect.left() < clip.left() - 0.5 || rect.right() > clip.right() + 0.5
+            })
+            .map(|(index, _)| index)
+            .collect::<Vec<_>>();
 
-        response = response.on_hover_cursor(CursorIcon::PointingHand);
+        let mut chosen = None;
+        Popup::from_toggle_button_response(&response)
+            .id(id.with("popup"))
+            .show(|ui| {
+                let leaf = self.dock_state[surface_index][node_index]
+                    .get_leaf_mut()
+                    .expect("This node must be a leaf");
+                let active = leaf.active.0;
+                for &index in &hidden {
+                    let title = tab_viewer.title(&mut leaf.tabs[index]);
+                    if ui.selectable_label(index == active, title).clicked() {
+                        chosen = Some(index);
+                    }
+                }
+            });
+
+        if let Some(index) = chosen {
+            let rect = along(tab_rects[index]);
+            let leaf = self.dock_state[surface_index][node_index]
+                .get_leaf_mut()
+                .expect("This node must be a leaf");
+            leaf.active = TabIndex(index);
+            if rect.left() < clip.left() {
+                leaf.scroll += clip.left() - rect.left();
+            } else if rect.right() > clip.right() {
+                leaf.scroll -= rect.right() - clip.right();
+            }
+            self.new_focused = Some((surface_index, node_index));
+        }
+    }
+
+    /// Draws a button at the ends of the tabs in the colors of the add button, painting its symbol with `paint`.
+    pub(super) fn tab_bar_button(
+        &self,
+        ui: &mut Ui,
+        rect: Rect,
+        id: Id,
+        enabled: bool,
+        fade_style: Option<&Style>,
+        paint: impl FnOnce(&Painter, Rect, Stroke),
+    ) -> Response {
+        let sense = if enabled {
+            Sense::click()
+        } else {
+            Sense::hover()
+        };
+        let mut response = ui.interact(rect, id, sense);
+        if enabled {
+            response = response.on_hover_cursor(CursorIcon::PointingHand);
+        }
 
         let style = fade_style.unwrap_or_else(|| self.style.as_ref().unwrap());
-        let color = if response.hovered() || response.has_focus() {
+        let color = if !enabled {
+            style.buttons.add_tab_color.gamma_multiply(0.4)
+        } else if response.hovered() || response.has_focus() {
             ui.painter()
                 .rect_filled(rect, CornerRadius::ZERO, style.buttons.add_tab_bg_fill);
             style.buttons.add_tab_active_color
@@ -526,62 +1330,30 @@ impl<Tab> DockArea<'_, Tab> {
             style.buttons.add_tab_color
         };
 
-        let mut plus_rect = rect;
-
-        rect_set_size_centered(&mut plus_rect, Vec2::splat(Style::TAB_ADD_PLUS_SIZE));
-
-        ui.painter().line_segment(
-            [plus_rect.center_top(), plus_rect.center_bottom()],
-            Stroke::new(1.0, color),
-        );
-        ui.painter().line_segment(
-            [plus_rect.right_center(), plus_rect.left_center()],
-            Stroke::new(1.0, color),
-        );
-
-        // Draw button left border.
-        ui.painter().vline(
-            rect.left(),
-            rect.y_range(),
-            Stroke::new(
-                ui.ctx().pixels_per_point().recip(),
-                style.buttons.add_tab_border_color,
-            ),
+        let mut symbol_rect = rect;
+        rect_set_size_centered(
+            &mut symbol_rect,
+            Vec2::splat(Style::TAB_OVERFLOW_ARROW_SIZE),
         );
+        paint(ui.painter(), symbol_rect, Stroke::new(1.0, color));
 
-        let popup_id = ui.id().with("tab_add_popup");
-        if self.show_add_popup {
-            Popup::from_toggle_button_response(&response)
-                .id(popup_id)
-                .close_behavior(PopupCloseBehavior::CloseOnClickOutside)
-                .show(|ui| {
-                    tab_viewer.add_popup(ui, surface_index, node_index);
-                });
-        }
-
-        if response.clicked() {
-            tab_viewer.on_add(surface_index, node_index);
-        }
+        response
     }
 
-    /// Draws the close all button.
+    /// Draws the close all button in `rect`.
     #[allow(clippy::too_many_arguments)]
     #[allow(unused_assignments)]
-    fn tab_close_all(
+    pub(super) fn tab_close_all(
         &mut self,
         ui: &mut Ui,
         surface_index: SurfaceIndex,
         node_index: NodeIndex,
-        tabbar_outer_rect: Rect,
+        rect: Rect,
+        vertical: bool,
         fade_style: Option<&Style>,
         disabled: bool,
         close_window_disabled: bool,
     ) {
-        let rect = Rect::from_min_max(
-            tabbar_outer_rect.right_top() - vec2(Style::TAB_CLOSE_ALL_BUTTON_SIZE, 0.0),
-            tabbar_outer_rect.right_bottom() - vec2(0.0, 2.0),
-        );
-
         let ui = &mut ui.new_child(
             UiBuilder::new()
                 .max_rect(rect)
@@ -689,14 +1461,12 @@ impl<Tab> DockArea<'_, Tab> {
             );
         }
 
-        // Draw button left border.
-        ui.painter().vline(
-            rect.left(),
-            rect.y_range(),
-            Stroke::new(
-                ui.ctx().pixels_per_point().recip(),
-                style.buttons.close_all_tabs_border_color,
-            ),
+        paint_button_border(
+            ui,
+            rect,
+            vertical,
+            false,
+            style.buttons.close_all_tabs_border_color,
         );
 
         if !disabled && !on_secondary_button {
@@ -704,21 +1474,18 @@ impl<Tab> DockArea<'_, Tab> {
         }
     }
 
-    /// Draws the collapse button.
-    fn tab_collapse(
+    /// Draws the collapse button in `rect`.
+    #[allow(clippy::too_many_arguments)]
+    pub(super) fn tab_collapse(
         &mut self,
         ui: &mut Ui,
-        surface_index: SurfaceIndex,
-        node_index: NodeIndex,
-        tabbar_outer_rect: Rect,
+        (surface_index, node_index): (SurfaceIndex, NodeIndex),
+        tab_viewer: &impl TabViewer<Tab = Tab>,
+        rect: Rect,
+        vertical: bool,
         fade_style: Option<&Style>,
         collapsed: bool,
     ) {
-        let rect = Rect::from_min_max(
-            tabbar_outer_rect.left_top(),
-            tabbar_outer_rect.left_bottom() + vec2(Style::TAB_COLLAPSE_BUTTON_SIZE, 0.0),
-        );
-
         let ui = &mut ui.new_child(
             UiBuilder::new()
                 .max_rect(rect)
@@ -757,23 +1524,24 @@ impl<Tab> DockArea<'_, Tab> {
             Self::draw_arrow(collapsed, ui, color, arrow_rect);
         }
 
-        // Draw button right border.
-        ui.painter().vline(
-            rect.right(),
-            rect.y_range(),
-            Stroke::new(
-                ui.ctx().pixels_per_point().recip(),
-                style.buttons.collapse_tabs_border_color,
-            ),
+        paint_button_border(
+            ui,
+            rect,
+            vertical,
+            true,
+            style.buttons.collapse_tabs_border_color,
         );
 
         if response.clicked() {
             if on_secondary_button {
                 self.window_toggle_minimized(surface_index);
             } else {
-                self.dock_state[surface_index][node_index].set_collapsed(!collapsed);
-                self.dock_state[surface_index].node_update_collapsed(node_index);
-                self.window_update_collapsed(surface_index, node_index);
+                // A leaf with a tab which isn't collapsible stays expanded.
+                let _ = self.dock_state.set_leaf_collapsed(
+                    (surface_index, node_index),
+                    !collapsed,
+                    tab_viewer,
+                );
             }
         }
 
@@ -794,6 +1562,66 @@ impl<Tab> DockArea<'_, Tab> {
         }
     }
 
+    /// Draws the handle used to drag the whole leaf in `rect`.
+    pub(super) fn leaf_drag_handle(
+        &mut self,
+        ui: &mut Ui,
+        state: &mut State,
+        (surface_index, node_index): (SurfaceIndex, NodeIndex),
+        rect: Rect,
+        vertical: bool,
+        fade_style: Option<&Style>,
+    ) {
+        let id = self
+            .id
+            .with((surface_index, "surface"))
+            .with((node_index, "node"))
+            .with("drag_handle");
+        let response = ui
+            .interact(rect, id, Sense::drag())
+            .on_hover_cursor(CursorIcon::Grab);
+
+        let style = fade_style.unwrap_or_else(|| self.style.as_ref().unwrap());
+        let color = if response.hovered() || response.dragged() {
+            style.buttons.collapse_tabs_active_color
+        } else {
+            style.buttons.collapse_tabs_color
+        };
+
+        // Draw a grip made of two columns of dots, or two rows of them in a vertical tab bar.
+        let center = rect.center();
+        for x in [-2.0, 2.0] {
+            for y in [-4.0, 0.0, 4.0] {
+                let offset = if vertical { vec2(y, x) } else { vec2(x, y) };
+                ui.painter().circle_filled(center + offset, 1.0, color);
+            }
+        }
+
+        if response.dragged() && ui.input(|i| i.pointer.is_decidedly_dragging()) {
+            ui.output_mut(|o| o.cursor_icon = CursorIcon::Grabbing);
+            self.drag_from_tab_bar(
+                ui,
+                state,
+                TreeComponent::Node(surface_index, node_index),
+                None,
+                vertical,
+            );
+        }
+    }
+
+    /// Whether `response` was tapped for the second time in a row, when [`DockArea::touch_gestures`] is enabled.
+    pub(super) fn double_tapped(&self, ui: &Ui, state: &mut State, response: &Response) -> bool {
+        self.touch_gestures
+            && response.clicked()
+            && response.interact_pointer_pos().is_some_and(|pos| {
+                state.double_tapped(
+                    (ui.input(|i| i.time), pos),
+                    self.double_tap_max_delay,
+                    self.double_tap_max_distance,
+                )
+            })
+    }
+
     fn show_tooltip_hints(&mut self, surface_index: SurfaceIndex, response: Response) -> Response {
         if !surface_index.is_main()
             && self.show_secondary_button_hint
@@ -919,64 +1747,78 @@ impl<Tab> DockArea<'_, Tab> {
         ));
     }
 
-    /// Updates the collapsed state of the node and its parents.
-    fn window_update_collapsed(&mut self, surface_index: SurfaceIndex, node_index: NodeIndex) {
-        let surface = &mut self.dock_state[surface_index];
-        let collapsed = surface[node_index].is_collapsed();
-        if !collapsed {
-            if let Some(window_state) = self.dock_state.get_window_state_mut(surface_index) {
-                window_state.set_new(true);
-            }
-        } else if surface.root_node().is_some_and(|root| root.is_collapsed()) {
-            let root_index = NodeIndex::root();
-            let surface_height = if surface.root_node().is_some() {
-                surface[root_index].rect().unwrap().height()
-            } else {
-                0.0
-            };
-            if let Some(window_state) = self.dock_state.get_window_state_mut(surface_index) {
-                window_state.set_expanded_height(surface_height);
-            }
-        }
-    }
-
     /// * `active` means "the tab that is opened in the parent panel".
     /// * `focused` means "the tab that was last interacted with".
+    /// * `close_button` is `None` if the tab can't be closed with a button.
     ///
     /// Returns the main button response plus the response of the close button, if any.
+    ///
+    /// The title is the `icon` if there's one, or else [`TabViewer::title_ui`] falling back to
+    /// [`TabViewer::title`]. The button is painted by [`TabViewer::tab_button`] instead if that returns a
+    /// [`Response`]. This doesn't borrow the [`DockArea`] so that the tab can be borrowed from it.
     #[allow(clippy::too_many_arguments)]
     fn tab_title(
-        &mut self,
         ui: &mut Ui,
+        style: &Style,
         tab_style: &TabStyle,
         id: Id,
-        label: WidgetText,
+        icon: Option<WidgetText>,
         focused: bool,
         active: bool,
         is_being_dragged: bool,
         preferred_width: Option<f32>,
-        show_close_button: bool,
-        fade: Option<&Style>,
+        close_button: Option<CloseButtonVisibility>,
+        reserve_close_button_space: bool,
+        draggable_tabs: bool,
+        at_bottom: bool,
+        (tab_viewer, tab): (&mut impl TabViewer<Tab = Tab>, &mut Tab),
     ) -> (Response, Option<Response>) {
-        let style = fade.unwrap_or_else(|| self.style.as_ref().unwrap());
+        // Whether the close button would be visible, given whether the tab is hovered.
+        let close_button_visible = |hovered: bool| match close_button {
+            None | Some(CloseButtonVisibility::Never) => false,
+            Some(CloseButtonVisibility::Always) => true,
+            Some(CloseButtonVisibility::HoverTab) => hovered,
+            Some(CloseButtonVisibility::ActiveTabOnly) => active,
+        };
+        // Without reserving space, the size of the tab depends on whether it was hovered in the last frame.
+        let reserve_close_button_space = match close_button {
+            None | Some(CloseButtonVisibility::Never) => false,
+            Some(_) if reserve_close_button_space => true,
+            Some(_) => close_button_visible(
+                ui.ctx()
+                    .read_response(id)
+                    .is_some_and(|response| response.contains_pointer()),
+            ),
+        };
 
-        let close_button_size = if show_close_button {
-            Style::TAB_CLOSE_BUTTON_SIZE.min(style.tab_bar.height)
+        let close_button_size = if reserve_close_button_space {
+            Style::TAB_CLOSE_BUTTON_SIZE.min(style.tab_bar_height())
         } else {
             0.0
         };
 
         let x_spacing = 8.0;
+        let equal_width = preferred_width.filter(|_| style.tab_bar.equal_tab_width);
+        // The width of the custom title in the last frame, if it was shown.
+        let title_ui_id = id.with("title_ui");
+        let title_ui_width = icon
+            .is_none()
+            .then(|| ui.data(|data| data.get_temp::<f32>(title_ui_id)))
+            .flatten();
+        let show_title_ui = icon.is_none();
+        let label = icon.unwrap_or_else(|| tab_viewer.title(tab));
         let galley = label.into_galley(
-            ui, 
-            Some(egui::TextWrapMode::Truncate), 
-            tab_style.maximum_width.map(|x| x - close_button_size - 2.0 * x_spacing)
-                .unwrap_or(f32::INFINITY), 
-            TextStyle::Button
+            ui,
+            Some(egui::TextWrapMode::Truncate),
+            equal_width
+                .or(tab_style.maximum_width)
+                .map(|x| (x - close_button_size - 2.0 * x_spacing).at_least(0.0))
+                .unwrap_or(f32::INFINITY),
+            TextStyle::Button,
         );
 
-        let text_width = galley.size().x + 2.0 * x_spacing;
-        
+        let text_width = title_ui_width.unwrap_or(galley.size().x) + 2.0 * x_spacing;
+
         // Compute total width of the tab bar.
         let minimum_width = tab_style
             .minimum_width
@@ -987,32 +1829,32 @@ impl<Tab> DockArea<'_, Tab> {
         if let Some(max_width) = tab_style.maximum_width {
             tab_width = tab_width.at_most(max_width);
         }
+        if let Some(equal_width) = equal_width {
+            tab_width = equal_width;
+        }
 
         let (_, tab_rect) = ui.allocate_space(vec2(tab_width, ui.available_height()));
         let mut response = ui.interact(tab_rect, id, Sense::click_and_drag());
-        if ui.ctx().dragged_id().is_none() && self.draggable_tabs {
-            response = response.on_hover_cursor(CursorIcon::Grab);
+        if ui.ctx().dragged_id().is_none() && draggable_tabs {
+            response = response.on_hover_cursor(tab_style.cursor_hovered);
         }
 
-        let tab_style = if focused || is_being_dragged {
-            if response.has_focus() {
-                &tab_style.focused_with_kb_focus
-            } else {
-                &tab_style.focused
-            }
-        } else if active {
-            if response.has_focus() {
-                &tab_style.active_with_kb_focus
-            } else {
-                &tab_style.active
-            }
-        } else if response.hovered() {
-            &tab_style.hovered
-        } else if response.has_focus() {
-            &tab_style.inactive_with_kb_focus
-        } else {
-            &tab_style.inactive
+        let button_state = TabButtonState {
+            rect: tab_rect,
+            active,
+            focused,
+            hovered: response.hovered(),
+            dragged: is_being_dragged,
         };
+        if let Some(custom_response) = tab_viewer.tab_button(ui, tab, button_state) {
+            return (response.union(custom_response), None);
+        }
+
+        let active_indicator = tab_style.active_indicator;
+        let text_align = tab_style.text_align;
+        let close_button_leading = tab_style.close_button_side == Align::Min;
+        let tab_style =
+            interaction_style(tab_style, &response, focused || is_being_dragged, active);
 
         // Draw the full tab first and then the stroke on top to avoid the stroke
         // mixing with the background color.
@@ -1027,29 +1869,81 @@ impl<Tab> DockArea<'_, Tab> {
         );
         if !is_being_dragged {
             // Make the tab name area connect with the tab ui area.
+            let radius = tab_style.corner_radius;
+            let (y, left_radius, right_radius) = if at_bottom {
+                (stroke_rect.top(), radius.nw, radius.ne)
+            } else {
+                (stroke_rect.bottom(), radius.sw, radius.se)
+            };
             ui.painter().hline(
                 RangeInclusive::new(
-                    stroke_rect.min.x + f32::max(tab_style.corner_radius.sw.into(), 1.5),
-                    stroke_rect.max.x - f32::max(tab_style.corner_radius.se.into(), 1.5),
+                    stroke_rect.min.x + f32::max(left_radius.into(), 1.5),
+                    stroke_rect.max.x - f32::max(right_radius.into(), 1.5),
                 ),
-                stroke_rect.bottom(),
+                y,
                 Stroke::new(2.0, tab_style.bg_fill),
             );
         }
 
+        if active {
+            match active_indicator {
+                ActiveTabIndicator::Fill => (),
+                ActiveTabIndicator::Underline { color, thickness } => {
+                    let mut line = tab_rect;
+                    line.set_top(tab_rect.bottom() - thickness);
+                    ui.painter().rect_filled(line, 0.0, color);
+                }
+                ActiveTabIndicator::Overline { color, thickness } => {
+                    let mut line = tab_rect;
+                    line.set_bottom(tab_rect.top() + thickness);
+                    ui.painter().rect_filled(line, 0.0, color);
+                }
+                ActiveTabIndicator::Pill { color, margin } => {
+                    let pill = tab_rect.shrink(margin);
+                    let radius = CornerRadius::same((pill.height() / 2.0).round() as u8);
+                    ui.painter().rect_filled(pill, radius, color);
+                }
+            }
+        }
+
         let mut text_rect = tab_rect;
-        text_rect.set_width(text_rect.width() - close_button_size);
-        let text_pos = {
-            let pos = Align2::CENTER_CENTER.pos_in_rect(&text_rect.shrink2(vec2(x_spacing, 0.0)));
-            pos - galley.size() / 2.0
+        if close_button_leading {
+            text_rect.set_left(text_rect.left() + close_button_size);
+        } else {
+            text_rect.set_width(text_rect.width() - close_button_size);
+        }
+        let title_rect = text_rect.shrink2(vec2(x_spacing, 0.0));
+        let title_ui_shown = show_title_ui && {
+            let width = title_ui_width.unwrap_or(galley.size().x);
+            let rect = Align2([text_align, Align::Center])
+                .align_size_within_rect(vec2(width, title_rect.height()), title_rect);
+            Self::tab_title_ui(
+                ui,
+                UiBuilder::new()
+                    .max_rect(rect)
+                    .layout(Layout::left_to_right(Align::Center)),
+                (title_ui_id, title_rect.intersect(ui.clip_rect())),
+                tab_style.text_color,
+                (tab_viewer, tab),
+            )
         };
+        if !title_ui_shown {
+            let text_pos = Align2([text_align, Align::Center])
+                .align_size_within_rect(galley.size(), title_rect)
+                .min;
+            ui.painter()
+                .add(TextShape::new(text_pos, galley, tab_style.text_color));
+        }
 
-        ui.painter()
-            .add(TextShape::new(text_pos, galley, tab_style.text_color));
-
+        let show_close_button =
+            reserve_close_button_space && close_button_visible(response.contains_pointer());
         let close_response = show_close_button.then(|| {
             let mut close_button_rect = tab_rect;
-            close_button_rect.set_left(text_rect.right());
+            if close_button_leading {
+                close_button_rect.set_right(text_rect.left());
+            } else {
+                close_button_rect.set_left(text_rect.right());
+            }
             close_button_rect =
                 Rect::from_center_size(close_button_rect.center(), Vec2::splat(close_button_size));
 
@@ -1065,8 +1959,13 @@ impl<Tab> DockArea<'_, Tab> {
 
             if close_response.hovered() || close_response.has_focus() {
                 let mut corner_radius = tab_style.corner_radius;
-                corner_radius.nw = 0;
-                corner_radius.sw = 0;
+                if close_button_leading {
+                    corner_radius.ne = 0;
+                    corner_radius.se = 0;
+                } else {
+                    corner_radius.nw = 0;
+                    corner_radius.sw = 0;
+                }
 
                 ui.painter().rect_filled(
                     close_button_rect,
@@ -1106,6 +2005,8 @@ impl<Tab> DockArea<'_, Tab> {
     ) {
         assert_ne!(available_width, 0.0);
 
+        let scroll_delta = self.tab_bar_scroll_delta(ui);
+
         let leaf = self.dock_state[surface_index][node_index]
             .get_leaf_mut()
             .expect("This node must be a leaf");
@@ -1114,10 +2015,11 @@ impl<Tab> DockArea<'_, Tab> {
 
         // Compare to 1.0 and not 0.0 to avoid drawing a scroll bar due
         // to floating point precision issue during tab drawing.
+        leaf.tab_overflow = Some(overflow > 1.0);
         if overflow > 1.0 {
             if style.tab_bar.show_scroll_bar_on_overflow {
                 // Draw scroll bar
-                let bar_height = 7.5;
+                let bar_height = Style::TAB_SCROLL_BAR_HEIGHT;
                 let (scroll_bar_rect, _scroll_bar_response) = ui.allocate_exact_size(
                     vec2(scroll_bar_width, bar_height),
                     Sense::click_and_drag(),
@@ -1152,9 +2054,7 @@ impl<Tab> DockArea<'_, Tab> {
 
                 if let Some(pos) = state.last_hover_pos {
                     if scroll_bar_rect.contains(pos) {
-                        leaf.scroll += ui
-                            .input(|i| i.smooth_scroll_delta.y + i.smooth_scroll_delta.x)
-                            * points_to_scroll_coefficient;
+                        leaf.scroll += scroll_delta * points_to_scroll_coefficient;
                     }
                 }
 
@@ -1174,7 +2074,7 @@ impl<Tab> DockArea<'_, Tab> {
 
             // Handle user input.
             if tabbar_response.hovered() {
-                leaf.scroll += ui.input(|i| i.smooth_scroll_delta.y + i.smooth_scroll_delta.x);
+                leaf.scroll += scroll_delta;
             }
         }
 
@@ -1185,7 +2085,7 @@ impl<Tab> DockArea<'_, Tab> {
     fn tab_body(
         &mut self,
         ui: &mut Ui,
-        state: &State,
+        state: &mut State,
         (surface_index, node_index): (SurfaceIndex, NodeIndex),
         tab_viewer: &mut impl TabViewer<Tab = Tab>,
         spacing: Vec2,
@@ -1195,6 +2095,7 @@ impl<Tab> DockArea<'_, Tab> {
     ) {
         let (body_rect, _body_response) =
             ui.allocate_exact_size(ui.available_size_before_wrap(), Sense::hover());
+        let focused = self.dock_state.focused_leaf() == Some((surface_index, node_index));
 
         let leaf = self.dock_state[surface_index][node_index]
             .get_leaf_mut()
@@ -1208,12 +2109,47 @@ impl<Tab> DockArea<'_, Tab> {
         } = leaf;
         if !collapsed {
             if let Some(tab) = tabs.get_mut(active.0) {
-                if *viewport != body_rect {
-                    *viewport = body_rect;
-                    tab_viewer.on_rect_changed(tab);
+                *viewport = body_rect;
+
+                // Sub-pixel jitter in the layout shouldn't count as a change.
+                const EPSILON: f32 = 0.1;
+                let id = tab_viewer.id(tab);
+                let changed = state.content_rects.get(&id).is_none_or(|last| {
+                    (last.min - body_rect.min).abs().max_elem() > EPSILON
+                        || (last.max - body_rect.max).abs().max_elem() > EPSILON
+                });
+                if changed {
+                    state.content_rects.insert(id, body_rect);
+                    tab_viewer.on_content_rect_changed(tab, body_rect);
+                }
+                if state.shown_tabs.insert(id) {
+                    tab_viewer.on_first_shown(tab);
+                }
+
+                // Repaint what the content showed when the tab was last visible if it was hidden since,
+                // until it's ready to be laid out again.
+                let cache_when_hidden = tab_viewer.cache_when_hidden(tab);
+                if !cache_when_hidden {
+                    state.content_cache.remove(&id);
                 }
+                let pass_nr = ui.ctx().cumulative_pass_nr();
+                let layer_id = ui.layer_id();
+                let replayed = match state.content_cache.get_mut(&id) {
+                    Some(cache)
+                        if !changed
+                            && fade.is_none()
+                            && (cache.replayed || cache.pass_nr + 1 < pass_nr)
+                            && !tab_viewer.content_ready(tab) =>
+                    {
+                        cache.pass_nr = pass_nr;
+                        cache.replayed = true;
+                        true
+                    }
+                    _ => false,
+                };
+                let tab_id = id;
 
-                if ui.input(|i| i.pointer.any_click()) {
+                if self.enabled && ui.input(|i| i.pointer.any_click()) {
                     if let Some(pos) = state.last_hover_pos {
                         if body_rect.contains(pos)
                             && Some(ui.layer_id()) == ui.ctx().layer_id_at(pos)
@@ -1229,7 +2165,7 @@ impl<Tab> DockArea<'_, Tab> {
 
                 let tabs_style = tabs_styles.as_ref().unwrap_or(&style.tab);
 
-                if tab_viewer.clear_background(tab) {
+                if tab_viewer.clear_content_background(tab) {
                     ui.painter().rect_filled(
                         body_rect,
                         tabs_style.tab_body.corner_radius,
@@ -1244,11 +2180,15 @@ impl<Tab> DockArea<'_, Tab> {
                 // from node to node.
                 let id = self.id.with(tab_viewer.id(tab));
                 ui.ctx().check_for_id_clash(id, body_rect, "a tab with id");
-                let ui = &mut Ui::new(
-                    ui.ctx().clone(),
-                    id,
-                    UiBuilder::new().max_rect(body_rect).layer_id(ui.layer_id()),
-                );
+                // The widgets registered from here on in this layer are the content ui and its children.
+                let first_widget = ui.ctx().viewport(|viewport| {
+                    viewport.this_pass.widgets.get_layer(ui.layer_id()).count()
+                });
+                let mut builder = UiBuilder::new().max_rect(body_rect).layer_id(ui.layer_id());
+                if !ui.is_enabled() {
+                    builder = builder.disabled();
+                }
+                let ui = &mut Ui::new(ui.ctx().clone(), id, builder);
                 ui.set_clip_rect(Rect::from_min_max(ui.cursor().min, ui.clip_rect().max));
 
                 // Use initial spacing for ui.
@@ -1269,18 +2209,96 @@ impl<Tab> DockArea<'_, Tab> {
                     StrokeKind::Inside,
                 );
 
-                ScrollArea::new(tab_viewer.scroll_bars(tab)).show(ui, |ui| {
-                    Frame::new()
-                        .inner_margin(tabs_style.tab_body.inner_margin)
-                        .show(ui, |ui| {
-                            if fade_factor != 1.0 {
-                                fade_visuals(ui.visuals_mut(), fade_factor);
-                            }
-                            let available_rect = ui.available_rect_before_wrap();
-                            ui.expand_to_include_rect(available_rect);
-                            tab_viewer.ui(ui, tab);
+                let loading = tab_viewer.tab_loading(tab);
+                if self.style.as_ref().unwrap().animations_enabled {
+                    // Only fade in the content once it's ready, the placeholder is shown as is.
+                    let ready = tab_viewer.content_ready(tab);
+                    let opacity = ui.ctx().animate_bool_with_time(
+                        id.with("content_ready"),
+                        ready,
+                        ui.style().animation_time,
+                    );
+                    if ready {
+                        ui.multiply_opacity(opacity);
+                    }
+                }
+                if !replayed {
+                    let start = ui.ctx().graphics(|g| {
+                        g.get(layer_id)
+                            .map_or(0, |paint_list| paint_list.next_idx().0)
+                    });
+                    let scroll_bars = tab_viewer
+                        .leaf_scroll_bars(surface_index, node_index)
+                        .unwrap_or_else(|| tab_viewer.scroll_bars(tab));
+                    ScrollArea::new(scroll_bars).show(ui, |ui| {
+                        Frame::new()
+                            .inner_margin(tabs_style.tab_body.inner_margin)
+                            .show(ui, |ui| {
+                                if fade_factor != 1.0 {
+                                    fade_visuals(ui.visuals_mut(), fade_factor);
+                                }
+                                if loading && self.block_loading_tabs {
+                                    ui.disable();
+                                }
+                                let available_rect = ui.available_rect_before_wrap();
+                                ui.expand_to_include_rect(available_rect);
+                                let context = TabContext {
+                                    surface: surface_index,
+                                    node: node_index,
+                                    tab: *active,
+                                    focused,
+                                };
+                                tab_viewer.ui_with_context(ui, tab, context);
+                            });
+                    });
+                    if cache_when_hidden {
+                        let shapes = ui.ctx().graphics(|g| {
+                            g.get(layer_id).map_or_else(Vec::new, |paint_list| {
+                                paint_list.all_entries().skip(start).cloned().collect()
+                            })
                         });
+                        state.content_cache.insert(
+                            tab_id,
+                            ContentCache {
+                                pass_nr,
+                                replayed: false,
+                                shapes,
+                            },
+                        );
+                    }
+                } else if let Some(cache) = state.content_cache.get(&tab_id) {
+                    ui.ctx().graphics_mut(|g| {
+                        let paint_list = g.entry(layer_id);
+                        for clipped in &cache.shapes {
+                            paint_list.add(clipped.clip_rect, clipped.shape.clone());
+                        }
+                    });
+                }
+
+                if loading {
+                    ui.painter().rect_filled(
+                        body_rect,
+                        tabs_style.tab_body.corner_radius,
+                        tabs_style.tab_body.loading_scrim,
+                    );
+                    let size = ui.spacing().interact_size.y * 2.0;
+                    Spinner::new().paint_at(
+                        ui,
+                        Rect::from_center_size(body_rect.center(), Vec2::splat(size)),
+                    );
+                }
+
+                let content_focused = ui.memory(|m| m.focused()).is_some_and(|focused| {
+                    let order = ui
+                        .ctx()
+                        .viewport(|viewport| viewport.this_pass.widgets.order(focused));
+                    order.is_some_and(|(layer_id, index)| {
+                        layer_id == ui.layer_id() && index >= first_widget
+                    })
                 });
+                if content_focused {
+                    self.focused_content = Some(tab_viewer.id(tab));
+                }
             }
         }
 
@@ -1292,6 +2310,14 @@ impl<Tab> DockArea<'_, Tab> {
             // if the dragged tab isn't allowed in a window,
             // it's unnecessary to change the hover state
             let is_dragged_valid = match &state.dnd {
+                Some(DragDropState {
+                    drag:
+                        DragData {
+                            foreign: Some(foreign),
+                            ..
+                        },
+                    ..
+                }) => foreign.allowed_in_windows || surface_index == SurfaceIndex::main(),
                 Some(DragDropState {
                     drag: DragData { src, .. },
                     ..
@@ -1304,7 +2330,17 @@ impl<Tab> DockArea<'_, Tab> {
                             true
                         }
                     }
-                    _ => unreachable!("collections of nodes can't be dragged (yet)"),
+                    TreeComponent::Node(d_surf, d_node) => {
+                        if let Node::Leaf(leaf) = &mut self.dock_state[d_surf][d_node] {
+                            leaf.tabs
+                                .iter_mut()
+                                .all(|tab| tab_viewer.allowed_in_windows(tab))
+                                || surface_index == SurfaceIndex::main()
+                        } else {
+                            true
+                        }
+                    }
+                    _ => unreachable!("surfaces can't be dragged (yet)"),
                 },
                 _ => true,
             };
@@ -1313,15 +2349,23 @@ impl<Tab> DockArea<'_, Tab> {
             // the underlying responses.
             if state.drag_start.is_some() && rect.contains(pointer) && is_dragged_valid {
                 let on_title_bar = tabbar_rect.contains(pointer);
-                let (dst, tab) = {
+                let (dst, tab, caret) = {
                     match self.tab_hover_rect {
-                        Some((rect, tab_index)) => (
-                            TreeComponent::Tab(surface_index, node_index, tab_index),
+                        // A collapsed leaf has no content, so all of it is one zone to drop tabs into.
+                        _ if collapsed => (
+                            TreeComponent::Node(surface_index, node_index),
                             Some(rect),
+                            None,
+                        ),
+                        Some((caret, tab_index)) => (
+                            TreeComponent::Tab(surface_index, node_index, tab_index),
+                            Some(tabbar_rect),
+                            Some(caret),
                         ),
                         None => (
                             TreeComponent::Node(surface_index, node_index),
                             on_title_bar.then_some(tabbar_rect),
+                            None,
                         ),
                     }
                 };
@@ -1329,10 +2373,173 @@ impl<Tab> DockArea<'_, Tab> {
                 ui.memory_mut(|mem| {
                     mem.data.insert_temp(
                         self.id.with("hover_data"),
-                        Some(HoverData { rect, dst, tab }),
+                        Some(HoverData {
+                            rect,
+                            dst,
+                            tab,
+                            caret,
+                        }),
                     );
                 });
             }
         }
     }
 }
+
+/// Picks the style of a tab button from the state it's in.
+pub(super) fn interaction_style<'a>(
+    tab_style: &'a TabStyle,
+    response: &Response,
+    focused: bool,
+    active: bool,
+) -> &'a TabInteractionStyle {
+    if focused {
+        if response.has_focus() {
+            &tab_style.focused_with_kb_focus
+        } else {
+            &tab_style.focused
+        }
+    } else if active {
+        if response.has_focus() {
+            &tab_style.active_with_kb_focus
+        } else {
+            &tab_style.active
+        }
+    } else if response.hovered() {
+        &tab_style.hovered
+    } else if response.has_focus() {
+        &tab_style.inactive_with_kb_focus
+    } else {
+        &tab_style.inactive
+    }
+}
+
+/// Mirrors the corner radii of a tab vertically, so that tabs below their content are rounded at the bottom.
+fn flip_corners(tab_style: &mut TabStyle) {
+    for style in [
+        &mut tab_style.active,
+        &mut tab_style.inactive,
+        &mut tab_style.focused,
+        &mut tab_style.hovered,
+        &mut tab_style.inactive_with_kb_focus,
+        &mut tab_style.active_with_kb_focus,
+        &mut tab_style.focused_with_kb_focus,
+    ] {
+        let CornerRadius { nw, ne, sw, se } = style.corner_radius;
+        style.corner_radius = CornerRadius {
+            nw: sw,
+            ne: se,
+            sw: nw,
+            se: ne,
+        };
+    }
+}
+
+/// Paints a chevron in `rect` pointing right if `direction` is positive, or left if it's negative.
+/// If `vertical` is set, it points down or up instead.
+pub(super) fn paint_chevron(
+    painter: &Painter,
+    rect: Rect,
+    direction: f32,
+    vertical: bool,
+    stroke: Stroke,
+) {
+    let along = |pos: Pos2| if vertical { pos2(pos.y, pos.x) } else { pos };
+    let rect = if vertical { transpose(rect) } else { rect };
+    let half = rect.width() / 4.0 * direction.signum();
+    let tip = rect.center() + vec2(half, 0.0);
+    let (top, bottom) = (
+        pos2(tip.x - 2.0 * half, rect.top()),
+        pos2(tip.x - 2.0 * half, rect.bottom()),
+    );
+    painter.line_segment([along(top), along(tip)], stroke);
+    painter.line_segment([along(bottom), along(tip)], stroke);
+}
+
+/// Paints the border of a button at the ends of a tab bar on its left side, or on its right one if `end` is set.
+/// In a `vertical` tab bar, it's painted on its top or bottom side instead.
+fn paint_button_border(ui: &Ui, rect: Rect, vertical: bool, end: bool, color: Color32) {
+    let stroke = Stroke::new(ui.ctx().pixels_per_point().recip(), color);
+    match (vertical, end) {
+        (false, false) => ui.painter().vline(rect.left(), rect.y_range(), stroke),
+        (false, true) => ui.painter().vline(rect.right(), rect.y_range(), stroke),
+        (true, false) => ui.painter().hline(rect.x_range(), rect.top(), stroke),
+        (true, true) => ui.painter().hline(rect.x_range(), rect.bottom(), stroke),
+    };
+}
+
+/// Swaps the x and y coordinates of a rect, so that vertical tab bars can be handled like horizontal ones.
+pub(super) fn transpose(rect: Rect) -> Rect {
+    Rect::from_min_max(pos2(rect.min.y, rect.min.x), pos2(rect.max.y, rect.max.x))
+}
+
+/// Where the other tabs of a leaf make room for the tab dragged along its tab bar, kept from one frame to the next.
+#[derive(Clone, Copy, Debug)]
+struct TabSlide {
+    dragged: TabIndex,
+    width: f32,
+
+    /// The index among the other tabs of the one the gap is before, or their count if it's after all of them.
+    gap: usize,
+}
+
+/// Lays out the tabs other than the `dragged` one from `origin` on as if there was no gap between them, given their
+/// rects and the spacing before each of them, and finds the gap a tab dragged to `x` is dropped into.
+/// Returns the index of the gap, see [`TabSlide::gap`], and the horizontal extent the dragged tab would take up in it.
+///
+/// The gap is never before the first `pinned_count` tabs, which stay in front.
+fn tab_slide_target(
+    (tab_rects, spacings): (&[Rect], &[f32]),
+    dragged: TabIndex,
+    pinned_count: usize,
+    origin: f32,
+    x: f32,
+) -> (usize, Rect) {
+    let (mut left, mut gap, mut landing) = (origin, 0, origin);
+    let others = tab_rects
+        .iter()
+        .zip(spacings)
+        .enumerate()
+        .filter(|&(index, _)| index != dragged.0);
+    for (index, (rect, spacing)) in others {
+        if index != 0 {
+            left += spacing;
+        }
+        if index < pinned_count || left + rect.width() / 2.0 < x {
+            gap += 1;
+            landing = left + rect.width();
+        }
+        left += rect.width();
+    }
+    let width = tab_rects[dragged.0].width();
+    let rect = Rect::from_x_y_ranges(landing..=landing + width, tab_rects[dragged.0].y_range());
+    (gap, rect)
+}
+
+/// Finds the gap between two of the `tab_rects` which is the closest to `x`,
+/// returning the rect of the caret shown in that gap and the index a tab dropped there is inserted at.
+pub(super) fn tab_insertion_caret(
+    tab_rects: &[Rect],
+    x: f32,
+    visible_rect: Rect,
+) -> Option<(Rect, TabIndex)> {
+    let (first, last) = (tab_rects.first()?, tab_rects.last()?);
+    let index = tab_rects
+        .iter()
+        .take_while(|rect| rect.center().x < x)
+        .count();
+    let caret_x = if index == 0 {
+        first.left()
+    } else if index == tab_rects.len() {
+        last.right()
+    } else {
+        (tab_rects[index - 1].right() + tab_rects[index].left()) / 2.0
+    };
+    let caret_x = caret_x.clamp(visible_rect.left(), visible_rect.right());
+    let half_width = Style::TAB_INSERTION_CARET_WIDTH / 2.0;
+    let caret = Rect::from_x_y_ranges(
+        (caret_x - half_width)..=(caret_x + half_width),
+        visible_rect.y_range(),
+    );
+    Some((caret, TabIndex(index)))
+}
@@ -1,22 +1,37 @@
 use egui::{
-    CentralPanel, Color32, Context, CornerRadius, CursorIcon, EventFilter, Frame, Key, Pos2, Rect,
-    Sense, StrokeKind, Ui, Vec2,
+    ahash::HashSet, CentralPanel, Color32, Context, CornerRadius, EventFilter, Frame, Id, Key,
+    Pos2, Rangef, Rect, Sense, StrokeKind, Ui, Vec2,
 };
 
 use duplicate::duplicate;
 use paste::paste;
 
-use super::{drag_and_drop::TreeComponent, state::State, tab_removal::TabRemoval};
+use super::{
+    animation,
+    dock_event::LayoutSnapshot,
+    drag_and_drop::{draw_highlight_rect, splits_fitting, TreeComponent},
+    state::State,
+    tab_removal::TabRemoval,
+};
 use crate::dock_area::tab_removal::ForcedRemoval;
+use crate::dock_state::LayoutChange;
 use crate::tab_viewer::OnCloseResponse;
 use crate::{
     utils::{expand_to_pixel, fade_dock_style, map_to_pixel},
-    AllowedSplits, DockArea, Node, NodeIndex, OverlayType, Style, SurfaceIndex, TabDestination,
-    TabViewer,
+    AllowedSplits, DockArea, DropKind, FocusAfterClose, Node, NodeIndex, OverlayType,
+    ReclickAction, Split, Style, SurfaceIndex, TabDestination, TabIndex, TabInsert, TabViewer,
+    Tree,
 };
+use window_surface::window_id;
 
 mod leaf;
 mod main_surface;
+mod shared_drag;
+mod shortcuts;
+mod tab_bar;
+mod tab_switcher;
+mod vertical_tab_bar;
+mod viewport_drag;
 mod window_surface;
 
 impl<Tab> DockArea<'_, Tab> {
@@ -48,7 +63,9 @@ impl<Tab> DockArea<'_, Tab> {
     ///
     /// See also [`show_inside`](Self::show_inside).
     #[inline]
-    pub fn show(self, ctx: &Context, tab_viewer: &mut impl TabViewer<Tab = Tab>) {
+    pub fn show(mut self, ctx: &Context, tab_viewer: &mut impl TabViewer<Tab = Tab>) {
+        // Windows aren't confined to the central panel.
+        self.window_bounds.get_or_insert(ctx.screen_rect());
         CentralPanel::default()
             .frame(
                 Frame::central_panel(&ctx.style())
@@ -64,47 +81,231 @@ impl<Tab> DockArea<'_, Tab> {
     ///
     /// See also [`show`](Self::show).
     pub fn show_inside(mut self, ui: &mut Ui, tab_viewer: &mut impl TabViewer<Tab = Tab>) {
-        self.style
-            .get_or_insert(Style::from_egui(ui.style().as_ref()));
-        self.window_bounds.get_or_insert(ui.ctx().screen_rect());
+        if self.style.is_none() {
+            let mut style = Style::from_egui(ui.style().as_ref());
+            if let Some(modify) = &self.style_modifier {
+                modify(&mut style);
+            }
+            self.style = Some(style);
+        }
+        let mut window_bounds = ui.max_rect();
+        if let Some(margin) = self.style.as_ref().unwrap().dock_area_padding {
+            window_bounds.min += margin.left_top();
+            window_bounds.max -= margin.right_bottom();
+        }
+        self.window_bounds.get_or_insert(window_bounds);
+
+        if !self.enabled {
+            ui.disable();
+            self.interactive = false;
+            self.mru_switcher = false;
+        }
+        if !self.interactive {
+            self.draggable_tabs = false;
+            self.rename_tabs_on_double_click = false;
+            self.maximize_on_double_click = false;
+            self.allow_os_window_detach = false;
+            self.allow_window_merging = false;
+            self.show_add_buttons = false;
+            self.show_close_buttons = false;
+            self.close_tab_shortcut = None;
+            self.touch_gestures = false;
+            self.show_window_close_buttons = false;
+            self.show_window_collapse_buttons = false;
+            self.show_leaf_close_all_buttons = false;
+            self.show_leaf_collapse_buttons = false;
+            self.show_leaf_drag_handles = false;
+            if self.active_tab_reclick == ReclickAction::ToggleMaximize {
+                self.active_tab_reclick = ReclickAction::None;
+            }
+        }
 
         let mut state = State::load(ui.ctx(), self.id);
+        let first_widget = widget_count(ui);
+        self.receive_shared_tab(ui, tab_viewer);
+
+        // The maximized leaf may have been moved or removed since the last frame.
+        if let Some((surface, node)) = self.dock_state.maximized_leaf {
+            let is_leaf = self.dock_state.is_surface_valid(surface)
+                && node.0 < self.dock_state[surface].len()
+                && self.dock_state[surface][node].is_leaf();
+            if !is_leaf {
+                self.dock_state.maximized_leaf = None;
+            }
+        }
+
+        // The selected tabs may have been moved or removed since the last frame.
+        if let Some(selection) = &state.selection {
+            let (surface, node) = selection.leaf;
+            let is_valid = self.multi_tab_selection
+                && self.dock_state.is_surface_valid(surface)
+                && node.0 < self.dock_state[surface].len()
+                && selection
+                    .tabs
+                    .iter()
+                    .all(|tab| tab.0 < self.dock_state[surface][node].tabs_count());
+            if !is_valid {
+                state.selection = None;
+            }
+        }
 
         // Delay hover position one frame. On touch screens hover_pos() is None when any_released()
         if !ui.input(|i| i.pointer.any_released()) {
             state.last_hover_pos = ui.input(|i| i.pointer.hover_pos());
         }
 
-        let (drag_data, hover_data) = ui.memory_mut(|mem| {
+        let (mut drag_data, hover_data) = ui.memory_mut(|mem| {
             (
                 mem.data.remove_temp(self.id.with("drag_data")).flatten(),
                 mem.data.remove_temp(self.id.with("hover_data")).flatten(),
             )
         });
+        if self.drag_context.is_some() {
+            drag_data =
+                self.share_drag(ui, &mut state, drag_data, hover_data.is_some(), tab_viewer);
+        }
+
+        self.dock_state.drop_kind = None;
+        self.drag_across_viewports(ui, &mut state, drag_data.as_ref(), tab_viewer);
 
         if let (Some(source), Some(hover)) = (drag_data, hover_data) {
             let style = self.style.as_ref().unwrap();
             state.set_drag_and_drop(source, hover, ui.ctx(), style);
             let tab_dst = self.show_drag_drop_overlay(ui, &mut state, tab_viewer);
-            if ui.input(|i| i.pointer.primary_released()) {
+            let drag = &state.dnd.as_ref().unwrap().drag;
+            self.dock_state.drop_kind = tab_dst.as_ref().map(|destination| match &drag.foreign {
+                // A tab from another area can't stay where it is.
+                Some(_) => match destination {
+                    TabDestination::Window(_) => DropKind::NewWindow,
+                    TabDestination::Node(_, _, TabInsert::Split(_)) => DropKind::Split,
+                    _ => DropKind::IntoLeaf,
+                },
+                None => drop_kind(&drag.src, destination),
+            });
+            if drag.foreign.is_some() {
+                // The area the tab is dragged in moves it here once it's dropped.
+                self.share_drop(tab_dst);
+            } else if ui.input(|i| i.pointer.primary_released()) {
                 if let Some(destination) = tab_dst {
-                    let source = {
-                        match state.dnd.as_ref().unwrap().drag.src {
-                            TreeComponent::Tab(src_surf, src_node, src_tab) => {
-                                (src_surf, src_node, src_tab)
-                            }
-                            _ => todo!(
-                                "collections of tabs, like nodes and surfaces can't be docked (yet)"
-                            ),
+                    // A dragged leaf is sized after its first tab.
+                    let (source, first_tab) = match state.dnd.as_ref().unwrap().drag.src {
+                        TreeComponent::Tab(src_surf, src_node, src_tab) => {
+                            (Some(src_tab), (src_surf, src_node, src_tab))
+                        }
+                        TreeComponent::Node(src_surf, src_node) => {
+                            (None, (src_surf, src_node, TabIndex(0)))
+                        }
+                        TreeComponent::Surface(_) => {
+                            todo!("surfaces can't be docked (yet)")
+                        }
+                    };
+                    let fraction = match destination {
+                        TabDestination::Node(_, _, TabInsert::Split(split)) => {
+                            self.drop_split_fraction(tab_viewer, first_tab, split)
                         }
+                        _ => self.drop_split_fraction,
                     };
-                    self.dock_state.move_tab(source, destination);
+                    let (src_surf, src_node, _) = first_tab;
+                    if let TabDestination::Node(
+                        dst_surf,
+                        dst_node,
+                        TabInsert::Append | TabInsert::Insert(_),
+                    ) = destination
+                    {
+                        self.expand_for_drop((src_surf, src_node), (dst_surf, dst_node));
+                    }
+                    match source {
+                        Some(src_tab) => {
+                            match state.selected_group((src_surf, src_node, src_tab)) {
+                                Some(src_tabs) => {
+                                    // Selected pinned tabs stay behind.
+                                    let src_tabs: Vec<TabIndex> = src_tabs
+                                        .iter()
+                                        .copied()
+                                        .filter(|&tab| {
+                                            !self.dock_state.is_pinned((src_surf, src_node, tab))
+                                        })
+                                        .collect();
+                                    self.dock_state.move_tabs_with_split_fraction(
+                                        (src_surf, src_node),
+                                        &src_tabs,
+                                        destination,
+                                        fraction,
+                                    )
+                                }
+                                None => {
+                                    // The insertion index counts the dragged tab, which is taken out first.
+                                    let destination = match destination {
+                                        TabDestination::Node(
+                                            surface,
+                                            node,
+                                            TabInsert::Insert(index),
+                                        ) if (surface, node) == (src_surf, src_node)
+                                            && src_tab < index =>
+                                        {
+                                            TabDestination::Node(
+                                                surface,
+                                                node,
+                                                TabInsert::Insert(TabIndex(index.0 - 1)),
+                                            )
+                                        }
+                                        destination => destination,
+                                    };
+                                    self.dock_state.move_tab_with_split_fraction(
+                                        (src_surf, src_node, src_tab),
+                                        destination,
+                                        fraction,
+                                    )
+                                }
+                            }
+                        }
+                        None => self.dock_state.move_leaf_with_split_fraction(
+                            (src_surf, src_node),
+                            destination,
+                            fraction,
+                        ),
+                    }
+                    state.selection = None;
                 }
             }
         }
 
         if ui.input(|i| i.pointer.primary_released()) {
             state.reset_drag();
+            self.dock_state.drop_kind = None;
+        }
+
+        if self.native_windows {
+            self.windows_to_viewports(ui);
+        }
+
+        if self.mru_switcher || self.focus_after_close == FocusAfterClose::MostRecentlyUsed {
+            self.update_recently_used_tabs(&mut state, tab_viewer);
+        }
+        if self.mru_switcher {
+            self.show_tab_switcher(ui, &mut state, tab_viewer);
+        }
+        if self.interactive {
+            self.handle_shortcuts(ui);
+        }
+
+        if let Some(shortcut) = self.close_tab_shortcut {
+            if let Some((surface_index, node_index)) = self.dock_state.focused_leaf() {
+                if self.has_focus(ui, &state) && ui.input_mut(|i| i.consume_shortcut(&shortcut)) {
+                    let active = self.dock_state[surface_index][node_index]
+                        .get_leaf()
+                        .map(|leaf| leaf.active);
+                    if let Some(tab_index) = active {
+                        self.close_tab(
+                            &state,
+                            (surface_index, node_index, tab_index),
+                            tab_viewer,
+                            false,
+                        );
+                        state.selection = None;
+                    }
+                }
+            }
         }
 
         let style = self.style.as_ref().unwrap();
@@ -119,6 +320,15 @@ impl<Tab> DockArea<'_, Tab> {
         };
 
         for &surface_index in self.dock_state.valid_surface_indices().iter() {
+            // Windows are hidden while a leaf of the main surface is maximized.
+            if !surface_index.is_main()
+                && self
+                    .dock_state
+                    .maximized_leaf()
+                    .is_some_and(|(surface, _)| surface.is_main())
+            {
+                continue;
+            }
             self.show_surface_inside(
                 surface_index,
                 ui,
@@ -130,58 +340,100 @@ impl<Tab> DockArea<'_, Tab> {
             );
         }
 
-        for removal in self.to_remove.drain(..).rev() {
+        if let Some(on_layout) = self.on_layout.take() {
+            on_layout(&std::mem::take(&mut self.layout_info));
+        }
+
+        if self.allow_window_merging {
+            self.merge_dragged_window(ui);
+        }
+
+        if self.external_drag_active && state.dnd.is_none() {
+            let hovered_leaf = ui
+                .input(|i| i.pointer.hover_pos())
+                .and_then(|pos| self.dock_state.hit_test(pos));
+            if let Some((surface_index, node_index)) = hovered_leaf {
+                if let Some(rect) = self.dock_state[surface_index][node_index].rect() {
+                    draw_highlight_rect(rect, ui, self.style.as_ref().unwrap());
+                }
+            }
+        }
+
+        // Removing, detaching, pinning or splitting off tabs shifts the indices of the selected ones.
+        if !self.to_remove.is_empty()
+            || !self.to_detach.is_empty()
+            || !self.to_pin.is_empty()
+            || !self.to_split.is_empty()
+        {
+            state.selection = None;
+        }
+
+        for tab in std::mem::take(&mut self.to_pin) {
+            let pinned = self.dock_state.is_pinned(tab);
+            let _ = self.dock_state.set_pinned(tab, !pinned);
+        }
+
+        for ((surface, node, tab), split) in std::mem::take(&mut self.to_split) {
+            let fraction = self.drop_split_fraction(tab_viewer, (surface, node, tab), split);
+            self.dock_state.move_tab_with_split_fraction(
+                (surface, node, tab),
+                TabDestination::Node(surface, node, TabInsert::Split(split)),
+                fraction,
+            );
+        }
+
+        for removal in std::mem::take(&mut self.to_remove).into_iter().rev() {
             match removal {
                 TabRemoval::Tab(surface, node, tab, ForcedRemoval(is_forced)) => {
-                    if is_forced {
-                        self.dock_state.remove_tab((surface, node, tab));
-                    } else {
-                        let leaf = &mut self.dock_state[surface][node].get_leaf_mut().unwrap();
-                        match tab_viewer.on_close(&mut leaf.tabs[tab.0]) {
-                            OnCloseResponse::Close => {
-                                self.dock_state.remove_tab((surface, node, tab));
-                            }
-                            OnCloseResponse::Focus => {
-                                leaf.active = tab;
-                                self.new_focused = Some((surface, node));
-                            }
-                            OnCloseResponse::Ignore => {
-                                // no-op
-                            }
-                        }
-                    }
+                    self.close_tab(&state, (surface, node, tab), tab_viewer, is_forced);
                 }
                 TabRemoval::Node(surface, node) => {
+                    // Pinned tabs are left open.
+                    let Some(leaf) = self.dock_state[surface][node].get_leaf_mut() else {
+                        continue;
+                    };
+                    let pinned = leaf.pinned_count();
                     let mut all_tabs_are_closable = true;
-                    for tab in self.dock_state[surface][node].iter_tabs_mut() {
+                    for tab in &mut leaf.tabs[pinned..] {
                         if !(tab_viewer.is_closeable(tab)
                             && matches!(tab_viewer.on_close(tab), OnCloseResponse::Close))
                         {
                             all_tabs_are_closable = false;
                         }
                     }
-                    if all_tabs_are_closable {
+                    if !all_tabs_are_closable {
+                        continue;
+                    }
+                    if pinned == 0 {
                         self.dock_state.remove_leaf((surface, node));
+                    } else {
+                        leaf.tabs.truncate(pinned);
+                        leaf.active = TabIndex(leaf.active.0.min(pinned - 1));
                     }
                 }
                 TabRemoval::Window(surface) => {
-                    let mut all_tabs_are_closable = true;
-                    for node in self.dock_state[surface].iter_mut() {
-                        for tab in node.iter_tabs_mut() {
-                            if !(tab_viewer.is_closeable(tab)
-                                && matches!(tab_viewer.on_close(tab), OnCloseResponse::Close))
-                            {
-                                all_tabs_are_closable = false;
-                            }
-                        }
-                    }
-                    if all_tabs_are_closable {
-                        self.dock_state.remove_surface(surface);
-                    }
+                    self.dock_state.remove_surface_with(surface, tab_viewer);
                 }
             }
         }
 
+        // Drop what's kept for tabs which don't exist anymore.
+        if !state.content_cache.is_empty()
+            || !state.content_rects.is_empty()
+            || !state.recently_used.is_empty()
+            || !state.shown_tabs.is_empty()
+        {
+            let ids: HashSet<Id> = self
+                .dock_state
+                .iter_all_tabs_mut()
+                .map(|(_, tab)| tab_viewer.id(tab))
+                .collect();
+            state.content_cache.retain(|id, _| ids.contains(id));
+            state.content_rects.retain(|id, _| ids.contains(id));
+            state.recently_used.retain(|id| ids.contains(id));
+            state.shown_tabs.retain(|id| ids.contains(id));
+        }
+
         for (surface_index, node_index, tab_index) in self.to_detach.drain(..).rev() {
             let mouse_pos = state.last_hover_pos;
             self.dock_state.detach_tab(
@@ -199,9 +451,110 @@ impl<Tab> DockArea<'_, Tab> {
             self.dock_state.set_focused_node_and_surface(focused);
         }
 
+        if state.focused_content != self.focused_content {
+            for (id, focused) in [(state.focused_content, false), (self.focused_content, true)] {
+                if let Some(tab) = id.and_then(|id| self.find_tab_by_id(tab_viewer, id)) {
+                    tab_viewer.on_content_focus_changed(tab, focused);
+                }
+            }
+            state.focused_content = self.focused_content;
+        }
+
+        // Tabs enter or leave the maximized leaf when it changes, or when they're moved into or out of it.
+        let maximized_tabs: Vec<Id> = self
+            .dock_state
+            .maximized_leaf
+            .and_then(|(surface, node)| {
+                let tree = self.dock_state.get_surface_mut(surface)?.node_tree_mut()?;
+                tree.iter_mut().nth(node.0)?.tabs_mut()
+            })
+            .into_iter()
+            .flatten()
+            .map(|tab| tab_viewer.id(tab))
+            .collect();
+        if state.maximized_tabs != maximized_tabs {
+            for (_, tab) in self.dock_state.iter_all_tabs_mut() {
+                let id = tab_viewer.id(tab);
+                let maximized = maximized_tabs.contains(&id);
+                if state.maximized_tabs.contains(&id) != maximized {
+                    tab_viewer.on_maximize_changed(tab, maximized);
+                }
+            }
+            state.maximized_tabs = maximized_tabs;
+        }
+
+        let snapshot = (self.on_events.is_some() || self.dock_state.has_layout_history())
+            .then(|| LayoutSnapshot::new(self.dock_state, tab_viewer));
+        state.layout_snapshot =
+            self.on_events
+                .take()
+                .zip(snapshot.clone())
+                .map(|(on_events, snapshot)| {
+                    if let Some(previous) = &state.layout_snapshot {
+                        let events = snapshot.events_since(previous);
+                        if !events.is_empty() {
+                            on_events(events);
+                        }
+                    }
+                    snapshot
+                });
+        if self.dock_state.has_layout_history() {
+            // Changes of the tabs or the tree are steps of the history, other changes update the last step.
+            let change = match (&state.history_snapshot, &snapshot) {
+                (Some(previous), Some(snapshot)) if previous != snapshot => {
+                    if snapshot.same_structure(previous) {
+                        LayoutChange::Adjusted
+                    } else {
+                        LayoutChange::Restructured
+                    }
+                }
+                _ => LayoutChange::None,
+            };
+            self.dock_state.record_layout(change);
+            state.history_snapshot = snapshot;
+        } else {
+            state.history_snapshot = None;
+        }
+
+        state.widgets = Some((ui.layer_id(), first_widget..widget_count(ui)));
         state.store(ui.ctx(), self.id);
     }
 
+    /// Returns `true` if no widget has keyboard focus, or if the focused one was shown by the [`DockArea`] in the
+    /// last frame, in its main surface or in one of its windows.
+    fn has_focus(&self, ui: &Ui, state: &State) -> bool {
+        let Some(focused) = ui.ctx().memory(|m| m.focused()) else {
+            return true;
+        };
+        let Some((layer_id, index)) = ui
+            .ctx()
+            .viewport(|viewport| viewport.prev_pass.widgets.order(focused))
+        else {
+            return false;
+        };
+        let in_main_surface = state
+            .widgets
+            .as_ref()
+            .is_some_and(|(layer, widgets)| *layer == layer_id && widgets.contains(&index));
+        in_main_surface
+            || self
+                .dock_state
+                .iter_windows()
+                .any(|(surface, _, _)| layer_id.id == window_id(surface))
+    }
+
+    /// Finds a tab by its [`TabViewer::id`].
+    fn find_tab_by_id(
+        &mut self,
+        tab_viewer: &mut impl TabViewer<Tab = Tab>,
+        id: Id,
+    ) -> Option<&mut Tab> {
+        self.dock_state
+            .iter_all_tabs_mut()
+            .map(|(_, tab)| tab)
+            .find_map(|tab| (tab_viewer.id(tab) == id).then_some(tab))
+    }
+
     /// Returns some when windows are fading, and what surface index is being hovered over
     #[inline(always)]
     fn hovered_window_surface(
@@ -217,10 +570,16 @@ impl<Tab> DockArea<'_, Tab> {
             }
         }
 
-        state.window_fade.and_then(|(time, surface)| {
-            ctx.request_repaint();
-            (hold_time > (ctx.input(|i| i.time) - time) as f32).then_some(surface)
-        })
+        let (time, surface) = state.window_fade?;
+        let remaining = hold_time - (ctx.input(|i| i.time) - time) as f32;
+        if remaining > 0.0 {
+            // Repaint once the fade is over, even if nothing else happens until then.
+            ctx.request_repaint_after_secs(remaining);
+            Some(surface)
+        } else {
+            state.window_fade = None;
+            None
+        }
     }
 
     /// Resolve where a dragged tab would land given it's dropped this frame, returns `None` when the resulting drop is an invalid move.
@@ -230,6 +589,13 @@ impl<Tab> DockArea<'_, Tab> {
         state: &mut State,
         tab_viewer: &impl TabViewer<Tab = Tab>,
     ) -> Option<TabDestination> {
+        let drag = &state.dnd.as_ref().unwrap().drag;
+        let dragged_group = match drag.src {
+            TreeComponent::Tab(surface, node, tab) if drag.foreign.is_none() => state
+                .selected_group((surface, node, tab))
+                .map(<[TabIndex]>::to_vec),
+            _ => None,
+        };
         let drag_state = state.dnd.as_mut().unwrap();
         let style = self.style.as_ref().unwrap();
 
@@ -239,35 +605,129 @@ impl<Tab> DockArea<'_, Tab> {
                 drag_state.hover.dst.node_address(),
             ) {
                 ((src_surf, Some(src_node)), (dst_surf, Some(dst_node))) => {
-                    src_surf == dst_surf
+                    drag_state.drag.foreign.is_none()
+                        && src_surf == dst_surf
                         && src_node == dst_node
-                        && self.dock_state[src_surf][src_node].tabs_count() == 1
+                        && (drag_state.drag.src.is_node()
+                            || self.dock_state[src_surf][src_node].tabs_count() == 1)
                 }
                 _ => false,
             }
         };
 
-        // Not all scenarios can house all splits.
-        let restricted_splits = if drag_state.hover.dst.is_surface() || deserted_node {
+        // Not all scenarios can house all splits, and a maximized leaf must keep its tree intact.
+        let restricted_splits = if drag_state.hover.dst.is_surface()
+            || deserted_node
+            || self.dock_state.maximized_leaf().is_some()
+        {
             AllowedSplits::None
         } else {
             AllowedSplits::All
         };
-        let allowed_splits = self.allowed_splits & restricted_splits;
+        let allowed_splits = match drag_state.hover.dst.node_address() {
+            (surface, Some(node)) => tab_viewer.allowed_splits(surface, node, self.allowed_splits),
+            (_, None) => self.allowed_splits,
+        } & restricted_splits
+            & splits_fitting(drag_state.hover.rect, style);
 
-        let allowed_in_window = match drag_state.drag.src {
+        let (allowed_in_window, split_fractions) = match drag_state.drag.src {
+            _ if drag_state.drag.foreign.is_some() => (
+                drag_state.drag.foreign.as_ref().unwrap().allowed_in_windows,
+                [self.drop_split_fraction.clamp(0.0, 1.0); 4],
+            ),
             TreeComponent::Tab(surface, node, tab) => {
+                let split_fractions = [Split::Above, Split::Below, Split::Left, Split::Right]
+                    .map(|split| self.drop_split_fraction(tab_viewer, (surface, node, tab), split));
                 let Node::Leaf(leaf) = &mut self.dock_state[surface][node] else {
                     unreachable!("tab drags can only come from leaf nodes")
                 };
-                tab_viewer.allowed_in_windows(&mut leaf.tabs[tab.0])
+                let allowed_in_window = match &dragged_group {
+                    Some(tabs) => tabs
+                        .iter()
+                        .all(|tab| tab_viewer.allowed_in_windows(&mut leaf.tabs[tab.0])),
+                    None => tab_viewer.allowed_in_windows(&mut leaf.tabs[tab.0]),
+                };
+                (allowed_in_window, split_fractions)
+            }
+            TreeComponent::Node(surface, node) => {
+                let split_fractions =
+                    [Split::Above, Split::Below, Split::Left, Split::Right].map(|split| {
+                        self.drop_split_fraction(tab_viewer, (surface, node, TabIndex(0)), split)
+                    });
+                let Node::Leaf(leaf) = &mut self.dock_state[surface][node] else {
+                    unreachable!("only leaf nodes can be dragged")
+                };
+                (
+                    leaf.tabs
+                        .iter_mut()
+                        .all(|tab| tab_viewer.allowed_in_windows(tab)),
+                    split_fractions,
+                )
             }
-            _ => todo!("collections of tabs, like nodes or surfaces, can't be dragged! (yet)"),
+            TreeComponent::Surface(_) => todo!("surfaces can't be dragged! (yet)"),
+        };
+        // Where the dragged tabs may go in the hovered node, dropped into it or next to each of its sides.
+        let allowed_drops = {
+            let (dst_surf, dst_node) = drag_state.hover.dst.node_address();
+            let dst_node = dst_node.unwrap_or(NodeIndex::root());
+            [
+                None,
+                Some(Split::Above),
+                Some(Split::Below),
+                Some(Split::Left),
+                Some(Split::Right),
+            ]
+            .map(|split| match drag_state.drag.src {
+                _ if drag_state.drag.foreign.is_some() => true,
+                TreeComponent::Tab(surface, node, tab) => {
+                    let tabs = self.dock_state[surface][node]
+                        .tabs()
+                        .expect("tab drags can only come from leaf nodes");
+                    match &dragged_group {
+                        Some(group) => group.iter().all(|tab| {
+                            tab_viewer.allowed_drop(&tabs[tab.0], dst_surf, dst_node, split)
+                        }),
+                        None => tab_viewer.allowed_drop(&tabs[tab.0], dst_surf, dst_node, split),
+                    }
+                }
+                TreeComponent::Node(surface, node) => tab_viewer.allowed_leaf_drop(
+                    self.dock_state[surface][node]
+                        .tabs()
+                        .expect("only leaf nodes can be dragged"),
+                    dst_surf,
+                    dst_node,
+                    split,
+                ),
+                TreeComponent::Surface(_) => true,
+            })
+        };
+        let allowed_drop = |split: Option<Split>| match split {
+            None => allowed_drops[0],
+            Some(Split::Above) => allowed_drops[1],
+            Some(Split::Below) => allowed_drops[2],
+            Some(Split::Left) => allowed_drops[3],
+            Some(Split::Right) => allowed_drops[4],
+        };
+
+        // New windows would be hidden behind a maximized leaf of the main surface.
+        let allowed_in_window = allowed_in_window
+            && !self
+                .dock_state
+                .maximized_leaf()
+                .is_some_and(|(surface, _)| surface.is_main());
+        let split_fraction = |split| match split {
+            Split::Above => split_fractions[0],
+            Split::Below => split_fractions[1],
+            Split::Left => split_fractions[2],
+            Split::Right => split_fractions[3],
         };
 
         if let Some(pointer) = state.last_hover_pos {
             drag_state.pointer = pointer;
         }
+        let torn_off = state
+            .drag_start
+            .is_none_or(|start| drag_state.pointer.distance(start) >= self.tearoff_distance);
 
         let window_bounds = self.window_bounds.unwrap();
         match (style.overlay.overlay_type, drag_state.is_on_title_bar()) {
@@ -276,18 +736,122 @@ impl<Tab> DockArea<'_, Tab> {
                 style,
                 allowed_splits,
                 allowed_in_window,
+                torn_off,
                 window_bounds,
+                split_fraction,
+                allowed_drop,
             ),
             (OverlayType::Widgets, false) => drag_state.resolve_icon_based(
                 ui,
                 style,
                 allowed_splits,
                 allowed_in_window,
+                torn_off,
                 window_bounds,
+                split_fraction,
+                allowed_drop,
             ),
         }
     }
 
+    /// Expands the collapsed leaf at `dst` which tabs from another leaf are about to be dropped into.
+    fn expand_for_drop(
+        &mut self,
+        src: (SurfaceIndex, NodeIndex),
+        (dst_surf, dst_node): (SurfaceIndex, NodeIndex),
+    ) {
+        let node = &mut self.dock_state[dst_surf][dst_node];
+        if src == (dst_surf, dst_node) || !node.is_leaf() || !node.is_collapsed() {
+            return;
+        }
+        node.set_collapsed(false);
+        self.dock_state[dst_surf].node_update_collapsed(dst_node);
+        self.dock_state.window_update_collapsed(dst_surf, dst_node);
+    }
+
+    /// Shows every window which isn't shown in its own viewport yet in one, where it was or would be shown.
+    fn windows_to_viewports(&mut self, ui: &Ui) {
+        // Viewports are positioned in screen coordinates, unlike windows inside the viewport.
+        let offset = ui
+            .input(|i| i.viewport().inner_rect)
+            .map_or(Vec2::ZERO, |rect| rect.min.to_vec2());
+        for (_, _, window_state) in self.dock_state.iter_windows_mut() {
+            if window_state.is_viewport() {
+                continue;
+            }
+            let rect = window_state.rect();
+            if let Some(position) = window_state.next_position() {
+                window_state.set_position(position + offset);
+            } else if rect.is_positive() {
+                window_state.set_position(rect.min + offset);
+                window_state.set_size(rect.size());
+            }
+            window_state.set_viewport(true);
+        }
+    }
+
+    /// Closes a tab, or removes it if `forced`, then picks the new active tab according to
+    /// [`DockArea::focus_after_close`] if the tab was active.
+    fn close_tab(
+        &mut self,
+        state: &State,
+        (surface, node, tab): (SurfaceIndex, NodeIndex, TabIndex),
+        tab_viewer: &mut impl TabViewer<Tab = Tab>,
+        forced: bool,
+    ) {
+        let was_active = self.dock_state.active_tab((surface, node)) == Some(tab);
+        // Closing the last tab removes the leaf, so there's nothing left to activate.
+        let was_last = self
+            .dock_state
+            .leaf(surface, node)
+            .is_none_or(|leaf| leaf.tabs.len() == 1);
+        let closed = if forced {
+            self.dock_state.remove_tab((surface, node, tab)).is_some()
+        } else {
+            self.dock_state.close_tab((surface, node, tab), tab_viewer)
+        };
+        if !closed || !was_active || was_last {
+            return;
+        }
+
+        let Some(leaf) = self.dock_state[surface][node].get_leaf_mut() else {
+            return;
+        };
+        let left = TabIndex(tab.0.saturating_sub(1));
+        leaf.active = match self.focus_after_close {
+            FocusAfterClose::None => return,
+            FocusAfterClose::Left => left,
+            FocusAfterClose::Right => TabIndex(tab.0.min(leaf.tabs.len() - 1)),
+            FocusAfterClose::MostRecentlyUsed => leaf
+                .tabs
+                .iter_mut()
+                .enumerate()
+                .filter_map(|(index, tab)| {
+                    let id = tab_viewer.id(tab);
+                    let used = state.recently_used.iter().position(|&used| used == id)?;
+                    Some((used, TabIndex(index)))
+                })
+                .min()
+                .map_or(left, |(_, index)| index),
+        };
+        self.new_focused = Some((surface, node));
+    }
+
+    /// Returns how much of a node's area the tab at the given address occupies when dropped into a new split.
+    fn drop_split_fraction(
+        &self,
+        tab_viewer: &impl TabViewer<Tab = Tab>,
+        (surface, node, tab): (SurfaceIndex, NodeIndex, TabIndex),
+        split: Split,
+    ) -> f32 {
+        self.dock_state[surface][node]
+            .tabs()
+            .and_then(|tabs| tabs.get(tab.0))
+            .and_then(|tab| tab_viewer.drop_split_fraction(tab, split))
+            .unwrap_or(self.drop_split_fraction)
+            .clamp(0.0, 1.0)
+    }
+
     /// Show a single surface of a [`DockState`].
     fn show_surface_inside(
         &mut self,
@@ -299,6 +863,12 @@ impl<Tab> DockArea<'_, Tab> {
     ) {
         if surf_index.is_main() {
             self.show_root_surface_inside(ui, tab_viewer, state);
+        } else if self
+            .dock_state
+            .get_window_state(surf_index)
+            .is_some_and(|window_state| window_state.is_viewport())
+        {
+            self.show_viewport_surface(ui, surf_index, tab_viewer, state, fade_style);
         } else {
             self.show_window_surface(ui, surf_index, tab_viewer, state, fade_style);
         }
@@ -312,13 +882,33 @@ impl<Tab> DockArea<'_, Tab> {
         surf_index: SurfaceIndex,
         fade_style: Option<(&Style, f32)>,
     ) {
+        // The rects the nodes were shown at, to move them from there if the tree changed since.
+        let shown_rects = self
+            .tree_changed(ui, surf_index)
+            .then(|| self.dock_state[surf_index].iter().map(Node::rect).collect());
+
         // First compute all rect sizes in the node graph.
         let max_rect = self.allocate_area_for_root_node(ui, surf_index);
+
+        // A maximized leaf takes the place of the whole tree, which is left untouched.
+        if let Some((_, node_index)) = self
+            .dock_state
+            .maximized_leaf
+            .filter(|(surface, _)| *surface == surf_index)
+        {
+            self.dock_state[surf_index][node_index].set_rect(max_rect);
+            self.show_leaf(ui, state, (surf_index, node_index), tab_viewer, fade_style);
+            self.highlight_viewport_drop(ui, state, surf_index);
+            return;
+        }
+
         for node_index in self.dock_state[surf_index].breadth_first_index_iter() {
             if self.dock_state[surf_index][node_index].is_parent() {
+                self.keep_fixed_size_leaf(ui, (surf_index, node_index), tab_viewer);
                 self.compute_rect_sizes(ui, (surf_index, node_index), max_rect);
             }
         }
+        self.animate_node_rects(ui, surf_index, shown_rects);
 
         // Then, draw the bodies of each leaves.
         for node_index in self.dock_state[surf_index].breadth_first_index_iter() {
@@ -335,6 +925,88 @@ impl<Tab> DockArea<'_, Tab> {
                 self.show_separator(ui, (surf_index, node_index), fade_style);
             }
         }
+        self.highlight_viewport_drop(ui, state, surf_index);
+    }
+
+    /// Returns whether nodes of the tree of a surface were added, removed, collapsed or expanded since it was last
+    /// shown, in which case they move to their new places over [`AnimationStyle::split_duration`](crate::AnimationStyle::split_duration).
+    fn tree_changed(&self, ui: &Ui, surface: SurfaceIndex) -> bool {
+        let style = self.style.as_ref().unwrap();
+        if !style.animations_enabled || style.animation.split_duration <= 0.0 {
+            return false;
+        }
+        let structure = self.dock_state[surface].iter().fold(Id::NULL, |id, node| {
+            id.with((std::mem::discriminant(node), node.is_collapsed()))
+        });
+        let id = self.id.with((surface, "tree_structure"));
+        ui.data_mut(|data| {
+            let last = data.get_temp::<Id>(id);
+            data.insert_temp(id, structure);
+            last.is_some_and(|last| last != structure)
+        })
+    }
+
+    /// Moves the nodes of a surface towards the rects computed for them this frame, starting from the rects they
+    /// were `shown` at when the tree changed.
+    ///
+    /// New nodes grow out of the side of their parent they're added on, and new splits start out where the
+    /// nodes they split were.
+    fn animate_node_rects(
+        &mut self,
+        ui: &Ui,
+        surface: SurfaceIndex,
+        shown: Option<Vec<Option<Rect>>>,
+    ) {
+        let style = self.style.as_ref().unwrap();
+        let tree = &mut self.dock_state[surface];
+        let shown_rect = |node: NodeIndex| {
+            shown
+                .as_ref()
+                .and_then(|rects| rects.get(node.0).copied().flatten())
+                .filter(Rect::is_positive)
+        };
+
+        let mut starts: Vec<Option<Rect>> = Vec::with_capacity(tree.len());
+        for index in 0..tree.len() {
+            let node_index = NodeIndex(index);
+            let Some(target) = tree[node_index].rect() else {
+                starts.push(None);
+                continue;
+            };
+            let start = shown.is_some().then(|| {
+                let parent_start = node_index.parent().and_then(|parent| starts[parent.0]);
+                if let Some(rect) = shown_rect(node_index) {
+                    rect
+                } else if tree[node_index].is_parent() {
+                    match (
+                        shown_rect(node_index.left()),
+                        shown_rect(node_index.right()),
+                    ) {
+                        (Some(left), Some(right)) => left.union(right),
+                        (Some(rect), None) | (None, Some(rect)) => rect,
+                        (None, None) => target,
+                    }
+                } else if let Some(parent) = node_index.parent().filter(|_| parent_start.is_some())
+                {
+                    let mut rect = target;
+                    match (tree[parent].is_horizontal(), node_index.is_left()) {
+                        (true, true) => rect.max.x = rect.min.x,
+                        (true, false) => rect.min.x = rect.max.x,
+                        (false, true) => rect.max.y = rect.min.y,
+                        (false, false) => rect.min.y = rect.max.y,
+                    }
+                    rect
+                } else {
+                    target
+                }
+            });
+            starts.push(start);
+
+            let id = self.id.with((surface, node_index, "rect_animation"));
+            let duration = style.animation.split_duration;
+            let rect = animation::animate(ui.ctx(), id, start, target, duration, style);
+            tree[node_index].set_rect(rect);
+        }
     }
 
     fn allocate_area_for_root_node(&mut self, ui: &mut Ui, surface: SurfaceIndex) -> Rect {
@@ -364,6 +1036,55 @@ impl<Tab> DockArea<'_, Tab> {
         rect
     }
 
+    /// Adjusts the fraction of a split whose size changed since the last frame, so that a
+    /// [fixed size leaf](TabViewer::fixed_size_leaf) among its children keeps its size.
+    fn keep_fixed_size_leaf(
+        &mut self,
+        ui: &Ui,
+        (surface_index, node_index): (SurfaceIndex, NodeIndex),
+        tab_viewer: &impl TabViewer<Tab = Tab>,
+    ) {
+        let tree = &self.dock_state[surface_index];
+        let is_fixed = |node: NodeIndex| {
+            tree[node].get_leaf().is_some_and(|leaf| {
+                !leaf.collapsed && tab_viewer.fixed_size_leaf(surface_index, node, &leaf.tabs)
+            })
+        };
+        let (left_fixed, right_fixed) = (is_fixed(node_index.left()), is_fixed(node_index.right()));
+        if left_fixed == right_fixed {
+            return;
+        }
+        // Sizes from the last frame aren't known yet for new nodes.
+        let (Some(left), Some(right)) = (
+            tree[node_index.left()].rect().filter(Rect::is_positive),
+            tree[node_index.right()].rect().filter(Rect::is_positive),
+        ) else {
+            return;
+        };
+
+        let separator_width = self.style.as_ref().unwrap().separator.width;
+        let px = ui.ctx().pixels_per_point().recip();
+        duplicate! {
+            [
+                orientation   dim_size;
+                [Horizontal]  [width];
+                [Vertical]    [height];
+            ]
+            if let Node::orientation(split) = &mut self.dock_state[surface_index][node_index] {
+                let size = split.rect.dim_size();
+                let last_size = left.dim_size() + right.dim_size() + separator_width;
+                if size > 0.0 && (size - last_size).abs() > px {
+                    let left_size = if left_fixed {
+                        left.dim_size()
+                    } else {
+                        size - right.dim_size() - separator_width
+                    };
+                    split.fraction = ((left_size + separator_width * 0.5) / size).clamp(0.0, 1.0);
+                }
+            }
+        }
+    }
+
     fn compute_rect_sizes(
         &mut self,
         ui: &Ui,
@@ -391,7 +1112,7 @@ impl<Tab> DockArea<'_, Tab> {
                 if left_collapsed {
                     // EITHER only left collapsed OR left and right both collapsed
                     let border_y =
-                        rect.min.y + (left_collapsed_count as f32) * style.tab_bar.height;
+                        rect.min.y + (left_collapsed_count as f32) * style.tab_bar_height();
                     let left_separator_border = map_to_pixel(
                         border_y - style.separator.width * 0.5,
                         pixels_per_point,
@@ -413,7 +1134,7 @@ impl<Tab> DockArea<'_, Tab> {
                 } else {
                     // Only right collapsed
                     let border_y =
-                        rect.max.y - (right_collapsed_count as f32) * style.tab_bar.height;
+                        rect.max.y - (right_collapsed_count as f32) * style.tab_bar_height();
                     let left_separator_border = map_to_pixel(
                         border_y - style.separator.width * 0.5,
                         pixels_per_point,
@@ -437,6 +1158,12 @@ impl<Tab> DockArea<'_, Tab> {
             }
         }
 
+        let ranges = children_size_ranges(
+            &self.dock_state[surface_index],
+            node_index,
+            style.separator.width,
+        );
+
         duplicate! {
             [
                 orientation   dim_point  dim_size  left_of    right_of;
@@ -448,6 +1175,16 @@ impl<Tab> DockArea<'_, Tab> {
                 debug_assert!(!rect.any_nan() && rect.is_finite());
                 let rect = expand_to_pixel(rect, pixels_per_point);
 
+                // Keeps the size constraints when the dock area is resized.
+                if let Some((left_range, right_range)) = ranges {
+                    split.fraction = clamp_fraction(
+                        split.fraction,
+                        rect.dim_size(),
+                        style.separator.width,
+                        (left_range, right_range),
+                    );
+                }
+
                 let midpoint = rect.min.dim_point + rect.dim_size() * split.fraction;
                 let left_separator_border = map_to_pixel(
                     midpoint - style.separator.width * 0.5,
@@ -490,17 +1227,43 @@ impl<Tab> DockArea<'_, Tab> {
         let style = fade_style.unwrap_or_else(|| self.style.as_ref().unwrap());
         let pixels_per_point = ui.ctx().pixels_per_point();
 
+        // The sizes of the children before and after the separator moved, if it did.
+        let mut resized = None;
+        let ranges = children_size_ranges(
+            &self.dock_state[surface_index],
+            node_index,
+            style.separator.width,
+        );
+
+        // While the children move into their places, the separator stays in between them.
+        let tree = &self.dock_state[surface_index];
+        let moving_children = [node_index.left(), node_index.right()]
+            .into_iter()
+            .any(|child| {
+                let id = self.id.with((surface_index, child, "rect_animation"));
+                animation::is_animating::<Rect>(ui.ctx(), id, style.animation.split_duration, style)
+            })
+            .then(|| {
+                tree[node_index.left()]
+                    .rect()
+                    .zip(tree[node_index.right()].rect())
+            })
+            .flatten();
+
         duplicate! {
             [
-                orientation   dim_point  dim_size;
-                [Horizontal]  [x]        [width];
-                [Vertical]    [y]        [height];
+                orientation   dim_point  dim_size  cursor               min_leaf;
+                [Horizontal]  [x]        [width]   [cursor_horizontal]  [min_leaf_width];
+                [Vertical]    [y]        [height]  [cursor_vertical]    [min_leaf_height];
             ]
             if let Node::orientation(split) = &mut self.dock_state[surface_index][node_index] {
                 let rect = split.rect;
                 let mut separator = rect;
 
-                let midpoint = rect.min.dim_point + rect.dim_size() * split.fraction;
+                let midpoint = match moving_children {
+                    Some((left, right)) => (left.max.dim_point + right.min.dim_point) / 2.0,
+                    None => rect.min.dim_point + rect.dim_size() * split.fraction,
+                };
                 separator.min.dim_point = midpoint - style.separator.width * 0.5;
                 separator.max.dim_point = midpoint + style.separator.width * 0.5;
 
@@ -508,8 +1271,12 @@ impl<Tab> DockArea<'_, Tab> {
                 expand.dim_point += style.separator.extra_interact_width / 2.0;
                 let interact_rect = separator.expand2(expand);
 
-                let response = ui.allocate_rect(interact_rect, Sense::click_and_drag())
-                    .on_hover_and_drag_cursor(paste!{ CursorIcon::[<Resize orientation>]});
+                let response = if self.interactive {
+                    ui.allocate_rect(interact_rect, Sense::click_and_drag())
+                        .on_hover_and_drag_cursor(style.separator.cursor)
+                } else {
+                    ui.allocate_rect(interact_rect, Sense::hover())
+                };
 
                 let should_respond_to_arrow_keys = ui.input(|i| i.modifiers.command || i.modifiers.shift);
 
@@ -540,32 +1307,64 @@ impl<Tab> DockArea<'_, Tab> {
                     None
                 };
 
-                let midpoint = rect.min.dim_point + rect.dim_size() * split.fraction;
+                let snap_bypassed = !self.split_snap_bypass_modifiers.is_none()
+                    && ui.input(|i| i.modifiers.contains(self.split_snap_bypass_modifiers));
+                let snapping = response.dragged()
+                    && arrow_key_offset.is_none()
+                    && !self.split_snap.is_empty()
+                    && !snap_bypassed;
+
+                let (color, width) = if snapping && self.split_snap.contains(&split.fraction) {
+                    (style.separator.color_snapped, style.separator.width_dragged)
+                } else if response.dragged() {
+                    (style.separator.color_dragged, style.separator.width_dragged)
+                } else if response.hovered() || response.has_focus() {
+                    (style.separator.color_hovered, style.separator.width_hovered)
+                } else {
+                    (style.separator.color_idle, None)
+                };
+                let width = width.unwrap_or(style.separator.width);
+
+                let midpoint = match moving_children {
+                    Some((left, right)) => (left.max.dim_point + right.min.dim_point) / 2.0,
+                    None => rect.min.dim_point + rect.dim_size() * split.fraction,
+                };
                 separator.min.dim_point = map_to_pixel(
-                    midpoint - style.separator.width * 0.5,
+                    midpoint - width * 0.5,
                     pixels_per_point,
                     f32::round,
                 );
                 separator.max.dim_point = map_to_pixel(
-                    midpoint + style.separator.width * 0.5,
+                    midpoint + width * 0.5,
                     pixels_per_point,
                     f32::round,
                 );
 
-                let color = if response.dragged() {
-                    style.separator.color_dragged
-                } else if response.hovered() || response.has_focus() {
-                    style.separator.color_hovered
-                } else {
-                    style.separator.color_idle
-                };
-
-                ui.painter().rect_filled(separator, CornerRadius::ZERO, color);
+                // A separator without width is still there to be dragged, it just isn't drawn.
+                if style.separator.visible && separator.dim_size() > 0.0 {
+                    ui.painter().rect_filled(separator, CornerRadius::ZERO, color);
+                }
 
                 // Update 'fraction' interaction after drawing separator,
                 // otherwise it may overlap on other separator / bodies when
                 // shrunk fast.
-                if let Some(pos) = response.interact_pointer_pos().or(arrow_key_offset.map(|v| separator.center() + v)) {
+                let old_fraction = split.fraction;
+                if let Some(pos) = response.interact_pointer_pos().filter(|_| snapping) {
+                    // Follow the pointer rather than its movement, so that the split can leave a snap point again.
+                    let range = rect.max.dim_point - rect.min.dim_point;
+                    let min = (style.separator.extra.max(style.separator.min_leaf) / range).min(1.0);
+                    let max = 1.0 - min;
+                    let (min, max) = (min.min(max), max.max(min));
+                    let fraction = ((pos.dim_point - rect.min.dim_point) / range).clamp(min, max);
+                    split.fraction = self
+                        .split_snap
+                        .iter()
+                        .copied()
+                        .filter(|snap| (min..=max).contains(snap))
+                        .filter(|snap| (snap - fraction).abs() * range <= self.split_snap_tolerance)
+                        .min_by(|a, b| (a - fraction).abs().total_cmp(&(b - fraction).abs()))
+                        .unwrap_or(fraction);
+                } else if let Some(pos) = response.interact_pointer_pos().or(arrow_key_offset.map(|v| separator.center() + v)) {
                     let dim_point = pos.dim_point;
                     let delta = arrow_key_offset.unwrap_or(response.drag_delta()).dim_point;
 
@@ -573,7 +1372,7 @@ impl<Tab> DockArea<'_, Tab> {
                         || (delta < 0. && dim_point < midpoint && dim_point > rect.min.dim_point)
                     {
                         let range = rect.max.dim_point - rect.min.dim_point;
-                        let min = (style.separator.extra / range).min(1.0);
+                        let min = (style.separator.extra.max(style.separator.min_leaf) / range).min(1.0);
                         let max = 1.0 - min;
                         let (min, max) = (min.min(max), max.max(min));
                         split.fraction = (split.fraction + delta / range).clamp(min, max);
@@ -583,7 +1382,175 @@ impl<Tab> DockArea<'_, Tab> {
                 if response.double_clicked() {
                     split.fraction = 0.5;
                 }
+
+                if let Some(ranges) = ranges.filter(|_| split.fraction != old_fraction) {
+                    split.fraction = clamp_fraction(split.fraction, rect.dim_size(), style.separator.width, ranges);
+                }
+
+                if split.fraction != old_fraction {
+                    let size = rect.dim_size();
+                    resized = Some((
+                        (old_fraction * size, split.fraction * size),
+                        ((1.0 - old_fraction) * size, (1.0 - split.fraction) * size),
+                    ));
+                }
+            }
+        }
+
+        if let Some((left, right)) = resized.filter(|_| !self.proportional_resize) {
+            let tree = &mut self.dock_state[surface_index];
+            let horizontal = tree[node_index].is_horizontal();
+            keep_outer_sizes(tree, node_index.left(), left, true, horizontal);
+            keep_outer_sizes(tree, node_index.right(), right, false, horizontal);
+        }
+    }
+}
+
+/// Returns how many widgets were registered in the layer of `ui` so far in this pass.
+fn widget_count(ui: &Ui) -> usize {
+    ui.ctx()
+        .viewport(|viewport| viewport.this_pass.widgets.get_layer(ui.layer_id()).count())
+}
+
+/// Returns the ranges of sizes the children of the split at `node_index` are constrained to along its direction,
+/// or [`None`] if none of their leaves has a [size range](Node::set_size_range).
+fn children_size_ranges<Tab>(
+    tree: &Tree<Tab>,
+    node_index: NodeIndex,
+    separator_width: f32,
+) -> Option<(Rangef, Rangef)> {
+    let horizontal = tree[node_index].is_horizontal();
+    let left = size_range(tree, node_index.left(), horizontal, separator_width);
+    let right = size_range(tree, node_index.right(), horizontal, separator_width);
+    (left.is_some() || right.is_some()).then(|| {
+        let unconstrained = Rangef::new(0.0, f32::INFINITY);
+        (
+            left.unwrap_or(unconstrained),
+            right.unwrap_or(unconstrained),
+        )
+    })
+}
+
+/// Returns the range of widths (or heights, if not `horizontal`) the subtree at `node_index` can take
+/// within the [size ranges](Node::set_size_range) of its leaves, or [`None`] if none of them is constrained.
+pub(super) fn size_range<Tab>(
+    tree: &Tree<Tab>,
+    node_index: NodeIndex,
+    horizontal: bool,
+    separator_width: f32,
+) -> Option<Rangef> {
+    if node_index.0 >= tree.len() {
+        return None;
+    }
+    match &tree[node_index] {
+        Node::Leaf(leaf) if !leaf.collapsed => {
+            if horizontal {
+                leaf.width_range
+            } else {
+                leaf.height_range
             }
         }
+        Node::Horizontal(_) | Node::Vertical(_) => {
+            let left = size_range(tree, node_index.left(), horizontal, separator_width);
+            let right = size_range(tree, node_index.right(), horizontal, separator_width);
+            if left.is_none() && right.is_none() {
+                return None;
+            }
+            let unconstrained = Rangef::new(0.0, f32::INFINITY);
+            let (left, right) = (
+                left.unwrap_or(unconstrained),
+                right.unwrap_or(unconstrained),
+            );
+            Some(if tree[node_index].is_horizontal() == horizontal {
+                // The children are side by side.
+                Rangef::new(
+                    left.min + right.min + separator_width,
+                    left.max + right.max + separator_width,
+                )
+            } else {
+                let min = left.min.max(right.min);
+                Rangef::new(min, left.max.min(right.max).max(min))
+            })
+        }
+        _ => None,
+    }
+}
+
+/// Returns `fraction` clamped so that both children of a split of `size` stay within their size ranges.
+///
+/// When there isn't enough room for both, the maximum sizes are given up on first, then the children
+/// share the room in proportion to their minimum sizes.
+fn clamp_fraction(
+    fraction: f32,
+    size: f32,
+    separator_width: f32,
+    (left, right): (Rangef, Rangef),
+) -> f32 {
+    if size <= 0.0 {
+        return fraction;
+    }
+    let available = (size - separator_width).max(0.0);
+    let left_size = fraction * size - separator_width * 0.5;
+    let (min, max) = (
+        left.min.max(available - right.max),
+        left.max.min(available - right.min),
+    );
+    let left_size = if min <= max {
+        left_size.clamp(min, max)
+    } else if left.min + right.min <= available {
+        left_size.clamp(left.min, available - right.min)
+    } else {
+        available * left.min / (left.min + right.min)
+    };
+    ((left_size + separator_width * 0.5) / size).clamp(0.0, 1.0)
+}
+
+/// Adjusts the fractions of the splits in the subtree at `node_index`, whose size along the direction of a resized
+/// split changes from `old` to `new`, so that only the nodes touching the separator of the resized split change size.
+///
+/// `at_end` tells whether the separator is at the right (or bottom) end of the subtree.
+fn keep_outer_sizes<Tab>(
+    tree: &mut Tree<Tab>,
+    node_index: NodeIndex,
+    (old, new): (f32, f32),
+    at_end: bool,
+    horizontal: bool,
+) {
+    if node_index.0 >= tree.len() || new <= 0.0 {
+        return;
+    }
+    let same_direction = tree[node_index].is_horizontal() == horizontal;
+    let (Node::Horizontal(split) | Node::Vertical(split)) = &mut tree[node_index] else {
+        return;
+    };
+    if !same_direction {
+        // Both children touch the separator.
+        keep_outer_sizes(tree, node_index.left(), (old, new), at_end, horizontal);
+        keep_outer_sizes(tree, node_index.right(), (old, new), at_end, horizontal);
+        return;
+    }
+
+    // The child away from the separator keeps its size, and the other one takes up the difference.
+    let (kept, inner) = if at_end {
+        let kept = (split.fraction * old).min(new);
+        split.fraction = kept / new;
+        (kept, node_index.right())
+    } else {
+        let kept = ((1.0 - split.fraction) * old).min(new);
+        split.fraction = 1.0 - kept / new;
+        (kept, node_index.left())
+    };
+    keep_outer_sizes(tree, inner, (old - kept, new - kept), at_end, horizontal);
+}
+
+/// Classifies what dropping the tabs dragged from `src` at `destination` would do.
+fn drop_kind(src: &TreeComponent, destination: &TabDestination) -> DropKind {
+    match destination {
+        TabDestination::Window(_) => DropKind::NewWindow,
+        TabDestination::Node(_, _, TabInsert::Split(_)) => DropKind::Split,
+        TabDestination::Node(surface, node, _) if src.node_address() == (*surface, Some(*node)) => {
+            DropKind::Reorder
+        }
+        TabDestination::Node(..) | TabDestination::EmptySurface(_) => DropKind::IntoLeaf,
     }
 }
@@ -0,0 +1,175 @@
+use egui::Ui;
+
+use crate::{
+    dock_area::{
+        drag_and_drop::{DragData, TreeComponent},
+        drag_context::{SharedDrag, Transfer},
+        state::State,
+    },
+    DockArea, Node, TabDestination, TabInsert, TabViewer,
+};
+
+impl<Tab> DockArea<'_, Tab> {
+    /// Adds the tab dropped into this area from another one sharing its [`DockDragContext`](crate::DockDragContext),
+    /// if there's one, or gives it back if the [`TabViewer`] refuses it.
+    pub(super) fn receive_shared_tab(
+        &mut self,
+        ui: &Ui,
+        tab_viewer: &mut impl TabViewer<Tab = Tab>,
+    ) {
+        let id = self.id;
+        let Some(drag_context) = self.drag_context.as_mut() else {
+            return;
+        };
+        let Some(Transfer {
+            destination,
+            origin,
+            tab,
+            ..
+        }) = drag_context
+            .context
+            .transfer
+            .take_if(|transfer| transfer.area == id)
+        else {
+            return;
+        };
+
+        match (drag_context.from_any)(tab).or_else(|tab| tab_viewer.accept_foreign_tab(tab)) {
+            Ok(tab) => {
+                // The layout may have changed since the tab was dropped.
+                let destination = match destination {
+                    TabDestination::Node(surface, node, insert)
+                        if self.dock_state.is_surface_valid(surface)
+                            && node.0 < self.dock_state[surface].len() =>
+                    {
+                        match &self.dock_state[surface][node] {
+                            Node::Leaf(leaf) => {
+                                let insert = match insert {
+                                    TabInsert::Insert(index) if index.0 > leaf.len() => {
+                                        TabInsert::Append
+                                    }
+                                    insert => insert,
+                                };
+                                self.new_focused = Some((surface, node));
+                                Some(TabDestination::Node(surface, node, insert))
+                            }
+                            _ => None,
+                        }
+                    }
+                    TabDestination::EmptySurface(surface)
+                        if self.dock_state.is_surface_valid(surface)
+                            && self.dock_state[surface].is_empty() =>
+                    {
+                        Some(destination)
+                    }
+                    TabDestination::Window(_) => Some(destination),
+                    _ => None,
+                };
+                match destination {
+                    Some(destination) => self.dock_state.accept_external_drop(destination, tab),
+                    None => self.dock_state.push_to_first_leaf(tab),
+                }
+            }
+            Err(tab) => {
+                drag_context.context.transfer = Some(Transfer {
+                    area: origin.0,
+                    destination: origin.1,
+                    origin: (id, destination),
+                    tab,
+                });
+                ui.ctx().request_repaint();
+            }
+        }
+    }
+
+    /// Shares the tab dragged in this area with the other areas of its [`DockDragContext`](crate::DockDragContext),
+    /// and hands it over to the one it's dropped into, if that's another one.
+    ///
+    /// Returns the dragged tab this area shows overlays for, which may be dragged in another area.
+    pub(super) fn share_drag(
+        &mut self,
+        ui: &Ui,
+        state: &mut State,
+        drag_data: Option<DragData>,
+        hovered: bool,
+        tab_viewer: &impl TabViewer<Tab = Tab>,
+    ) -> Option<DragData> {
+        let id = self.id;
+        let released = ui.input(|i| i.pointer.primary_released());
+        let drag_context = self.drag_context.as_mut().unwrap();
+
+        let Some(drag_data) = drag_data else {
+            let context = &mut drag_context.context;
+            if context.drag.as_ref().is_some_and(|drag| drag.area == id) {
+                // The tab was removed while it was dragged.
+                context.drag = None;
+            }
+            if context.drop.is_some_and(|(area, _)| area == id) {
+                context.drop = None;
+            }
+            let drag = context.drag.clone()?;
+            if state.drag_start.is_none() {
+                state.drag_start = ui.input(|i| i.pointer.hover_pos());
+            }
+            let (surface, node, tab) = drag.tab;
+            return Some(DragData {
+                src: TreeComponent::Tab(surface, node, tab),
+                rect: drag.rect,
+                foreign: Some(drag),
+            });
+        };
+
+        let TreeComponent::Tab(surface, node, tab) = drag_data.src else {
+            return Some(drag_data);
+        };
+        // Groups of selected tabs stay within their area.
+        if state.selected_group((surface, node, tab)).is_some() {
+            return Some(drag_data);
+        }
+        let Node::Leaf(leaf) = &mut self.dock_state[surface][node] else {
+            return Some(drag_data);
+        };
+        let context = &mut drag_context.context;
+        context.drag = Some(SharedDrag {
+            area: id,
+            tab: (surface, node, tab),
+            rect: drag_data.rect,
+            allowed_in_windows: tab_viewer.allowed_in_windows(&mut leaf.tabs[tab.0]),
+        });
+        if !released {
+            return Some(drag_data);
+        }
+
+        context.drag = None;
+        let Some((area, destination)) = context
+            .drop
+            .take()
+            .filter(|(area, _)| *area != id && !hovered)
+        else {
+            return Some(drag_data);
+        };
+        if let Some(tab_value) = self.dock_state.remove_tab((surface, node, tab)) {
+            context.transfer = Some(Transfer {
+                area,
+                destination,
+                origin: (
+                    id,
+                    TabDestination::Node(surface, node, TabInsert::Insert(tab)),
+                ),
+                tab: (drag_context.into_any)(tab_value),
+            });
+            state.selection = None;
+            ui.ctx().request_repaint();
+        }
+        None
+    }
+
+    /// Tells the other areas sharing the [`DockDragContext`](crate::DockDragContext) where the tab dragged in one of
+    /// them lands in this area if it's dropped now.
+    pub(super) fn share_drop(&mut self, destination: Option<TabDestination>) {
+        let id = self.id;
+        if let Some(drag_context) = self.drag_context.as_mut() {
+            drag_context.context.drop = destination.map(|destination| (id, destination));
+        }
+    }
+}
@@ -0,0 +1,74 @@
+use egui::{KeyboardShortcut, Ui};
+
+use crate::{DockArea, NodeIndex, SurfaceIndex};
+
+impl<Tab> DockArea<'_, Tab> {
+    /// Handles the [`Shortcuts`](crate::Shortcuts) of the style, cycling through the tabs of the focused leaf
+    /// and through the leaves, skipping the ones of minimized and collapsed windows.
+    pub(super) fn handle_shortcuts(&mut self, ui: &Ui) {
+        let Some((surface_index, node_index)) = self.dock_state.focused_leaf() else {
+            return;
+        };
+        let shortcuts = &self.style.as_ref().unwrap().shortcuts;
+        // The shortcuts with shift come first, since the ones without it match too when shift is held.
+        let pressed = |shortcut: &Option<KeyboardShortcut>| {
+            shortcut.is_some_and(|shortcut| ui.input_mut(|i| i.consume_shortcut(&shortcut)))
+        };
+        let leaf = (surface_index, node_index);
+        let tab = if pressed(&shortcuts.previous_tab) {
+            self.dock_state.previous_tab(leaf, true)
+        } else if pressed(&shortcuts.next_tab) {
+            self.dock_state.next_tab(leaf, true)
+        } else {
+            None
+        };
+        let leaf_step = if pressed(&shortcuts.previous_leaf) {
+            -1
+        } else if pressed(&shortcuts.next_leaf) {
+            1
+        } else {
+            0
+        };
+
+        if let Some(tab) = tab {
+            self.dock_state
+                .set_active_tab((surface_index, node_index, tab));
+        }
+
+        // A maximized leaf hides all the others.
+        if leaf_step != 0 && self.dock_state.maximized_leaf().is_none() {
+            // The leaves of hidden windows can't be seen, so they're left out unless one of them is focused.
+            let hidden: Vec<SurfaceIndex> = self
+                .dock_state
+                .iter_windows()
+                .filter(|&(surface, tree, window_state)| {
+                    surface != surface_index && (window_state.is_minimized() || tree.is_collapsed())
+                })
+                .map(|(surface, _, _)| surface)
+                .collect();
+            let leaves: Vec<(SurfaceIndex, NodeIndex)> = self
+                .dock_state
+                .iter_surfaces()
+                .enumerate()
+                .filter(|(surface_index, _)| !hidden.contains(&SurfaceIndex(*surface_index)))
+                .flat_map(|(surface_index, surface)| {
+                    surface
+                        .iter_nodes()
+                        .enumerate()
+                        .filter(|(_, node)| node.is_leaf())
+                        .map(move |(node_index, _)| {
+                            (SurfaceIndex(surface_index), NodeIndex(node_index))
+                        })
+                })
+                .collect();
+            if let Some(position) = leaves
+                .iter()
+                .position(|&leaf| leaf == (surface_index, node_index))
+            {
+                let len = leaves.len() as isize;
+                let next = (position as isize + leaf_step).rem_euclid(len) as usize;
+                self.new_focused = Some(leaves[next]);
+            }
+        }
+    }
+}
@@ -0,0 +1,525 @@
+use egui::{
+    emath::{Rot2, TSTransform},
+    epaint::Tessellator,
+    layers::ShapeIdx,
+    pos2, vec2, Color32, Id, Key, LayerId, Mesh, NumExt, Pos2, Rect, Response, Shape, Ui,
+    UiBuilder,
+};
+
+use super::leaf::transpose;
+use crate::{
+    dock_area::{
+        drag_and_drop::{DragData, TreeComponent},
+        state::State,
+        tab_removal::{ForcedRemoval, TabRemoval},
+    },
+    ActivationTrigger, DockArea, NodeIndex, OverflowMode, ReclickAction, Style, SurfaceIndex,
+    TabAddAlign, TabIndex, TabViewer,
+};
+
+/// How much room the buttons and widgets at the ends of a tab bar take up along it, in either orientation.
+pub(super) struct TabBarEnds {
+    collapse_button: f32,
+    drag_handle: f32,
+    leading_ui: f32,
+    scroll_button: f32,
+    overflow_menu: f32,
+    add_button: f32,
+    trailing_ui: f32,
+    close_all_button: f32,
+}
+
+impl TabBarEnds {
+    /// The length taken up before the tabs.
+    pub(super) fn leading(&self) -> f32 {
+        self.collapse_button + self.drag_handle + self.leading_ui + self.scroll_button
+    }
+
+    /// The length taken up after the tabs.
+    pub(super) fn trailing(&self) -> f32 {
+        self.scroll_button
+            + self.overflow_menu
+            + self.add_button
+            + self.trailing_ui
+            + self.close_all_button
+    }
+}
+
+impl<Tab> DockArea<'_, Tab> {
+    /// Returns how much room the buttons and widgets at the ends of the tab bar of a leaf take up.
+    ///
+    /// The widgets of the [`TabViewer`] get as much room as they took up in the last frame, and the buttons
+    /// reaching the hidden tabs are shown as long as the tabs overflowed in the last frame.
+    pub(super) fn tab_bar_ends(
+        &self,
+        ui: &Ui,
+        (surface_index, node_index): (SurfaceIndex, NodeIndex),
+        tab_viewer: &impl TabViewer<Tab = Tab>,
+        style: &Style,
+    ) -> TabBarEnds {
+        let size_if = |shown: bool, size: f32| if shown { size } else { 0.0 };
+        let collapse_button = self.show_leaf_collapse_buttons
+            && self.dock_state[surface_index][node_index]
+                .iter_tabs()
+                .all(|tab| tab_viewer.is_collapsible(tab));
+        let (leading_ui, trailing_ui) = ui
+            .data(|data| {
+                data.get_temp::<(f32, f32)>(self.tab_bar_ui_id((surface_index, node_index)))
+            })
+            .unwrap_or_default();
+        let overflowed = self.dock_state[surface_index][node_index]
+            .get_leaf()
+            .and_then(|leaf| leaf.tab_overflow)
+            .unwrap_or(false);
+        let overflow_mode = style.tab_bar.overflow_mode;
+        TabBarEnds {
+            collapse_button: size_if(collapse_button, Style::TAB_COLLAPSE_BUTTON_SIZE),
+            drag_handle: size_if(self.show_leaf_drag_handles, Style::TAB_DRAG_HANDLE_SIZE),
+            leading_ui,
+            scroll_button: size_if(
+                overflowed && overflow_mode == OverflowMode::Scroll,
+                Style::TAB_SCROLL_BUTTON_SIZE,
+            ),
+            overflow_menu: size_if(
+                overflowed && overflow_mode == OverflowMode::Menu,
+                Style::TAB_OVERFLOW_BUTTON_SIZE,
+            ),
+            add_button: size_if(
+                self.show_add_buttons && tab_viewer.show_add_button(surface_index, node_index),
+                Style::TAB_ADD_BUTTON_SIZE,
+            ),
+            trailing_ui,
+            close_all_button: size_if(
+                self.show_leaf_close_all_buttons,
+                Style::TAB_CLOSE_ALL_BUTTON_SIZE,
+            ),
+        }
+    }
+
+    /// Where the widths of the widgets of the [`TabViewer`] at the ends of a tab bar are kept between frames.
+    fn tab_bar_ui_id(&self, (surface_index, node_index): (SurfaceIndex, NodeIndex)) -> Id {
+        self.id
+            .with((surface_index, "surface"))
+            .with((node_index, "node"))
+            .with("tab_bar_ui")
+    }
+
+    /// Shows the buttons and widgets at the ends of a tab bar spanning `bar_rect`, around the tabs shown in
+    /// `clip_rect`, which are `tabs_length` long: the collapse button, drag handle and leading widgets first, then a
+    /// scroll button on either side of the tabs, the overflow menu, the add button, the trailing widgets and the close
+    /// all button last.
+    ///
+    /// They're laid out from left to right, or from top to bottom in a `vertical` tab bar.
+    #[allow(clippy::too_many_arguments)]
+    pub(super) fn tab_bar_ends_ui(
+        &mut self,
+        ui: &mut Ui,
+        state: &mut State,
+        (surface_index, node_index): (SurfaceIndex, NodeIndex),
+        tab_viewer: &mut impl TabViewer<Tab = Tab>,
+        fade_style: Option<&Style>,
+        ends: &TabBarEnds,
+        (bar_rect, clip_rect, tabs_length): (Rect, Rect, f32),
+        tab_rects: &[Rect],
+        vertical: bool,
+        collapsed: bool,
+    ) {
+        // The rects are worked out as in a horizontal tab bar, and transposed back in a vertical one.
+        let along = |rect: Rect| if vertical { transpose(rect) } else { rect };
+        let (bar, clip) = (along(bar_rect), along(clip_rect));
+        // Horizontal buttons stop short of the line below the tabs.
+        let shrink = if vertical { 0.0 } else { 2.0 };
+
+        if ends.scroll_button > 0.0 {
+            self.tab_scroll_buttons(
+                ui,
+                (surface_index, node_index),
+                clip_rect,
+                tabs_length,
+                fade_style,
+                vertical,
+            );
+        }
+
+        if ends.overflow_menu > 0.0 {
+            self.tab_overflow_menu(
+                ui,
+                (surface_index, node_index),
+                tab_viewer,
+                clip_rect,
+                tab_rects,
+                fade_style,
+                vertical,
+            );
+        }
+
+        if ends.add_button > 0.0 {
+            let style = fade_style.unwrap_or_else(|| self.style.as_ref().unwrap());
+            let offset = match style.buttons.add_tab_align {
+                TabAddAlign::Left => (clip.width() - tabs_length).at_least(0.0),
+                TabAddAlign::Right => 0.0,
+            } + ends.close_all_button
+                + ends.trailing_ui;
+            let rect = Rect::from_min_max(
+                pos2(bar.right() - ends.add_button - offset, bar.top()),
+                pos2(bar.right() - offset, bar.bottom() - shrink),
+            );
+            self.tab_plus(
+                ui,
+                surface_index,
+                node_index,
+                tab_viewer,
+                along(rect),
+                vertical,
+                fade_style,
+            );
+        }
+
+        if ends.close_all_button > 0.0 {
+            // Current leaf contains non-closable tabs, or only pinned ones, which are left open.
+            let disabled = self.dock_state[surface_index][node_index]
+                .get_leaf_mut()
+                .map(|leaf| {
+                    let pinned = leaf.pinned_count();
+                    pinned == leaf.tabs.len()
+                        || !leaf.tabs[pinned..]
+                            .iter()
+                            .all(|tab| tab_viewer.is_closeable(tab))
+                })
+                .expect("This node must be a leaf");
+
+            // Current window contains non-closable or pinned tabs.
+            let close_window_disabled = disabled
+                || !self.dock_state[surface_index].iter_mut().all(|node| {
+                    node.get_leaf_mut().is_none_or(|leaf| {
+                        leaf.pinned_count() == 0
+                            && leaf.tabs.iter_mut().all(|tab| tab_viewer.is_closeable(tab))
+                    })
+                });
+
+            let rect = Rect::from_min_max(
+                pos2(bar.right() - ends.close_all_button, bar.top()),
+                pos2(bar.right(), bar.bottom() - shrink),
+            );
+            self.tab_close_all(
+                ui,
+                surface_index,
+                node_index,
+                along(rect),
+                vertical,
+                fade_style,
+                disabled,
+                close_window_disabled,
+            )
+        }
+
+        if ends.collapse_button > 0.0 {
+            let rect = bar.with_max_x(bar.left() + ends.collapse_button);
+            self.tab_collapse(
+                ui,
+                (surface_index, node_index),
+                tab_viewer,
+                along(rect),
+                vertical,
+                fade_style,
+                collapsed,
+            )
+        }
+
+        if ends.drag_handle > 0.0 {
+            let left = bar.left() + ends.collapse_button;
+            let rect = Rect::from_x_y_ranges(left..=left + ends.drag_handle, bar.y_range());
+            self.leaf_drag_handle(
+                ui,
+                state,
+                (surface_index, node_index),
+                along(rect),
+                vertical,
+                fade_style,
+            );
+        }
+
+        // The leading widgets follow the buttons on the left, the trailing ones precede the close all button.
+        let leading = bar.with_min_x(bar.left() + ends.collapse_button + ends.drag_handle);
+        let trailing = bar.with_max_x(bar.right() - ends.close_all_button);
+        let lengths = (
+            self.tab_bar_custom_ui(
+                ui,
+                (surface_index, node_index),
+                tab_viewer,
+                along(leading),
+                ends.leading_ui,
+                false,
+                vertical,
+            ),
+            self.tab_bar_custom_ui(
+                ui,
+                (surface_index, node_index),
+                tab_viewer,
+                along(trailing),
+                ends.trailing_ui,
+                true,
+                vertical,
+            ),
+        );
+        if lengths != (ends.leading_ui, ends.trailing_ui) {
+            let id = self.tab_bar_ui_id((surface_index, node_index));
+            ui.data_mut(|data| data.insert_temp(id, lengths));
+            ui.ctx().request_repaint();
+        }
+    }
+
+    /// Handles what clicking the button of a tab does, apart from its close button and context menu:
+    /// activating and selecting it, closing it with a middle click if it's `closeable`,
+    /// and maximizing its leaf or focusing its content.
+    ///
+    /// Tabs of `vertical` tab bars can't be renamed, so double-clicking them maximizes their leaf regardless of
+    /// [`DockArea::rename_tabs_on_double_click`].
+    #[allow(clippy::too_many_arguments)]
+    pub(super) fn tab_button_interaction(
+        &mut self,
+        ui: &Ui,
+        state: &mut State,
+        (surface_index, node_index, tab_index): (SurfaceIndex, NodeIndex, TabIndex),
+        (id, title_id): (Id, Id),
+        response: &Response,
+        closeable: bool,
+        vertical: bool,
+        tab_viewer: &mut impl TabViewer<Tab = Tab>,
+    ) {
+        let double_tapped = self.double_tapped(ui, state, response);
+        let leaf = self.dock_state[surface_index][node_index]
+            .get_leaf_mut()
+            .expect("This node must be a leaf");
+
+        let activated = match self.tab_activation {
+            ActivationTrigger::OnPress => {
+                response.is_pointer_button_down_on() && ui.input(|i| i.pointer.primary_pressed())
+            }
+            ActivationTrigger::OnRelease => response.clicked(),
+        };
+
+        if self.multi_tab_selection && activated {
+            let modifiers = ui.input(|i| i.modifiers);
+            state.select_tab(
+                (surface_index, node_index),
+                tab_index,
+                leaf.active,
+                modifiers,
+            );
+        }
+
+        // When tabs are activated on press, the click completing that press doesn't count as a reclick.
+        if activated
+            && self.tab_activation == ActivationTrigger::OnPress
+            && leaf.active != tab_index
+        {
+            state.activated_on_press = Some(id);
+        }
+        let reclicked =
+            response.clicked() && leaf.active == tab_index && state.activated_on_press != Some(id);
+        if response.clicked() || response.drag_stopped() {
+            state.activated_on_press = None;
+        }
+        if activated
+            || (ui.memory(|m| m.has_focus(title_id))
+                && ui.input(|i| i.key_pressed(Key::Enter) || i.key_pressed(Key::Space)))
+        {
+            leaf.active = tab_index;
+            self.new_focused = Some((surface_index, node_index));
+        }
+        let tab = &mut leaf.tabs[tab_index.0];
+        if reclicked {
+            tab_viewer.on_active_tab_clicked(tab);
+        }
+
+        tab_viewer.on_tab_button(tab, response);
+
+        if closeable && response.middle_clicked() {
+            self.to_remove.push(TabRemoval::Tab(
+                surface_index,
+                node_index,
+                tab_index,
+                ForcedRemoval(false),
+            ));
+        }
+
+        let double_clicked = self.maximize_on_double_click
+            && (vertical || !self.rename_tabs_on_double_click)
+            && response.double_clicked();
+        if double_tapped || double_clicked {
+            self.dock_state
+                .toggle_maximized_leaf((surface_index, node_index));
+        } else if reclicked {
+            match self.active_tab_reclick {
+                ReclickAction::None => (),
+                ReclickAction::FocusContent => {
+                    ui.memory_mut(|m| m.surrender_focus(title_id));
+                }
+                ReclickAction::ToggleMaximize => self
+                    .dock_state
+                    .toggle_maximized_leaf((surface_index, node_index)),
+            }
+        }
+    }
+
+    /// Starts dragging a tab or a whole leaf once the pointer moved far enough from where the drag started, which
+    /// is further along the tab bar than across it. The shapes painted in the `layer_id` of a dragged tab follow the
+    /// pointer.
+    pub(super) fn drag_from_tab_bar(
+        &self,
+        ui: &Ui,
+        state: &mut State,
+        src: TreeComponent,
+        layer_id: Option<LayerId>,
+        vertical: bool,
+    ) {
+        let Some(pointer_pos) = ui.ctx().pointer_interact_pos() else {
+            return;
+        };
+        let start = *state.drag_start.get_or_insert(pointer_pos);
+        let delta = pointer_pos - start;
+        let (along, across) = if vertical {
+            (delta.y, delta.x)
+        } else {
+            (delta.x, delta.y)
+        };
+        if along.abs() <= 30.0 && across.abs() <= 6.0 {
+            return;
+        }
+        if let Some(layer_id) = layer_id {
+            ui.ctx()
+                .transform_layer_shapes(layer_id, TSTransform::new(delta, 1.0));
+        }
+        let (TreeComponent::Tab(surface_index, node_index, _)
+        | TreeComponent::Node(surface_index, node_index)) = src
+        else {
+            return;
+        };
+        ui.memory_mut(|mem| {
+            mem.data.insert_temp(
+                self.id.with("drag_data"),
+                Some(DragData {
+                    src,
+                    rect: self.dock_state[surface_index][node_index].rect().unwrap(),
+                    foreign: None,
+                }),
+            );
+        });
+    }
+
+    /// Returns how far the mouse wheel scrolls the tabs, along either axis.
+    pub(super) fn tab_bar_scroll_delta(&self, ui: &Ui) -> f32 {
+        let animations_enabled = self.style.as_ref().unwrap().animations_enabled;
+        ui.input(|i| {
+            let delta = if animations_enabled {
+                i.smooth_scroll_delta
+            } else {
+                i.raw_scroll_delta
+            };
+            delta.x + delta.y
+        })
+    }
+
+    /// Shows [`TabViewer::title_ui`] in a child of `ui` built by `builder`, returning whether it showed anything.
+    ///
+    /// Clicks and drags go to the tab, so the content can't get in the way of dragging it. How wide the content is
+    /// is kept at `title_ui_id` to lay out the tab with in the next frame.
+    pub(super) fn tab_title_ui(
+        ui: &mut Ui,
+        builder: UiBuilder,
+        (title_ui_id, clip_rect): (Id, Rect),
+        text_color: Color32,
+        (tab_viewer, tab): (&mut impl TabViewer<Tab = Tab>, &mut Tab),
+    ) -> bool {
+        let previous_width = ui.data(|data| data.get_temp::<f32>(title_ui_id));
+        let mut title_ui = ui.new_child(builder.id_salt(title_ui_id));
+        title_ui.disable();
+        title_ui.set_opacity(ui.opacity());
+        title_ui.set_clip_rect(clip_rect);
+        title_ui.visuals_mut().override_text_color = Some(text_color);
+        tab_viewer.title_ui(&mut title_ui, tab);
+
+        let new_width = title_ui.min_rect().width();
+        let shown = new_width > 0.0;
+        if shown && previous_width != Some(new_width) {
+            ui.data_mut(|data| data.insert_temp(title_ui_id, new_width));
+            ui.ctx().request_repaint();
+        } else if !shown && previous_width.is_some() {
+            ui.data_mut(|data| data.remove::<f32>(title_ui_id));
+            ui.ctx().request_repaint();
+        }
+        shown
+    }
+}
+
+/// Rotates the shapes painted in a layer from the `start` index on a quarter turn about `origin`, clockwise if
+/// `angle` is positive, clipping them to `clip_rect`.
+///
+/// Shapes can't be rotated directly, so they're tessellated into meshes first. Callbacks are left as they are.
+pub(super) fn rotate_shapes(
+    ui: &Ui,
+    layer_id: LayerId,
+    start: usize,
+    (origin, angle): (Pos2, f32),
+    clip_rect: Rect,
+) {
+    fn rotated(shape: Shape, tessellator: &mut Tessellator, rot: Rot2, origin: Pos2) -> Shape {
+        match shape {
+            Shape::Vec(shapes) => Shape::Vec(
+                shapes
+                    .into_iter()
+                    .map(|shape| rotated(shape, tessellator, rot, origin))
+                    .collect(),
+            ),
+            Shape::Callback(_) => shape,
+            shape => {
+                let mut mesh = Mesh::default();
+                tessellator.tessellate_shape(shape, &mut mesh);
+                mesh.rotate(rot, origin);
+                Shape::mesh(mesh)
+            }
+        }
+    }
+
+    let ctx = ui.ctx();
+    let end = ctx.graphics_mut(|graphics| graphics.entry(layer_id).next_idx().0);
+    if start == end {
+        return;
+    }
+    let (font_tex_size, prepared_discs) = ctx.fonts(|fonts| {
+        (
+            fonts.font_image_size(),
+            fonts.texture_atlas().lock().prepared_discs(),
+        )
+    });
+    let options = ctx.tessellation_options(|options| *options);
+    let mut tessellator = Tessellator::new(
+        ctx.pixels_per_point(),
+        options,
+        font_tex_size,
+        prepared_discs,
+    );
+    let rot = Rot2::from_angle(angle);
+    ctx.graphics_mut(|graphics| {
+        let list = graphics.entry(layer_id);
+        for index in start..end {
+            list.mutate_shape(ShapeIdx(index), |clipped| {
+                let shape = std::mem::replace(&mut clipped.shape, Shape::Noop);
+                clipped.shape = rotated(shape, &mut tessellator, rot, origin);
+                clipped.clip_rect = clip_rect;
+            });
+        }
+    });
+}
+
+/// Returns the index the next shape painted in a layer gets.
+pub(super) fn next_shape_index(ui: &Ui, layer_id: LayerId) -> usize {
+    ui.ctx()
+        .graphics_mut(|graphics| graphics.entry(layer_id).next_idx().0)
+}
+
+/// Returns the upright rect a [`TabViewer::title_ui`] rotated a quarter turn to fit in `rect` is laid out in.
+pub(super) fn upright_rect(rect: Rect) -> Rect {
+    Rect::from_center_size(rect.center(), vec2(rect.height(), rect.width()))
+}
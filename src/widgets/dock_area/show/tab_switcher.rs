@@ -0,0 +1,129 @@
+use egui::{Align2, Area, Frame, Id, Key, Modifiers, Order, Ui, WidgetText};
+
+use crate::{dock_area::state::State, DockArea, NodeIndex, SurfaceIndex, TabIndex, TabViewer};
+
+impl<Tab> DockArea<'_, Tab> {
+    /// Moves the active tab of the focused leaf to the front of the most recently used tabs.
+    pub(super) fn update_recently_used_tabs(
+        &mut self,
+        state: &mut State,
+        tab_viewer: &mut impl TabViewer<Tab = Tab>,
+    ) {
+        let Some((surface, node)) = self.dock_state.focused_leaf() else {
+            return;
+        };
+        let Some(leaf) = self.dock_state[surface][node].get_leaf_mut() else {
+            return;
+        };
+        let Some(tab) = leaf.tabs.get_mut(leaf.active.0) else {
+            return;
+        };
+        let id = tab_viewer.id(tab);
+        if state.recently_used.first() != Some(&id) {
+            state.recently_used.retain(|&used| used != id);
+            state.recently_used.insert(0, id);
+        }
+    }
+
+    /// Handles holding ctrl and pressing tab to browse the most recently used tabs,
+    /// activating the highlighted one once ctrl is released.
+    pub(super) fn show_tab_switcher(
+        &mut self,
+        ui: &Ui,
+        state: &mut State,
+        tab_viewer: &mut impl TabViewer<Tab = Tab>,
+    ) {
+        let (backwards, forwards) = ui.input_mut(|i| {
+            (
+                i.consume_key(Modifiers::CTRL | Modifiers::SHIFT, Key::Tab),
+                i.consume_key(Modifiers::CTRL, Key::Tab),
+            )
+        });
+        if state.switcher.is_none() && !backwards && !forwards {
+            return;
+        }
+
+        let tabs = self.recently_used_tabs(state, tab_viewer);
+        if tabs.is_empty() {
+            state.switcher = None;
+            return;
+        }
+
+        // Opening the switcher highlights the tab used before the current one.
+        let highlighted = match state.switcher {
+            None if backwards => tabs.len() - 1,
+            None => 1 % tabs.len(),
+            Some(highlighted) if forwards => (highlighted + 1) % tabs.len(),
+            Some(highlighted) if backwards => (highlighted + tabs.len() - 1) % tabs.len(),
+            Some(highlighted) => highlighted.min(tabs.len() - 1),
+        };
+        state.switcher = Some(highlighted);
+
+        if ui.input(|i| i.key_pressed(Key::Escape)) {
+            state.switcher = None;
+            return;
+        }
+
+        let mut chosen = None;
+        Area::new(self.id.with("tab_switcher"))
+            .order(Order::Foreground)
+            .anchor(Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ui.ctx(), |ui| {
+                Frame::popup(ui.style()).show(ui, |ui| {
+                    for (index, (_, title)) in tabs.iter().enumerate() {
+                        if ui
+                            .selectable_label(index == highlighted, title.clone())
+                            .clicked()
+                        {
+                            chosen = Some(index);
+                        }
+                    }
+                });
+            });
+
+        if !ui.input(|i| i.modifiers.ctrl) {
+            chosen = Some(highlighted);
+        }
+        if let Some(index) = chosen {
+            let (surface, node, tab) = tabs[index].0;
+            self.dock_state.set_active_tab((surface, node, tab));
+            self.new_focused = Some((surface, node));
+            state.switcher = None;
+        }
+    }
+
+    /// Returns the location and title of every tab, the most recently used ones first.
+    fn recently_used_tabs(
+        &mut self,
+        state: &State,
+        tab_viewer: &mut impl TabViewer<Tab = Tab>,
+    ) -> Vec<((SurfaceIndex, NodeIndex, TabIndex), WidgetText)> {
+        let mut tabs: Vec<(Id, _, _)> = Vec::new();
+        for &surface in self.dock_state.valid_surface_indices().iter() {
+            for node in self.dock_state[surface].breadth_first_index_iter() {
+                let Some(leaf) = self.dock_state[surface][node].get_leaf_mut() else {
+                    continue;
+                };
+                for (index, tab) in leaf.tabs.iter_mut().enumerate() {
+                    tabs.push((
+                        tab_viewer.id(tab),
+                        (surface, node, TabIndex(index)),
+                        tab_viewer.title(tab),
+                    ));
+                }
+            }
+        }
+
+        // Tabs which were never used keep their order, after the used ones.
+        tabs.sort_by_key(|(id, _, _)| {
+            state
+                .recently_used
+                .iter()
+                .position(|used| used == id)
+                .unwrap_or(usize::MAX)
+        });
+        tabs.into_iter()
+            .map(|(_, location, title)| (location, title))
+            .collect()
+    }
+}
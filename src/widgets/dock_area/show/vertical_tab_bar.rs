@@ -0,0 +1,421 @@
+use egui::{
+    epaint::TextShape, pos2, vec2, Align, Align2, CornerRadius, CursorIcon, LayerId, Layout,
+    NumExt, Order, Rect, Sense, Stroke, StrokeKind, TextStyle, TextWrapMode, Ui, UiBuilder, Vec2,
+};
+use std::f32::consts::FRAC_PI_2;
+
+use super::{
+    leaf::{interaction_style, tab_insertion_caret, transpose},
+    tab_bar::{next_shape_index, rotate_shapes, upright_rect},
+};
+use crate::{
+    dock_area::{
+        drag_and_drop::TreeComponent,
+        state::State,
+        tab_removal::{ForcedRemoval, TabRemoval},
+    },
+    utils::{rect_set_size_centered, rect_stroke_box},
+    CloseButtonVisibility, DockArea, NodeIndex, OverflowMode, Style, SurfaceIndex, TabBarPosition,
+    TabIndex, TabViewer,
+};
+
+impl<Tab> DockArea<'_, Tab> {
+    /// Shows the tab bar of a leaf along its left or right side, filling the `ui`, and returns its rect.
+    ///
+    /// The tabs are stacked from the top, with their titles rotated to read along the tab bar,
+    /// or showing their [`TabViewer::tab_icon`] instead if
+    /// [`TabBarStyle::vertical_icons_only`](crate::TabBarStyle::vertical_icons_only) is set.
+    /// The buttons and widgets at the ends of the tab bar are the same as in a horizontal one, from top to bottom.
+    #[allow(clippy::too_many_arguments)]
+    pub(super) fn vertical_tab_bar(
+        &mut self,
+        ui: &mut Ui,
+        state: &mut State,
+        (surface_index, node_index): (SurfaceIndex, NodeIndex),
+        tab_viewer: &mut impl TabViewer<Tab = Tab>,
+        fade_style: Option<&Style>,
+        position: TabBarPosition,
+    ) -> Rect {
+        let style = fade_style.unwrap_or_else(|| self.style.as_ref().unwrap());
+        let bar_rect = ui.max_rect();
+        let thickness = bar_rect.width();
+        let clear_background = self.dock_state[surface_index][node_index]
+            .get_leaf()
+            .and_then(|leaf| leaf.tabs.get(leaf.active.0))
+            .is_none_or(|tab| tab_viewer.clear_tab_bar_background(tab));
+        if clear_background {
+            match &style.tab_bar.bg_painter {
+                Some(bg_painter) => bg_painter.paint(ui.painter(), bar_rect),
+                None => {
+                    ui.painter().rect_filled(
+                        bar_rect,
+                        style.tab_bar.corner_radius,
+                        style.tab_bar.bg_fill,
+                    );
+                }
+            }
+        }
+
+        // Draw the line separating the tab bar from the content, which the active tab covers.
+        let px = ui.ctx().pixels_per_point().recip();
+        let content_side = if position == TabBarPosition::Left {
+            bar_rect.right() - px
+        } else {
+            bar_rect.left()
+        };
+        ui.painter().vline(
+            content_side,
+            bar_rect.y_range(),
+            (px, style.tab_bar.hline_color),
+        );
+
+        // Reserve space for the buttons and widgets at the ends of the tab bar.
+        let ends = self.tab_bar_ends(ui, (surface_index, node_index), tab_viewer, style);
+        let mut tabs_rect = bar_rect.with_min_y(bar_rect.top() + ends.leading());
+        tabs_rect.set_bottom((bar_rect.bottom() - ends.trailing()).at_least(tabs_rect.top()));
+
+        let tabs_ui = &mut ui.new_child(
+            UiBuilder::new()
+                .max_rect(tabs_rect)
+                .id_salt("vertical_tabs"),
+        );
+        tabs_ui.set_clip_rect(tabs_rect.intersect(ui.clip_rect()));
+
+        let focused = self.dock_state.focused_leaf() == Some((surface_index, node_index));
+        let (tabs_len, scroll) = {
+            let leaf = self.dock_state[surface_index][node_index]
+                .get_leaf()
+                .expect("This node must be a leaf");
+            (leaf.tabs.len(), leaf.scroll)
+        };
+        let style = fade_style.unwrap_or_else(|| self.style.as_ref().unwrap());
+        let shrunk_length = (style.tab_bar.overflow_mode == OverflowMode::Shrink).then(|| {
+            let spacing = style.tab.spacing * (tabs_len as f32 - 1.0).at_least(0.0);
+            ((tabs_rect.height() - spacing) / tabs_len as f32).at_least(0.0)
+        });
+
+        let y_spacing = 8.0;
+        let mut y = tabs_rect.top() + scroll;
+        let mut tab_rects = Vec::with_capacity(tabs_len);
+        for tab_index in 0..tabs_len {
+            let id = self
+                .id
+                .with((surface_index, "surface"))
+                .with((node_index, "node"))
+                .with((tab_index, "tab"));
+            let tab_index = TabIndex(tab_index);
+
+            let leaf = self.dock_state[surface_index][node_index]
+                .get_leaf_mut()
+                .expect("This node must be a leaf");
+            let pinned = leaf.is_pinned(tab_index);
+            let is_active = leaf.active == tab_index;
+            let tab = &mut leaf.tabs[tab_index.0];
+            let style = fade_style.unwrap_or_else(|| self.style.as_ref().unwrap());
+            let mut tab_style = tab_viewer
+                .tab_style_override(tab, &style.tab)
+                .unwrap_or_else(|| style.tab.clone());
+            if let Some(color) = tab_viewer.tab_color(tab) {
+                tab_style.tint(color);
+            }
+            let icon = (style.tab_bar.vertical_icons_only
+                || (pinned && tab_style.pinned_icon_only))
+                .then(|| tab_viewer.tab_icon(tab))
+                .flatten();
+            let rotated = icon.is_none();
+            let label = icon.unwrap_or_else(|| tab_viewer.title(tab));
+            // The length of the custom title in the last frame, if it was shown.
+            let title_ui_id = id.with("title_ui");
+            let title_ui_length = rotated
+                .then(|| tabs_ui.data(|data| data.get_temp::<f32>(title_ui_id)))
+                .flatten();
+
+            let closeable = self.show_close_buttons
+                && tab_viewer.is_closeable(tab)
+                && !(pinned && tab_style.pinned_icon_only);
+            let close_button_visibility = tab_viewer
+                .close_button_visibility(tab)
+                .unwrap_or(self.close_button_visibility);
+            let close_button_size =
+                if closeable && close_button_visibility != CloseButtonVisibility::Never {
+                    Style::TAB_CLOSE_BUTTON_SIZE.min(thickness)
+                } else {
+                    0.0
+                };
+
+            // The length of a tab along the tab bar is limited like the width of a horizontal one.
+            let maximum_length = match (tab_style.maximum_width, shrunk_length) {
+                (Some(maximum), Some(shrunk)) => Some(maximum.min(shrunk)),
+                (maximum, shrunk) => maximum.or(shrunk),
+            };
+            let wrap_width = if rotated {
+                maximum_length.map_or(f32::INFINITY, |max| {
+                    (max - close_button_size - 2.0 * y_spacing).at_least(0.0)
+                })
+            } else {
+                thickness
+            };
+            let galley = label.into_galley(
+                tabs_ui,
+                Some(TextWrapMode::Truncate),
+                wrap_width,
+                TextStyle::Button,
+            );
+            let text_length = if rotated {
+                title_ui_length.unwrap_or(galley.size().x)
+            } else {
+                galley.size().y
+            };
+            let mut length =
+                (text_length + 2.0 * y_spacing).at_least(if rotated { 0.0 } else { thickness })
+                    + close_button_size;
+            length = length.at_least(tab_style.minimum_width.unwrap_or(0.0));
+            if let Some(maximum_length) = maximum_length {
+                length = length.at_most(maximum_length);
+            }
+
+            let tab_rect = Rect::from_min_size(pos2(bar_rect.left(), y), vec2(thickness, length));
+            y += length + tab_style.spacing;
+            tab_rects.push(tab_rect);
+
+            let mut response = tabs_ui.interact(tab_rect, id, Sense::click_and_drag());
+            let is_being_dragged = tabs_ui.ctx().is_being_dragged(id)
+                && tabs_ui.input(|i| i.pointer.is_decidedly_dragging())
+                && self.draggable_tabs
+                && !pinned;
+            if is_being_dragged {
+                tabs_ui.output_mut(|o| o.cursor_icon = tab_style.cursor_dragged);
+            } else if tabs_ui.ctx().dragged_id().is_none() && self.draggable_tabs {
+                response = response.on_hover_cursor(tab_style.cursor_hovered);
+            }
+            if self.show_tab_name_on_hover {
+                response = response.on_hover_ui(|ui| {
+                    ui.label(tab_viewer.title(tab));
+                });
+            }
+
+            // The dragged tab follows the pointer in a layer above everything else.
+            let layer_id = LayerId::new(Order::Tooltip, id);
+            let painter = if is_being_dragged {
+                tabs_ui.painter().clone().with_layer_id(layer_id)
+            } else {
+                tabs_ui.painter().clone()
+            };
+
+            let is_highlighted = is_active
+                || (self.multi_tab_selection
+                    && state.is_selected((surface_index, node_index, tab_index)));
+            let interaction = interaction_style(
+                &tab_style,
+                &response,
+                (is_active && focused) || is_being_dragged,
+                is_highlighted,
+            );
+            painter.rect_filled(tab_rect, interaction.corner_radius, interaction.bg_fill);
+            painter.rect_stroke(
+                rect_stroke_box(tab_rect, 1.0),
+                interaction.corner_radius,
+                Stroke::new(1.0, interaction.outline_color),
+                StrokeKind::Inside,
+            );
+            if is_active && !is_being_dragged && !tab_style.hline_below_active_tab_name {
+                // Make the tab name area connect with the tab ui area.
+                painter.vline(
+                    content_side,
+                    tab_rect.shrink2(vec2(0.0, 1.5)).y_range(),
+                    Stroke::new(2.0, interaction.bg_fill),
+                );
+            }
+
+            // The close button is where the rotated title ends.
+            let (text_rect, close_rect) = if position == TabBarPosition::Left {
+                let (close, text) =
+                    tab_rect.split_top_bottom_at_y(tab_rect.top() + close_button_size);
+                (text, close)
+            } else {
+                tab_rect.split_top_bottom_at_y(tab_rect.bottom() - close_button_size)
+            };
+            let text_size = galley.size();
+            let text_color = interaction.text_color;
+            let center = text_rect.center();
+            let angle = if position == TabBarPosition::Left {
+                -FRAC_PI_2
+            } else {
+                FRAC_PI_2
+            };
+            // The custom title is laid out upright, and then turned to read along the tab bar.
+            let title_ui_shown = rotated && {
+                let upright = upright_rect(text_rect).shrink2(vec2(y_spacing, 0.0));
+                let rect = Rect::from_center_size(
+                    center,
+                    vec2(title_ui_length.unwrap_or(text_size.x), upright.height()),
+                );
+                let start = next_shape_index(tabs_ui, painter.layer_id());
+                let shown = Self::tab_title_ui(
+                    tabs_ui,
+                    UiBuilder::new()
+                        .layer_id(painter.layer_id())
+                        .max_rect(rect)
+                        .layout(Layout::left_to_right(Align::Center)),
+                    (title_ui_id, upright),
+                    text_color,
+                    (&mut *tab_viewer, &mut *tab),
+                );
+                let clip_rect = text_rect
+                    .shrink2(vec2(0.0, y_spacing))
+                    .intersect(tabs_ui.clip_rect());
+                rotate_shapes(
+                    tabs_ui,
+                    painter.layer_id(),
+                    start,
+                    (center, angle),
+                    clip_rect,
+                );
+                shown
+            };
+            let text_shape = match position {
+                _ if !rotated => TextShape::new(
+                    Align2::CENTER_CENTER
+                        .align_size_within_rect(text_size, text_rect)
+                        .min,
+                    galley,
+                    text_color,
+                ),
+                TabBarPosition::Left => TextShape::new(
+                    pos2(center.x - text_size.y / 2.0, center.y + text_size.x / 2.0),
+                    galley,
+                    text_color,
+                )
+                .with_angle(-FRAC_PI_2),
+                _ => TextShape::new(
+                    pos2(center.x + text_size.y / 2.0, center.y - text_size.x / 2.0),
+                    galley,
+                    text_color,
+                )
+                .with_angle(FRAC_PI_2),
+            };
+            if !title_ui_shown {
+                painter.add(text_shape);
+            }
+
+            let close_button_visible = match close_button_visibility {
+                CloseButtonVisibility::Never => false,
+                CloseButtonVisibility::Always => true,
+                CloseButtonVisibility::HoverTab => response.contains_pointer(),
+                CloseButtonVisibility::ActiveTabOnly => is_active,
+            };
+            let mut close_clicked = false;
+            if close_button_size > 0.0 && close_button_visible {
+                let close_rect =
+                    Rect::from_center_size(close_rect.center(), Vec2::splat(close_button_size));
+                let close_response = tabs_ui
+                    .interact(close_rect, id.with("close-button"), Sense::click())
+                    .on_hover_cursor(CursorIcon::PointingHand);
+                let color = if close_response.hovered() || close_response.has_focus() {
+                    painter.rect_filled(
+                        close_rect,
+                        CornerRadius::ZERO,
+                        style.buttons.close_tab_bg_fill,
+                    );
+                    style.buttons.close_tab_active_color
+                } else {
+                    style.buttons.close_tab_color
+                };
+                let mut x_rect = close_rect;
+                rect_set_size_centered(&mut x_rect, Vec2::splat(Style::TAB_CLOSE_X_SIZE));
+                painter.line_segment(
+                    [x_rect.left_top(), x_rect.right_bottom()],
+                    Stroke::new(1.0, color),
+                );
+                painter.line_segment(
+                    [x_rect.right_top(), x_rect.left_bottom()],
+                    Stroke::new(1.0, color),
+                );
+                close_clicked = close_response.clicked();
+            }
+
+            if is_being_dragged {
+                self.drag_from_tab_bar(
+                    tabs_ui,
+                    state,
+                    TreeComponent::Tab(surface_index, node_index, tab_index),
+                    Some(layer_id),
+                    true,
+                );
+            }
+
+            if close_clicked {
+                self.to_remove.push(TabRemoval::Tab(
+                    surface_index,
+                    node_index,
+                    tab_index,
+                    ForcedRemoval(false),
+                ));
+            }
+
+            if self.tab_context_menus {
+                self.tab_context_menu(
+                    &response,
+                    (surface_index, node_index, tab_index),
+                    tab_viewer,
+                    closeable,
+                );
+            }
+
+            self.tab_button_interaction(
+                tabs_ui,
+                state,
+                (surface_index, node_index, tab_index),
+                (id, id),
+                &response,
+                closeable,
+                true,
+                tab_viewer,
+            );
+        }
+
+        // Scroll along the tab bar with the mouse wheel.
+        let tabs_length = match (tab_rects.first(), tab_rects.last()) {
+            (Some(first), Some(last)) => last.bottom() - first.top(),
+            _ => 0.0,
+        };
+        let overflow = (tabs_length - tabs_rect.height()).at_least(0.0);
+        let scroll_delta = self.tab_bar_scroll_delta(ui);
+        let hovered = ui.rect_contains_pointer(bar_rect);
+
+        self.tab_bar_ends_ui(
+            ui,
+            state,
+            (surface_index, node_index),
+            tab_viewer,
+            fade_style,
+            &ends,
+            (bar_rect, tabs_rect, tabs_length),
+            &tab_rects,
+            true,
+            false,
+        );
+
+        let leaf = self.dock_state[surface_index][node_index]
+            .get_leaf_mut()
+            .expect("This node must be a leaf");
+        leaf.tab_overflow = Some(overflow > 1.0);
+        if hovered {
+            leaf.scroll += scroll_delta;
+        }
+        leaf.scroll = leaf.scroll.clamp(-overflow, 0.0);
+
+        // Use rect.contains instead of response.hovered as the dragged tab covers the underlying tabs.
+        if let Some(pos) = state.last_hover_pos {
+            let visible_rect = tabs_ui.clip_rect();
+            if state.drag_start.is_some() && visible_rect.contains(pos) {
+                let tab_rects: Vec<Rect> = tab_rects.into_iter().map(transpose).collect();
+                self.tab_hover_rect =
+                    tab_insertion_caret(&tab_rects, pos.y, transpose(visible_rect))
+                        .map(|(caret, index)| (transpose(caret), index));
+            }
+        }
+
+        bar_rect
+    }
+}
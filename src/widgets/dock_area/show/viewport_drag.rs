@@ -0,0 +1,263 @@
+use egui::{InputState, Pos2, Rect, Ui, Vec2, ViewportId};
+
+use super::window_surface::window_id;
+use crate::{
+    dock_area::{
+        drag_and_drop::{draw_highlight_rect, DragData, TreeComponent},
+        state::{State, ViewportDrag},
+    },
+    DockArea, DropKind, NodeIndex, SurfaceIndex, TabDestination, TabIndex, TabInsert, TabViewer,
+};
+
+/// Returns where the viewport an input is for is on the screen, since its positions are relative to it.
+fn viewport_origin(input: &InputState) -> Vec2 {
+    input
+        .viewport()
+        .inner_rect
+        .map_or(Vec2::ZERO, |rect| rect.min.to_vec2())
+}
+
+impl<Tab> DockArea<'_, Tab> {
+    /// Follows a tab dragged out of the viewport it's shown in, which stops reporting where the pointer is when it
+    /// leaves, then drops it once it's released: into the leaf of another viewport it's released over, or into
+    /// a new window shown in its own viewport anywhere else.
+    pub(super) fn drag_across_viewports(
+        &mut self,
+        ui: &Ui,
+        state: &mut State,
+        drag_data: Option<&DragData>,
+        tab_viewer: &mut impl TabViewer<Tab = Tab>,
+    ) {
+        let has_viewports = self
+            .dock_state
+            .iter_windows()
+            .any(|(_, _, window_state)| window_state.is_viewport());
+        let follows = self.allow_os_window_detach || self.native_windows || has_viewports;
+        if ui.ctx().embed_viewports() || !follows {
+            state.viewport_drag = None;
+            return;
+        }
+
+        match drag_data {
+            Some(&DragData {
+                src: TreeComponent::Tab(surface, node, tab),
+                rect,
+                foreign: None,
+            }) if !self.dock_state.is_pinned((surface, node, tab)) => {
+                let drag = state.viewport_drag.get_or_insert(ViewportDrag {
+                    tab: (surface, node, tab),
+                    size: rect.size(),
+                    pointer: rect.center(),
+                    outside: false,
+                    target: None,
+                });
+                drag.tab = (surface, node, tab);
+                drag.size = rect.size();
+            }
+            // The viewport stops publishing the drag once the pointer left it.
+            _ if state
+                .viewport_drag
+                .as_ref()
+                .is_some_and(|drag| drag.outside) => {}
+            _ => {
+                state.viewport_drag = None;
+                return;
+            }
+        }
+        let Some(drag) = state.viewport_drag.as_mut() else {
+            return;
+        };
+        let (surface, node, tab) = drag.tab;
+        if self
+            .dock_state
+            .leaf(surface, node)
+            .is_none_or(|leaf| leaf.tabs.len() <= tab.0)
+        {
+            state.viewport_drag = None;
+            return;
+        }
+
+        let ctx = ui.ctx();
+        let host = ctx.viewport_id();
+        let source = self.surface_viewport(host, surface);
+        let viewports: Vec<ViewportId> = std::iter::once(host)
+            .chain(
+                self.dock_state
+                    .iter_windows()
+                    .filter(|(_, _, window_state)| window_state.is_viewport())
+                    .map(|(surface, _, _)| self.viewport_id(surface)),
+            )
+            .collect();
+        // Without a pointer capture, the release is reported to the viewport the pointer is over instead.
+        let released = viewports
+            .iter()
+            .any(|&viewport| ctx.input_for(viewport, |i| i.pointer.any_released()));
+
+        if !released {
+            let (latest_pos, screen_rect, origin) = ctx.input_for(source, |i| {
+                (i.pointer.latest_pos(), i.screen_rect(), viewport_origin(i))
+            });
+            drag.outside = latest_pos.is_none_or(|pos| !screen_rect.contains(pos));
+            if let Some(pos) = latest_pos {
+                drag.pointer = pos + origin;
+            }
+            if drag.outside {
+                let hovered = viewports
+                    .iter()
+                    .filter(|&&viewport| viewport != source)
+                    .find_map(|&viewport| {
+                        ctx.input_for(viewport, |i| {
+                            i.pointer.hover_pos().map(|pos| pos + viewport_origin(i))
+                        })
+                    });
+                if let Some(pointer) = hovered {
+                    drag.pointer = pointer;
+                }
+            }
+            let (outside, pointer) = (drag.outside, drag.pointer);
+            let target = outside
+                .then(|| {
+                    self.viewport_drop_target(ui, source, (surface, node, tab), pointer, tab_viewer)
+                })
+                .flatten();
+            state.viewport_drag.as_mut().unwrap().target = target;
+        }
+
+        let drag = state.viewport_drag.as_ref().unwrap();
+        if !drag.outside {
+            return;
+        }
+        let (target, pointer, size) = (drag.target, drag.pointer, drag.size);
+        // Dragging the only tab of a viewport out of it just leaves the viewport where it is.
+        let is_last = !surface.is_main() && self.dock_state[surface].num_tabs() == 1;
+        let detachable = (self.allow_os_window_detach || self.native_windows)
+            && !is_last
+            && self.dock_state.maximized_leaf.is_none()
+            && self.dock_state[surface][node]
+                .get_leaf_mut()
+                .is_some_and(|leaf| tab_viewer.allowed_in_windows(&mut leaf.tabs[tab.0]));
+        self.dock_state.drop_kind = match target {
+            Some(_) => Some(DropKind::IntoLeaf),
+            None => detachable.then_some(DropKind::NewWindow),
+        };
+        if !released {
+            ctx.request_repaint();
+            return;
+        }
+
+        state.viewport_drag = None;
+        state.reset_drag();
+        self.dock_state.drop_kind = None;
+        match target {
+            Some((dst_surf, dst_node)) => {
+                self.expand_for_drop((surface, node), (dst_surf, dst_node));
+                self.dock_state.move_tab(
+                    (surface, node, tab),
+                    TabDestination::Node(dst_surf, dst_node, TabInsert::Append),
+                );
+            }
+            None if detachable => {
+                let surface = self
+                    .dock_state
+                    .detach_tab((surface, node, tab), Rect::from_min_size(pointer, size));
+                self.dock_state
+                    .get_window_state_mut(surface)
+                    .unwrap()
+                    .set_viewport(true);
+            }
+            None => {}
+        }
+    }
+
+    /// Returns the leaf under a position in screen coordinates in another viewport than the `source` one, if the
+    /// dragged tab may be dropped into it.
+    fn viewport_drop_target(
+        &self,
+        ui: &Ui,
+        source: ViewportId,
+        (surface, node, tab): (SurfaceIndex, NodeIndex, TabIndex),
+        pointer: Pos2,
+        tab_viewer: &impl TabViewer<Tab = Tab>,
+    ) -> Option<(SurfaceIndex, NodeIndex)> {
+        let ctx = ui.ctx();
+        let host = ctx.viewport_id();
+        let viewport_windows = self
+            .dock_state
+            .iter_windows()
+            .filter(|(_, _, window_state)| window_state.is_viewport())
+            .map(|(surface, _, _)| (self.viewport_id(surface), Some(surface)));
+        let (dst_surf, pos) = std::iter::once((host, None))
+            .chain(viewport_windows)
+            .filter(|&(viewport, _)| viewport != source)
+            .find_map(|(viewport, surface)| {
+                let (screen_rect, origin) =
+                    ctx.input_for(viewport, |i| (i.screen_rect(), viewport_origin(i)));
+                let pos = pointer - origin;
+                screen_rect.contains(pos).then(|| {
+                    let surface = surface.unwrap_or_else(|| self.host_surface_at(ui, pos));
+                    (surface, pos)
+                })
+            })?;
+        let dst_node = self.leaf_at(dst_surf, pos)?;
+        let dragged = &self.dock_state.leaf(surface, node)?.tabs[tab.0];
+        ((surface, node) != (dst_surf, dst_node)
+            && tab_viewer.allowed_drop(dragged, dst_surf, dst_node, None))
+        .then_some((dst_surf, dst_node))
+    }
+
+    /// Returns the surface shown under a position of the viewport the [`DockArea`] is shown in: the window inside
+    /// of it on top there if there's one, the main surface otherwise.
+    fn host_surface_at(&self, ui: &Ui, pos: Pos2) -> SurfaceIndex {
+        let Some(layer_id) = ui.ctx().layer_id_at(pos) else {
+            return SurfaceIndex::main();
+        };
+        self.dock_state
+            .iter_windows()
+            .find(|&(surface, _, window_state)| {
+                !window_state.is_viewport()
+                    && !window_state.is_minimized()
+                    && layer_id.id == window_id(surface)
+            })
+            .map_or(SurfaceIndex::main(), |(surface, _, _)| surface)
+    }
+
+    /// Returns the leaf of a surface shown under a position, the maximized one if there's one.
+    fn leaf_at(&self, surf_index: SurfaceIndex, pos: Pos2) -> Option<NodeIndex> {
+        if let Some((surface, node)) = self.dock_state.maximized_leaf {
+            if surface == surf_index {
+                return self.dock_state[surface][node]
+                    .rect()
+                    .is_some_and(|rect| rect.contains(pos))
+                    .then_some(node);
+            }
+        }
+        self.dock_state[surf_index]
+            .iter()
+            .enumerate()
+            .find(|(_, node)| node.is_leaf() && node.rect().is_some_and(|rect| rect.contains(pos)))
+            .map(|(index, _)| NodeIndex(index))
+    }
+
+    /// Returns the viewport a surface is shown in.
+    fn surface_viewport(&self, host: ViewportId, surf_index: SurfaceIndex) -> ViewportId {
+        let is_viewport = self
+            .dock_state
+            .iter_windows()
+            .any(|(surface, _, window_state)| surface == surf_index && window_state.is_viewport());
+        if is_viewport {
+            self.viewport_id(surf_index)
+        } else {
+            host
+        }
+    }
+
+    /// Highlights the leaf of a surface a tab dragged from another viewport would be dropped into.
+    pub(super) fn highlight_viewport_drop(&self, ui: &Ui, state: &State, surf_index: SurfaceIndex) {
+        let target = state.viewport_drag.as_ref().and_then(|drag| drag.target);
+        if let Some((_, node)) = target.filter(|(surface, _)| *surface == surf_index) {
+            if let Some(rect) = self.dock_state[surf_index][node].rect() {
+                draw_highlight_rect(rect, ui, self.style.as_ref().unwrap());
+            }
+        }
+    }
+}
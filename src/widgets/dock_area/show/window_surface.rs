@@ -1,14 +1,21 @@
 use egui::{
-    vec2, Align, Color32, CornerRadius, CursorIcon, Frame, Layout, Rect, Response, RichText, Sense,
-    Shape, Stroke, Ui, UiBuilder, Vec2, WidgetText,
+    pos2, vec2, Align, CentralPanel, Color32, CornerRadius, CursorIcon, Frame, Id, LayerId, Layout,
+    Order, Rangef, Rect, Response, RichText, Sense, Shape, Stroke, Ui, UiBuilder, Vec2,
+    ViewportBuilder, ViewportClass, ViewportCommand, ViewportId, WidgetText,
 };
 
+use super::size_range;
 use crate::{
-    dock_area::{state::State, tab_removal::TabRemoval},
+    dock_area::{drag_and_drop::draw_highlight_rect, state::State, tab_removal::TabRemoval},
     utils::{fade_visuals, rect_set_size_centered},
     DockArea, NodeIndex, Style, SurfaceIndex, TabViewer,
 };
 
+/// Returns the id of the [`egui::Window`] a window surface is shown in when it's shown inside its viewport.
+pub(super) fn window_id(surf_index: SurfaceIndex) -> Id {
+    format!("window {surf_index:?}").into()
+}
+
 impl<Tab> DockArea<'_, Tab> {
     pub(super) fn show_window_surface(
         &mut self,
@@ -19,14 +26,14 @@ impl<Tab> DockArea<'_, Tab> {
         fade_style: Option<(&Style, f32, SurfaceIndex)>,
     ) {
         // Construct egui window
-        let id = format!("window {surf_index:?}").into();
+        let id = window_id(surf_index);
         let bounds = self.window_bounds.unwrap();
         let open = true;
-        let window = self
-            .dock_state
-            .get_window_state_mut(surf_index)
-            .unwrap()
-            .create_window(id, bounds);
+        let window_state = self.dock_state.get_window_state_mut(surf_index).unwrap();
+        if window_state.raise_requested() {
+            ui.ctx().move_to_top(LayerId::new(Order::Middle, id));
+        }
+        let window = window_state.create_window(id, bounds);
 
         // Calculate fading of the window (if any)
         let (fade_factor, fade_style) = match fade_style {
@@ -41,22 +48,9 @@ impl<Tab> DockArea<'_, Tab> {
         };
 
         // Get galley of currently selected node as a window title
-        let title = {
-            let node_id = self.dock_state[surf_index]
-                .focused_leaf()
-                .unwrap_or_else(|| {
-                    for node_index in self.dock_state[surf_index].breadth_first_index_iter() {
-                        if self.dock_state[surf_index][node_index].is_leaf() {
-                            return node_index;
-                        }
-                    }
-                    unreachable!("a window surface should never be empty")
-                });
-            let leaf = self.dock_state[surf_index][node_id].get_leaf_mut().unwrap();
-            tab_viewer
-                .title(&mut leaf.tabs[leaf.active.0])
-                .color(ui.visuals().widgets.noninteractive.fg_stroke.color)
-        };
+        let title = self
+            .window_title(surf_index, tab_viewer)
+            .color(ui.visuals().widgets.noninteractive.fg_stroke.color);
 
         // Iterate through every node in dock_state[surf_index], and sum up the number of tabs in them
         let mut tab_count = 0;
@@ -74,13 +68,13 @@ impl<Tab> DockArea<'_, Tab> {
             frame.shadow.color = frame.shadow.color.linear_multiply(fade_factor);
         }
 
-        let tab_bar_height = self.style.as_ref().unwrap().tab_bar.height;
+        let tab_bar_height = self.style.as_ref().unwrap().tab_bar_height();
         let minimized = self
             .dock_state
             .get_window_state(surf_index)
             .unwrap()
             .is_minimized();
-        if minimized {
+        let window = if minimized {
             let height = tab_bar_height;
             window
                 .resizable([true, false])
@@ -92,11 +86,20 @@ impl<Tab> DockArea<'_, Tab> {
                 .resizable([true, false])
                 .max_height(height)
                 .min_height(height)
+        } else if let Some((min_size, max_size)) = self.surface_size_range(surf_index) {
+            window.min_size(min_size).max_size(max_size)
         } else {
             window
         }
         .frame(frame)
-        .show(ui.ctx(), |ui| {
+        .movable(self.interactive)
+        .enabled(self.enabled);
+        let window = if self.interactive {
+            window
+        } else {
+            window.resizable(false)
+        };
+        let response = window.show(ui.ctx(), |ui| {
             // Fade inner ui (if necessary)
             if fade_factor != 1.0 {
                 fade_visuals(ui.visuals_mut(), fade_factor);
@@ -113,12 +116,184 @@ impl<Tab> DockArea<'_, Tab> {
                 self.render_nodes(ui, tab_viewer, state, surf_index, fade_style);
             }
         });
+        if let Some(response) = response {
+            let rect = response.response.rect;
+            let layer_id = response.response.layer_id;
+            let stack_order = ui
+                .ctx()
+                .memory(|memory| memory.layer_ids().position(|layer| layer == layer_id))
+                .unwrap_or_default();
+            let window_state = self.dock_state.get_window_state_mut(surf_index).unwrap();
+            window_state.set_screen_rect(rect);
+            window_state.set_stack_order(stack_order);
+            if self.on_layout.is_some() {
+                self.layout_info.windows.push((surf_index, rect));
+            }
+        }
 
         if !open {
             self.to_remove.push(TabRemoval::Window(surf_index));
         }
     }
 
+    /// Highlights the title bar of the window a dragged window is over, and merges the dragged window into it once
+    /// it's released there, see [`DockArea::allow_window_merging`].
+    pub(super) fn merge_dragged_window(&mut self, ui: &Ui) {
+        let ctx = ui.ctx();
+        let Some(dragged) = ctx.dragged_id().or_else(|| ctx.drag_stopped_id()) else {
+            return;
+        };
+        // Windows are moved by dragging their area, see `egui::Area`.
+        let src = self
+            .dock_state
+            .iter_windows()
+            .find(|&(surface, _, window_state)| {
+                !window_state.is_viewport() && dragged == window_id(surface).with("move")
+            })
+            .map(|(surface, _, _)| surface);
+        let (Some(src), Some(pos)) = (src, ctx.pointer_latest_pos()) else {
+            return;
+        };
+
+        let style = self.style.as_ref().unwrap();
+        let tab_bar_height = style.tab_bar_height();
+        let target = self
+            .dock_state
+            .iter_windows()
+            .filter(|&(surface, _, window_state)| surface != src && !window_state.is_viewport())
+            .find_map(|(surface, tree, window_state)| {
+                let rect = window_state.rect();
+                let bottom = if window_state.is_minimized() {
+                    rect.bottom()
+                } else {
+                    tree.root_node()?.rect()?.top() + tab_bar_height
+                };
+                let title_bar = Rect::from_min_max(rect.min, pos2(rect.right(), bottom));
+                title_bar.contains(pos).then_some((surface, title_bar))
+            });
+        let Some((dst, title_bar)) = target else {
+            return;
+        };
+        if ctx.drag_stopped_id() == Some(dragged) {
+            self.dock_state.merge_windows(src, dst);
+        } else {
+            draw_highlight_rect(title_bar, ui, style);
+        }
+    }
+
+    /// Shows a window surface in its own OS-level viewport.
+    ///
+    /// If the viewport gets closed, the tabs of the window are put back into the main surface.
+    pub(super) fn show_viewport_surface(
+        &mut self,
+        ui: &Ui,
+        surf_index: SurfaceIndex,
+        tab_viewer: &mut impl TabViewer<Tab = Tab>,
+        state: &mut State,
+        fade_style: Option<(&Style, f32, SurfaceIndex)>,
+    ) {
+        let viewport_id = self.viewport_id(surf_index);
+        let title = self.window_title(surf_index, tab_viewer).text().to_owned();
+        let size_range = self.surface_size_range(surf_index);
+
+        let window_state = self.dock_state.get_window_state_mut(surf_index).unwrap();
+        let mut builder = ViewportBuilder::default().with_title(title);
+        if let Some(position) = window_state.next_position() {
+            builder = builder.with_position(position);
+        }
+        if let Some(size) = window_state.next_size() {
+            builder = builder.with_inner_size(size);
+        }
+        if let Some((min_size, max_size)) = size_range {
+            builder = builder
+                .with_min_inner_size(min_size)
+                .with_max_inner_size(max_size);
+        }
+        if window_state.raise_requested() {
+            ui.ctx()
+                .send_viewport_cmd_to(viewport_id, ViewportCommand::Focus);
+        }
+
+        let close_requested =
+            ui.ctx()
+                .show_viewport_immediate(viewport_id, builder, |ctx, class| {
+                    if class == ViewportClass::Embedded {
+                        // The backend can't show another viewport, fall back to an in-app window.
+                        self.show_window_surface(ui, surf_index, tab_viewer, state, fade_style);
+                        return false;
+                    }
+                    CentralPanel::default()
+                        .frame(Frame::central_panel(&ctx.style()).inner_margin(0.))
+                        .show(ctx, |ui| {
+                            if !self.enabled {
+                                ui.disable();
+                            }
+                            self.render_nodes(ui, tab_viewer, state, surf_index, None);
+                        });
+                    ctx.input(|i| i.viewport().close_requested())
+                });
+
+        if close_requested {
+            for tab in self.dock_state.remove_surface_tabs(surf_index) {
+                self.dock_state[SurfaceIndex::main()].push_to_focused_leaf(tab);
+            }
+        }
+    }
+
+    /// Returns the id of the viewport a window surface is shown in when it's shown in its own one.
+    pub(super) fn viewport_id(&self, surf_index: SurfaceIndex) -> ViewportId {
+        ViewportId::from_hash_of(self.id.with((surf_index, "viewport")))
+    }
+
+    /// Returns the smallest and largest sizes a surface can take within the size ranges of its leaves,
+    /// or [`None`] if none of them is constrained.
+    fn surface_size_range(&self, surf_index: SurfaceIndex) -> Option<(Vec2, Vec2)> {
+        let tree = &self.dock_state[surf_index];
+        let style = self.style.as_ref().unwrap();
+        let separator_width = style.separator.width;
+        let width = size_range(tree, NodeIndex::root(), true, separator_width);
+        let height = size_range(tree, NodeIndex::root(), false, separator_width);
+        if width.is_none() && height.is_none() {
+            return None;
+        }
+        let unconstrained = Rangef::new(0.0, f32::INFINITY);
+        let (width, height) = (
+            width.unwrap_or(unconstrained),
+            height.unwrap_or(unconstrained),
+        );
+        let padding = style
+            .dock_area_padding
+            .map_or(Vec2::ZERO, |margin| margin.sum());
+        Some((
+            vec2(width.min, height.min) + padding,
+            vec2(width.max, height.max) + padding,
+        ))
+    }
+
+    /// Returns the title of a window, which is the title of the active tab in its focused (or first) leaf
+    /// unless [`TabViewer::window_title`] says otherwise.
+    fn window_title(
+        &mut self,
+        surf_index: SurfaceIndex,
+        tab_viewer: &mut impl TabViewer<Tab = Tab>,
+    ) -> WidgetText {
+        let node_id = self.dock_state[surf_index]
+            .focused_leaf()
+            .unwrap_or_else(|| {
+                for node_index in self.dock_state[surf_index].breadth_first_index_iter() {
+                    if self.dock_state[surf_index][node_index].is_leaf() {
+                        return node_index;
+                    }
+                }
+                unreachable!("a window surface should never be empty")
+            });
+        let leaf = self.dock_state[surf_index][node_id].get_leaf_mut().unwrap();
+        let tab = &mut leaf.tabs[leaf.active.0];
+        tab_viewer
+            .window_title(surf_index, tab)
+            .unwrap_or_else(|| tab_viewer.title(tab))
+    }
+
     fn minimized_body(
         &mut self,
         ui: &mut Ui,
@@ -130,14 +305,19 @@ impl<Tab> DockArea<'_, Tab> {
         ui.horizontal(|ui| {
             let style = fade_style.unwrap_or_else(|| self.style.as_ref().unwrap());
             let (tabbar_outer_rect, _) = ui.allocate_exact_size(
-                vec2(Style::TAB_EXPAND_BUTTON_SIZE, style.tab_bar.height),
+                vec2(Style::TAB_EXPAND_BUTTON_SIZE, style.tab_bar_height()),
                 Sense::hover(),
             );
-            ui.painter().rect_filled(
-                tabbar_outer_rect,
-                style.tab_bar.corner_radius,
-                style.tab_bar.bg_fill,
-            );
+            match &style.tab_bar.bg_painter {
+                Some(bg_painter) => bg_painter.paint(ui.painter(), tabbar_outer_rect),
+                None => {
+                    ui.painter().rect_filled(
+                        tabbar_outer_rect,
+                        style.tab_bar.corner_radius,
+                        style.tab_bar.bg_fill,
+                    );
+                }
+            }
             self.window_expand(ui, surface_index, tabbar_outer_rect, fade_style);
             ui.label(title);
             if tab_count > 1 {
@@ -253,36 +433,7 @@ impl<Tab> DockArea<'_, Tab> {
     }
 
     pub(super) fn window_toggle_minimized(&mut self, surf_index: SurfaceIndex) {
-        let minimized = self
-            .dock_state
-            .get_window_state(surf_index)
-            .unwrap()
-            .is_minimized();
-        let surface = &mut self.dock_state[surf_index];
-
-        if surface.root_node().is_some_and(|node| node.is_collapsed()) {
-            // The window is already fully collapsed,
-            // so `expanded_height` has already been set.
-            // We don't need to set `new` either.
-            if let Some(window_state) = self.dock_state.get_window_state_mut(surf_index) {
-                window_state.toggle_minimized();
-            }
-        } else if minimized {
-            if let Some(window_state) = self.dock_state.get_window_state_mut(surf_index) {
-                window_state.set_new(true);
-                window_state.toggle_minimized();
-            }
-        } else {
-            let root_index = NodeIndex::root();
-            let surface_height = if surface.root_node().is_some() {
-                surface[root_index].rect().unwrap().height()
-            } else {
-                0.0
-            };
-            if let Some(window_state) = self.dock_state.get_window_state_mut(surf_index) {
-                window_state.set_expanded_height(surface_height);
-                window_state.toggle_minimized();
-            }
-        }
+        let minimized = self.dock_state.is_window_collapsed(surf_index) == Some(true);
+        self.dock_state.set_window_collapsed(surf_index, !minimized);
     }
 }
@@ -0,0 +1,71 @@
+use std::ops::{BitAnd, BitOr, BitOrAssign};
+
+/// The entries the [`DockArea`](crate::DockArea) adds to the context menu of tabs,
+/// before those of [`TabViewer::tab_context_menu`](crate::TabViewer::tab_context_menu).
+///
+/// Combine them with `|`, e.g. `StandardActions::CLOSE_OTHERS | StandardActions::FLOAT`.
+/// Closing tabs goes through [`TabViewer::is_closeable`](crate::TabViewer::is_closeable) and
+/// [`TabViewer::on_close`](crate::TabViewer::on_close), pinned tabs are only closed by the plain close entry,
+/// and floating a tab goes through [`TabViewer::allowed_in_windows`](crate::TabViewer::allowed_in_windows).
+#[derive(Copy, Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct StandardActions(u8);
+
+impl StandardActions {
+    /// No entries.
+    pub const NONE: Self = Self(0);
+
+    /// Closes the other tabs of the leaf.
+    pub const CLOSE_OTHERS: Self = Self(1 << 0);
+
+    /// Closes all tabs of the leaf.
+    pub const CLOSE_ALL: Self = Self(1 << 1);
+
+    /// Closes the tabs after the tab in its leaf.
+    pub const CLOSE_TO_THE_RIGHT: Self = Self(1 << 2);
+
+    /// Moves the tab into a new window. It takes the place of the eject entry shown otherwise.
+    pub const FLOAT: Self = Self(1 << 3);
+
+    /// Moves the tab into a new leaf to the right of its leaf.
+    pub const SPLIT_RIGHT: Self = Self(1 << 4);
+
+    /// Moves the tab into a new leaf below its leaf.
+    pub const SPLIT_BELOW: Self = Self(1 << 5);
+
+    /// All entries.
+    pub const ALL: Self = Self(0b11_1111);
+
+    /// Returns whether all entries of `other` are part of `self`.
+    #[inline]
+    pub const fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Returns `true` if there are no entries.
+    #[inline]
+    pub const fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+}
+
+impl BitOr for StandardActions {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        Self(self.0 | rhs.0)
+    }
+}
+
+impl BitOrAssign for StandardActions {
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.0 |= rhs.0;
+    }
+}
+
+impl BitAnd for StandardActions {
+    type Output = Self;
+
+    fn bitand(self, rhs: Self) -> Self::Output {
+        Self(self.0 & rhs.0)
+    }
+}
@@ -1,7 +1,13 @@
-use egui::{Context, Id, Pos2};
+use egui::{
+    ahash::{HashMap, HashSet},
+    epaint::ClippedShape,
+    Context, Id, LayerId, Modifiers, Pos2, Rect, Vec2,
+};
+use std::ops::Range;
 
-use crate::{Style, SurfaceIndex};
+use crate::{NodeIndex, Style, SurfaceIndex, TabIndex};
 
+use super::dock_event::LayoutSnapshot;
 use super::drag_and_drop::{DragData, DragDropState, HoverData};
 
 #[derive(Clone, Debug, Default)]
@@ -10,6 +16,103 @@ pub(super) struct State {
     pub last_hover_pos: Option<Pos2>,
     pub dnd: Option<DragDropState>,
     pub window_fade: Option<(f64, SurfaceIndex)>,
+    pub renaming: Option<TabRename>,
+
+    /// The last content rect reported to [`TabViewer::on_content_rect_changed`](crate::TabViewer::on_content_rect_changed)
+    /// for each tab, by [`TabViewer::id`](crate::TabViewer::id).
+    pub content_rects: HashMap<Id, Rect>,
+
+    /// Tabs which were already passed to [`TabViewer::on_first_shown`](crate::TabViewer::on_first_shown),
+    /// by [`TabViewer::id`](crate::TabViewer::id).
+    pub shown_tabs: HashSet<Id>,
+
+    /// What the content of tabs opting into [`TabViewer::cache_when_hidden`](crate::TabViewer::cache_when_hidden)
+    /// painted when it was last shown, by [`TabViewer::id`](crate::TabViewer::id).
+    pub content_cache: HashMap<Id, ContentCache>,
+
+    pub selection: Option<TabSelection>,
+
+    /// Tabs by [`TabViewer::id`](crate::TabViewer::id), the most recently used first.
+    pub recently_used: Vec<Id>,
+
+    /// Which entry of the tab switcher is highlighted, if it's open.
+    pub switcher: Option<usize>,
+
+    /// The tab whose content held keyboard focus in the last frame, by [`TabViewer::id`](crate::TabViewer::id).
+    pub focused_content: Option<Id>,
+
+    /// The layer the [`DockArea`](crate::DockArea) was shown in during the last frame, and the indices of the
+    /// widgets it registered there.
+    pub widgets: Option<(LayerId, Range<usize>)>,
+
+    /// The button of a tab which was activated by pressing on it, so that releasing the press doesn't count as
+    /// clicking an already active tab.
+    pub activated_on_press: Option<Id>,
+
+    /// When and where the last tap on a tab bar happened, used to recognize double-taps.
+    pub last_tap: Option<(f64, Pos2)>,
+
+    /// The tabs of the maximized leaf at the end of the last frame, by [`TabViewer::id`](crate::TabViewer::id).
+    pub maximized_tabs: Vec<Id>,
+
+    /// The layout at the end of the last frame, recorded while [`DockArea::on_events`](crate::DockArea::on_events)
+    /// is set.
+    pub layout_snapshot: Option<LayoutSnapshot>,
+
+    /// The layout at the end of the last frame, recorded while the [`DockState`](crate::DockState) has a history,
+    /// see [`DockState::set_layout_history`](crate::DockState::set_layout_history).
+    pub history_snapshot: Option<LayoutSnapshot>,
+
+    /// A tab dragged out of the viewport it's shown in.
+    pub viewport_drag: Option<ViewportDrag>,
+}
+
+/// The shapes painted by the content of a tab, see [`State::content_cache`].
+#[derive(Clone, Debug)]
+pub(super) struct ContentCache {
+    /// The pass in which the content was last shown.
+    pub pass_nr: u64,
+
+    /// Whether the shapes were repainted in place of the content since it was last shown.
+    pub replayed: bool,
+    pub shapes: Vec<ClippedShape>,
+}
+
+/// A tab whose title is currently being edited inline.
+#[derive(Clone, Debug)]
+pub(super) struct TabRename {
+    pub tab: (SurfaceIndex, NodeIndex, TabIndex),
+    pub text: String,
+    pub width: f32,
+    pub request_focus: bool,
+}
+
+/// A tab dragged out of the viewport it's shown in, which is dropped into another viewport or detached into
+/// a new one once it's released.
+#[derive(Clone, Debug)]
+pub(super) struct ViewportDrag {
+    pub tab: (SurfaceIndex, NodeIndex, TabIndex),
+
+    /// The size of the tab's leaf, which a new window takes.
+    pub size: Vec2,
+
+    /// Where the pointer is, or was last seen, in screen coordinates.
+    pub pointer: Pos2,
+
+    /// Whether the pointer left the viewport the tab is shown in.
+    pub outside: bool,
+
+    /// The leaf the tab would be dropped into.
+    pub target: Option<(SurfaceIndex, NodeIndex)>,
+}
+
+/// Tabs of a leaf which are selected to be dragged together.
+#[derive(Clone, Debug)]
+pub(super) struct TabSelection {
+    pub leaf: (SurfaceIndex, NodeIndex),
+
+    /// Always sorted and free of duplicates.
+    pub tabs: Vec<TabIndex>,
 }
 
 impl State {
@@ -20,6 +123,21 @@ impl State {
             last_hover_pos: None,
             dnd: None,
             window_fade: None,
+            renaming: None,
+            content_rects: HashMap::default(),
+            shown_tabs: HashSet::default(),
+            content_cache: HashMap::default(),
+            selection: None,
+            recently_used: Vec::new(),
+            switcher: None,
+            focused_content: None,
+            widgets: None,
+            activated_on_press: None,
+            last_tap: None,
+            maximized_tabs: Vec::new(),
+            layout_snapshot: None,
+            history_snapshot: None,
+            viewport_drag: None,
         })
     }
 
@@ -34,6 +152,85 @@ impl State {
         self.drag_start = None;
     }
 
+    /// Records a tap at `pos`, returning whether it completes a double-tap with the previous one.
+    ///
+    /// The tap completing a double-tap is forgotten, so that a third tap starts over.
+    pub(super) fn double_tapped(
+        &mut self,
+        (time, pos): (f64, Pos2),
+        max_delay: f64,
+        max_distance: f32,
+    ) -> bool {
+        let double_tapped = self.last_tap.is_some_and(|(last_time, last_pos)| {
+            time - last_time <= max_delay && pos.distance(last_pos) <= max_distance
+        });
+        self.last_tap = (!double_tapped).then_some((time, pos));
+        double_tapped
+    }
+
+    /// Updates the tab selection after `clicked` was clicked with `modifiers` held.
+    ///
+    /// Holding the command key toggles the tab, holding shift selects the range of tabs from the `active` one,
+    /// and a plain click clears the selection.
+    pub(super) fn select_tab(
+        &mut self,
+        leaf: (SurfaceIndex, NodeIndex),
+        clicked: TabIndex,
+        active: TabIndex,
+        modifiers: Modifiers,
+    ) {
+        if !modifiers.command && !modifiers.shift {
+            self.selection = None;
+            return;
+        }
+
+        let selection = match &mut self.selection {
+            Some(selection) if selection.leaf == leaf => selection,
+            selection => selection.insert(TabSelection {
+                leaf,
+                tabs: vec![active],
+            }),
+        };
+        if modifiers.shift {
+            let (start, end) = (active.0.min(clicked.0), active.0.max(clicked.0));
+            selection.tabs.extend((start..=end).map(TabIndex));
+        } else if let Some(position) = selection.tabs.iter().position(|&tab| tab == clicked) {
+            selection.tabs.remove(position);
+        } else {
+            selection.tabs.push(clicked);
+        }
+        selection.tabs.sort_unstable();
+        selection.tabs.dedup();
+
+        if selection.tabs.is_empty() {
+            self.selection = None;
+        }
+    }
+
+    /// Returns the selected tabs if the given tab is part of a selection of several tabs.
+    pub(super) fn selected_group(
+        &self,
+        (surface, node, tab): (SurfaceIndex, NodeIndex, TabIndex),
+    ) -> Option<&[TabIndex]> {
+        self.selection
+            .as_ref()
+            .filter(|selection| {
+                selection.leaf == (surface, node)
+                    && selection.tabs.len() > 1
+                    && selection.tabs.contains(&tab)
+            })
+            .map(|selection| &selection.tabs[..])
+    }
+
+    pub(super) fn is_selected(
+        &self,
+        (surface, node, tab): (SurfaceIndex, NodeIndex, TabIndex),
+    ) -> bool {
+        self.selection.as_ref().is_some_and(|selection| {
+            selection.leaf == (surface, node) && selection.tabs.contains(&tab)
+        })
+    }
+
     pub(super) fn set_drag_and_drop(
         &mut self,
         drag: DragData,
@@ -1,5 +1,8 @@
-use crate::{NodeIndex, SurfaceIndex, TabStyle};
-use egui::{Id, Ui, WidgetText};
+use crate::{
+    AllowedSplits, CloseButtonVisibility, NodeIndex, Split, SurfaceIndex, TabIndex, TabStyle,
+};
+use egui::{Color32, Id, Rect, Ui, WidgetText};
+use std::any::Any;
 
 /// Defines how a tab should behave and be rendered inside a [`Tree`](crate::Tree).
 pub trait TabViewer {
@@ -9,13 +12,45 @@ pub trait TabViewer {
     /// The title to be displayed in the tab bar.
     fn title(&mut self, tab: &mut Self::Tab) -> WidgetText;
 
+    /// Custom content of the button of the `_tab` in a horizontal tab bar, such as an icon next to the title,
+    /// a dot for unsaved changes, or a spinner.
+    ///
+    /// The [`Ui`] is laid out from left to right within the button, and the button is as wide as the content was
+    /// in the last frame. The [`DockArea`](crate::DockArea) still handles clicking, dragging and the close button,
+    /// so the widgets added here don't react to the pointer.
+    ///
+    /// If nothing is added, the [`title`](Self::title) is shown, which is the default.
+    fn title_ui(&mut self, _ui: &mut Ui, _tab: &mut Self::Tab) {}
+
     /// Actual tab content.
     fn ui(&mut self, ui: &mut Ui, tab: &mut Self::Tab);
 
+    /// Actual tab content, with a [`TabContext`] describing where the tab is and whether its leaf is focused.
+    ///
+    /// By default, calls [`ui`](Self::ui).
+    fn ui_with_context(&mut self, ui: &mut Ui, tab: &mut Self::Tab, _context: TabContext) {
+        self.ui(ui, tab);
+    }
+
+    /// A toolbar shown between the tab bar and the content of the leaf at `_surface` and `_node`,
+    /// while `_tab` is its active tab.
+    ///
+    /// The toolbar is laid out left to right and takes up as much height as its content needs.
+    /// Nothing is reserved for it when nothing is added to `_ui`, which is the default.
+    fn leaf_toolbar(
+        &mut self,
+        _ui: &mut Ui,
+        _tab: &mut Self::Tab,
+        _surface: SurfaceIndex,
+        _node: NodeIndex,
+    ) {
+    }
+
     /// Content inside the context menu shown when the tab is right-clicked.
     ///
     /// `_surface` and `_node` specify which [`Surface`](crate::Surface) and [`Node`](crate::Node)
     /// that this particular context menu belongs to.
+    #[deprecated = "Use the `TabViewer::tab_context_menu` function instead."]
     fn context_menu(
         &mut self,
         _ui: &mut Ui,
@@ -25,6 +60,24 @@ pub trait TabViewer {
     ) {
     }
 
+    /// Content inside the context menu shown when the tab is right-clicked.
+    ///
+    /// `surface`, `node` and `tab_index` specify which [`Surface`](crate::Surface), [`Node`](crate::Node)
+    /// and position within that node the right-clicked tab is at.
+    ///
+    /// By default, calls [`context_menu`](Self::context_menu).
+    fn tab_context_menu(
+        &mut self,
+        ui: &mut Ui,
+        tab: &mut Self::Tab,
+        surface: SurfaceIndex,
+        node: NodeIndex,
+        _tab_index: TabIndex,
+    ) {
+        #[allow(deprecated)]
+        self.context_menu(ui, tab, surface, node);
+    }
+
     /// Unique ID for this tab.
     ///
     /// If not implemented, uses tab title text as an ID source.
@@ -35,6 +88,18 @@ pub trait TabViewer {
     /// Called after each tab button is shown, so you can add a tooltip, check for clicks, etc.
     fn on_tab_button(&mut self, _tab: &mut Self::Tab, _response: &egui::Response) {}
 
+    /// This is called when the user finishes renaming the `_tab` inline.
+    ///
+    /// This requires that [`DockArea::rename_tabs_on_double_click`](crate::DockArea::rename_tabs_on_double_click)
+    /// is set to `true`. `_new_name` is the text entered by the user; renaming is cancelled with the escape key,
+    /// in which case this isn't called.
+    fn on_rename(&mut self, _tab: &mut Self::Tab, _new_name: String) {}
+
+    /// This is called when the user clicks the `_tab` while it's already the active tab of its leaf.
+    ///
+    /// See also [`DockArea::on_active_tab_reclick`](crate::DockArea::on_active_tab_reclick).
+    fn on_active_tab_clicked(&mut self, _tab: &mut Self::Tab) {}
+
     /// This is called when the `_tab` gets closed by the user.
     ///
     /// Returns an `OnCloseResponse` which determines what happens to the tab after this function gets called.
@@ -49,6 +114,40 @@ pub trait TabViewer {
         true
     }
 
+    /// Returns `true` if the user of your app should be able to pin or unpin the `_tab` from its context menu,
+    /// see [`DockState::set_pinned`](crate::DockState::set_pinned).
+    ///
+    /// By default, `true` is always returned.
+    fn is_pinnable(&self, _tab: &Self::Tab) -> bool {
+        true
+    }
+
+    /// Icon shown instead of the title of the `_tab` while it's pinned and
+    /// [`TabStyle::pinned_icon_only`](crate::TabStyle::pinned_icon_only) is set.
+    ///
+    /// Returns [`None`] to show the [`title`](Self::title) anyway, which is the default.
+    fn tab_icon(&mut self, _tab: &mut Self::Tab) -> Option<WidgetText> {
+        None
+    }
+
+    /// Returns `true` if the leaf containing `_tab` may be collapsed down to its tab bar.
+    ///
+    /// The most restrictive tab wins: a leaf with any tab which isn't collapsible shows no collapse button,
+    /// and [`DockState::set_leaf_collapsed`](crate::DockState::set_leaf_collapsed) refuses to collapse it.
+    ///
+    /// By default, `true` is always returned.
+    fn is_collapsible(&self, _tab: &Self::Tab) -> bool {
+        true
+    }
+
+    /// Overrides when the close button of a given `_tab` is shown,
+    /// see [`DockArea::close_button_visibility`](crate::DockArea::close_button_visibility).
+    ///
+    /// By default, `None` is returned, which uses the visibility set on the [`DockArea`](crate::DockArea).
+    fn close_button_visibility(&self, _tab: &Self::Tab) -> Option<CloseButtonVisibility> {
+        None
+    }
+
     /// Returns `true` if the user of your app should be able to close a given `_tab`.
     ///
     /// By default, `true` is always returned.
@@ -70,8 +169,23 @@ pub trait TabViewer {
     ///
     /// `_surface` and `_node` specify which [`Surface`](crate::Surface) and on which
     /// [`Node`](crate::Node) this particular add button was pressed.
+    #[deprecated = "Use the `TabViewer::on_add_tab` function instead."]
     fn on_add(&mut self, _surface: SurfaceIndex, _node: NodeIndex) {}
 
+    /// This is called when the add button is pressed.
+    ///
+    /// `surface` and `node` specify which [`Surface`](crate::Surface) and on which
+    /// [`Node`](crate::Node) this particular add button was pressed.
+    ///
+    /// If a tab is returned, it's added to the end of that node and made active.
+    ///
+    /// By default, calls [`on_add`](Self::on_add) and returns `None`.
+    fn on_add_tab(&mut self, surface: SurfaceIndex, node: NodeIndex) -> Option<Self::Tab> {
+        #[allow(deprecated)]
+        self.on_add(surface, node);
+        None
+    }
+
     /// Called when the rectangle of the tab content changes.
     ///
     /// This can happen when the window is resized, panels are docked or undocked,
@@ -80,19 +194,151 @@ pub trait TabViewer {
     ///
     /// This is useful for tabs that need to adjust their content based on the
     /// available space.
+    #[deprecated = "Use the `TabViewer::on_content_rect_changed` function instead."]
     fn on_rect_changed(&mut self, _tab: &mut Self::Tab) {}
 
+    /// Called when keyboard focus enters (`_focused` is `true`) or leaves (`_focused` is `false`)
+    /// the content of the `_tab`.
+    ///
+    /// The content has focus while the focused widget is shown in it, rather than e.g. in the tab bar.
+    /// This is called once per transition, at the end of the frame in which it happened.
+    fn on_content_focus_changed(&mut self, _tab: &mut Self::Tab, _focused: bool) {}
+
+    /// Called when the rect of the content area of a tab changes, with the new `rect`.
+    ///
+    /// This is only called for the active tab of a leaf, the first time it's shown and whenever its content area
+    /// moves or resizes by more than a fraction of a point since the last call for the same tab
+    /// (as identified by [`TabViewer::id`]).
+    ///
+    /// This is useful for tabs that need to adjust their content based on the available space.
+    ///
+    /// By default it calls [`TabViewer::on_rect_changed`].
+    fn on_content_rect_changed(&mut self, tab: &mut Self::Tab, _rect: Rect) {
+        #[allow(deprecated)]
+        self.on_rect_changed(tab);
+    }
+
+    /// Called once for each tab, right before its content is shown with [`ui`](Self::ui) for the first time.
+    ///
+    /// Only the active tab of a leaf is shown, so this is useful to defer expensive setup,
+    /// like loading a file, until the user activates the tab. Tabs are told apart by [`TabViewer::id`].
+    fn on_first_shown(&mut self, _tab: &mut Self::Tab) {}
+
+    /// Called when the leaf holding the `_tab` gets [maximized](crate::DockState::maximize) (`_maximized` is `true`)
+    /// or restored (`_maximized` is `false`), or when the tab is moved into or out of a maximized leaf.
+    ///
+    /// This is called at the end of the frame in which the [`DockArea`](crate::DockArea) noticed the change.
+    fn on_maximize_changed(&mut self, _tab: &mut Self::Tab, _maximized: bool) {}
+
     /// Content of the popup under the add button. Useful for selecting what type of tab to add.
     ///
     /// This requires that [`DockArea::show_add_buttons`](crate::DockArea::show_add_buttons) and
     /// [`DockArea::show_add_popup`](crate::DockArea::show_add_popup) are set to `true`.
     fn add_popup(&mut self, _ui: &mut Ui, _surface: SurfaceIndex, _node: NodeIndex) {}
 
+    /// Whether the leaf at `_surface` and `_node` has an add button.
+    ///
+    /// This is only consulted when [`DockArea::show_add_buttons`](crate::DockArea::show_add_buttons) is `true`.
+    /// Without an add button, no space is reserved for it,
+    /// and neither [`on_add_tab`](Self::on_add_tab) nor [`add_popup`](Self::add_popup) are called for that leaf.
+    fn show_add_button(&self, _surface: SurfaceIndex, _node: NodeIndex) -> bool {
+        true
+    }
+
+    /// Widgets shown in the tab bar of the leaf at `_surface` and `_node` before its tabs, after the buttons on
+    /// its left, e.g. an icon or a menu for the leaf.
+    ///
+    /// The [`Ui`] is laid out from left to right. The tab bar keeps as much space for the widgets as they took up
+    /// in the last frame, so the tabs overflow and can be dropped only beside them. Nothing is reserved when
+    /// nothing is added, which is the default. Vertical tab bars don't show these widgets.
+    fn tab_bar_leading_ui(&mut self, _ui: &mut Ui, _surface: SurfaceIndex, _node: NodeIndex) {}
+
+    /// Widgets shown in the tab bar of the leaf at `_surface` and `_node` after its tabs, e.g. a search box or a
+    /// button splitting the leaf.
+    ///
+    /// The [`Ui`] is laid out from right to left, from the right end of the tab bar or its close all button.
+    /// Space is kept for the widgets like for [`tab_bar_leading_ui`](Self::tab_bar_leading_ui).
+    fn tab_bar_trailing_ui(&mut self, _ui: &mut Ui, _surface: SurfaceIndex, _node: NodeIndex) {}
+
     /// Sets custom style for given tab.
     fn tab_style_override(&self, _tab: &Self::Tab, _global_style: &TabStyle) -> Option<TabStyle> {
         None
     }
 
+    /// Paints the whole button of the `_tab` in the tab bar, instead of its background, title and close button.
+    ///
+    /// The button is laid out and made interactive by the [`DockArea`](crate::DockArea) as usual, the
+    /// [`TabButtonState`] tells where it is and how to paint it. Returning `Some` means the button was painted
+    /// within [`TabButtonState::rect`], and the returned [`Response`](egui::Response) is combined with the one of
+    /// the button, e.g. to tell it was clicked. Returning `None` paints the default button.
+    ///
+    /// A tab painted this way has no close button, but can still be closed with the middle mouse button or its
+    /// context menu.
+    fn tab_button(
+        &mut self,
+        _ui: &mut Ui,
+        _tab: &mut Self::Tab,
+        _state: TabButtonState,
+    ) -> Option<egui::Response> {
+        None
+    }
+
+    /// Color to tint the given tab with, e.g. to color-code tabs.
+    ///
+    /// When `Some`, the color is blended into the background of the tab in every state,
+    /// on top of the style from [`TabViewer::tab_style_override`] if there is one.
+    fn tab_color(&self, _tab: &Self::Tab) -> Option<Color32> {
+        None
+    }
+
+    /// Specifies how much of a node's area the `_tab` occupies when it's dropped next to it, creating a new split
+    /// in the direction given by `_split`.
+    ///
+    /// Returns [`None`] to use the [`DockArea::default_drop_split_fraction`](crate::DockArea::default_drop_split_fraction).
+    fn drop_split_fraction(&self, _tab: &Self::Tab, _split: Split) -> Option<f32> {
+        None
+    }
+
+    /// Specifies in which directions the leaf at `_surface` and `_node` can be split by dropping tabs next to it.
+    ///
+    /// `global` is the value set with [`DockArea::allowed_splits`](crate::DockArea::allowed_splits),
+    /// which is returned by default.
+    fn allowed_splits(
+        &self,
+        _surface: SurfaceIndex,
+        _node: NodeIndex,
+        global: AllowedSplits,
+    ) -> AllowedSplits {
+        global
+    }
+
+    /// Whether the leaf at `_surface` and `_node`, containing the `_tabs`, keeps its size when its parent is resized,
+    /// e.g. for a sidebar.
+    ///
+    /// When only one of the two nodes of a split is a fixed size leaf, the other one takes up all the space gained
+    /// or lost by the split, along its direction. Dragging the separator next to a fixed size leaf still resizes it.
+    fn fixed_size_leaf(
+        &self,
+        _surface: SurfaceIndex,
+        _node: NodeIndex,
+        _tabs: &[Self::Tab],
+    ) -> bool {
+        false
+    }
+
+    /// Title of the window at `_surface`, shown when the window is minimized and as the title of its viewport.
+    /// Icons can be added as part of the text, e.g. with an emoji.
+    ///
+    /// `_root_tab` is the active tab of the focused (or first) leaf of the window.
+    /// Returns [`None`] to use the [`title`](Self::title) of that tab, which is the default.
+    fn window_title(
+        &mut self,
+        _surface: SurfaceIndex,
+        _root_tab: &mut Self::Tab,
+    ) -> Option<WidgetText> {
+        None
+    }
+
     /// Specifies a tab's ability to be shown in a window.
     ///
     /// Returns `false` if this tab should never be turned into a window.
@@ -100,18 +346,132 @@ pub trait TabViewer {
         true
     }
 
+    /// Whether the dragged `_tab` may be dropped into the node at `_surface` and `_node`, e.g. to keep tool tabs in
+    /// a sidebar and documents in the center. `_split` is the side of the node the tab is dropped next to, or
+    /// [`None`] if it's dropped into the node itself. A tab dropped into an empty surface is dropped into its root.
+    ///
+    /// Disallowed drops are greyed out or not highlighted on the overlay, and releasing the tab over them doesn't
+    /// move it.
+    /// New windows are allowed by [`allowed_in_windows`](Self::allowed_in_windows) instead.
+    /// Tabs dragged in from another [`DockArea`](crate::DockArea) aren't checked, since they aren't
+    /// of the [`Tab`](Self::Tab) type yet.
+    ///
+    /// By default, `true` is always returned.
+    fn allowed_drop(
+        &self,
+        _tab: &Self::Tab,
+        _surface: SurfaceIndex,
+        _node: NodeIndex,
+        _split: Option<Split>,
+    ) -> bool {
+        true
+    }
+
+    /// Like [`allowed_drop`](Self::allowed_drop), but for a whole leaf with the `tabs` being dragged.
+    ///
+    /// By default, the leaf may be dropped wherever all of its tabs may be.
+    fn allowed_leaf_drop(
+        &self,
+        tabs: &[Self::Tab],
+        surface: SurfaceIndex,
+        node: NodeIndex,
+        split: Option<Split>,
+    ) -> bool {
+        tabs.iter()
+            .all(|tab| self.allowed_drop(tab, surface, node, split))
+    }
+
+    /// Converts a tab dropped from another [`DockArea`](crate::DockArea) sharing a
+    /// [`DockDragContext`](crate::DockDragContext), which shows another type of tabs.
+    ///
+    /// Return the tab back in `Err` to refuse it, which puts it back where it was dragged from.
+    /// Tabs of the same type are accepted without calling this. By default, other tabs are refused.
+    fn accept_foreign_tab(&mut self, tab: Box<dyn Any>) -> Result<Self::Tab, Box<dyn Any>> {
+        Err(tab)
+    }
+
+    /// Returns `true` while the content of the `_tab` is loading.
+    ///
+    /// A loading tab is still shown with [`ui`](Self::ui), after which its content is covered with
+    /// [`TabBodyStyle::loading_scrim`](crate::TabBodyStyle::loading_scrim) and a spinner.
+    /// See also [`DockArea::block_loading_tabs`](crate::DockArea::block_loading_tabs).
+    fn tab_loading(&self, _tab: &Self::Tab) -> bool {
+        false
+    }
+
+    /// Returns `false` while the `_tab` shows a placeholder instead of its real content.
+    ///
+    /// When this turns from `false` to `true`, the content of the tab fades in over
+    /// [`egui::Style::animation_time`], unless [`Style::animations_enabled`](crate::Style::animations_enabled)
+    /// is disabled.
+    ///
+    /// If the tab [keeps its content while hidden](Self::cache_when_hidden), what it painted when it was last
+    /// shown is repainted instead of calling [`ui`](Self::ui) while this returns `false` after it was hidden.
+    /// Nothing is repainted when this starts returning `true` then, so request a repaint, e.g. with
+    /// [`egui::Context::request_repaint`].
+    fn content_ready(&self, _tab: &Self::Tab) -> bool {
+        true
+    }
+
     /// Whether the tab body will be cleared with the color specified in
     /// [`TabBarStyle::bg_fill`](crate::TabBarStyle::bg_fill).
+    #[deprecated = "Use the `TabViewer::clear_content_background` function instead."]
     fn clear_background(&self, _tab: &Self::Tab) -> bool {
         true
     }
 
+    /// Whether the content area of the tab is cleared with [`TabBodyStyle::bg_fill`](crate::TabBodyStyle::bg_fill)
+    /// before it's shown.
+    ///
+    /// This doesn't affect the tab bar, see [`TabViewer::clear_tab_bar_background`].
+    ///
+    /// By default it calls [`TabViewer::clear_background`].
+    fn clear_content_background(&self, tab: &Self::Tab) -> bool {
+        #[allow(deprecated)]
+        self.clear_background(tab)
+    }
+
+    /// Whether the tab bar of a leaf is filled with [`TabBarStyle::bg_fill`](crate::TabBarStyle::bg_fill),
+    /// or painted by [`TabBarStyle::bg_painter`](crate::TabBarStyle::bg_painter), while `_tab` is its active tab.
+    fn clear_tab_bar_background(&self, _tab: &Self::Tab) -> bool {
+        true
+    }
+
+    /// Whether the content of `_tab` is kept around while it's hidden, so that it's shown instantly when
+    /// the tab is activated again.
+    ///
+    /// When the tab comes back, what its content painted when it was last shown is repainted as is instead of
+    /// calling [`TabViewer::ui`], for as long as [`content_ready`](Self::content_ready) returns `false`. This
+    /// hides heavy content being prepared again, e.g. a document laid out in the background, behind a stale image
+    /// of it.
+    ///
+    /// Keeping the content means copying the shapes it paints on every frame the tab is shown, and holding
+    /// on to them while it's hidden, so only enable this for tabs which are slow to lay out.
+    /// Only what's painted within the tab's own layer is kept, not popups or tooltips, and the
+    /// kept content isn't used once the content rect of the tab changed, nor while its window is fading.
+    /// The kept shapes only refer to the textures they paint by their [`TextureId`](egui::TextureId), so these
+    /// textures must outlive the kept content: free them only after this returned `false` for the tab, or after
+    /// the tab was removed, which drops what was kept for it.
+    ///
+    /// By default it's `false`.
+    fn cache_when_hidden(&self, _tab: &Self::Tab) -> bool {
+        false
+    }
+
     /// Returns `true` if the horizontal and vertical scroll bars will be shown for `tab`.
     ///
     /// By default, both scroll bars are shown.
     fn scroll_bars(&self, _tab: &Self::Tab) -> [bool; 2] {
         [true, true]
     }
+
+    /// Returns `Some` to decide which scroll bars are shown for all tabs of the leaf at `_surface` and `_node`,
+    /// instead of asking [`scroll_bars`](Self::scroll_bars) for each tab.
+    ///
+    /// By default it's `None`.
+    fn leaf_scroll_bars(&self, _surface: SurfaceIndex, _node: NodeIndex) -> Option<[bool; 2]> {
+        None
+    }
 }
 
 /// Determines what happens to a tab when a user attempts to close it.
@@ -124,3 +484,41 @@ pub enum OnCloseResponse {
     /// Ignores the close request.
     Ignore,
 }
+
+/// Describes the button of a tab painted by [`TabViewer::tab_button`].
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub struct TabButtonState {
+    /// The space allocated for the button in the tab bar.
+    pub rect: Rect,
+
+    /// Whether the tab is the active one of its leaf, or is selected along with it.
+    pub active: bool,
+
+    /// Whether the tab is the active one of the focused leaf, see
+    /// [`DockState::focused_leaf`](crate::DockState::focused_leaf).
+    pub focused: bool,
+
+    /// Whether the pointer is over the button.
+    pub hovered: bool,
+
+    /// Whether the tab is being dragged.
+    pub dragged: bool,
+}
+
+/// Describes the tab shown by [`TabViewer::ui_with_context`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct TabContext {
+    /// The surface the tab is on.
+    pub surface: SurfaceIndex,
+
+    /// The leaf the tab is in.
+    pub node: NodeIndex,
+
+    /// The position of the tab within its leaf.
+    pub tab: TabIndex,
+
+    /// Whether the leaf of the tab is the focused one, see [`DockState::focused_leaf`](crate::DockState::focused_leaf).
+    pub focused: bool,
+}